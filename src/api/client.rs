@@ -7,12 +7,23 @@ use reqwest::Client;
 
 use super::{
     gen_skype_token, gen_token, renew_refresh_token, SCOPE_CHATSVCAGG, SCOPE_GRAPH, SCOPE_IC3,
-    SCOPE_SPACES,
+    SCOPE_SPACES, TEAMS_CLIENT_ID,
 };
 use crate::cache::{Cache, TOKENS_FILE};
 use crate::config::Config;
 use crate::types::*;
 
+/// Largest attachment Graph accepts in a single request
+const MAX_INLINE_ATTACHMENT: usize = 3 * 1024 * 1024;
+/// Upload session chunk size; must be a multiple of 320 KiB
+const UPLOAD_CHUNK_SIZE: usize = 12 * 320 * 1024;
+/// Messages requested per page when listing mail
+const MAIL_PAGE_SIZE: usize = 100;
+/// Graph's limit on requests per `$batch`
+const BATCH_SIZE: usize = 20;
+/// Times a throttled batch request is retried
+const BATCH_RETRIES: usize = 3;
+
 fn get_epoch_s() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -41,12 +52,64 @@ fn strip_html_simple(s: &str) -> String {
         .to_string()
 }
 
+/// `fileAttachment` body; inline files are referenced from the HTML as `cid:<name>`
+fn file_attachment(
+    name: &str,
+    content_type: &str,
+    content: &[u8],
+    is_inline: bool,
+) -> serde_json::Value {
+    let mut request = serde_json::json!({
+        "@odata.type": "#microsoft.graph.fileAttachment",
+        "name": name,
+        "contentType": content_type,
+        "contentBytes": base64::Engine::encode(
+            &base64::engine::general_purpose::STANDARD,
+            content
+        ),
+        "isInline": is_inline
+    });
+    if is_inline {
+        request["contentId"] = serde_json::json!(name);
+    }
+    request
+}
+
+/// Fill `in_reply_to` from the message headers and sort a conversation oldest first
+fn order_thread(messages: &mut [MailMessage]) {
+    for message in messages.iter_mut() {
+        message.in_reply_to = message.internet_message_headers.as_ref().and_then(|h| {
+            h.iter()
+                .find(|h| h.name.eq_ignore_ascii_case("In-Reply-To"))
+                .map(|h| h.value.clone())
+        });
+    }
+    messages.sort_by(|a, b| {
+        let at = |m: &MailMessage| m.sent_date_time.clone().or(m.received_date_time.clone());
+        at(a).cmp(&at(b))
+    });
+}
+
+/// A reply `comment` is inserted as HTML above the quoted message; text keeps its line breaks
+fn reply_comment(body: &str, content_type: &str) -> String {
+    if content_type.eq_ignore_ascii_case("html") {
+        return body.to_string();
+    }
+    let escaped = body
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    format!("<div style=\"white-space: pre-wrap\">{}</div>", escaped)
+}
+
 /// Microsoft Teams API client
 pub struct TeamsClient {
     tokens: Arc<RwLock<TokenStore>>,
     tenant: String,
     http: Client,
     cache: Cache,
+    /// Mailbox that mail requests go to; the signed-in user's own when `None`
+    mailbox: Option<String>,
 }
 
 impl TeamsClient {
@@ -62,9 +125,54 @@ impl TeamsClient {
                 .redirect(reqwest::redirect::Policy::none())
                 .build()?,
             cache,
+            mailbox: None,
         })
     }
 
+    /// Route mail requests to a shared or delegated mailbox instead of `/me`
+    pub fn with_mailbox(mut self, mailbox: Option<&str>) -> Self {
+        self.mailbox = mailbox.map(str::to_string);
+        self
+    }
+
+    /// Mailbox mail requests go to, when it isn't the signed-in user's own
+    pub fn mailbox(&self) -> Option<&str> {
+        self.mailbox.as_deref()
+    }
+
+    /// Graph path of the mailbox, relative to the API version (`/me` or `/users/{address}`)
+    pub fn mailbox_path(&self) -> String {
+        match &self.mailbox {
+            Some(mailbox) => format!("/users/{}", urlencoding::encode(mailbox)),
+            None => "/me".to_string(),
+        }
+    }
+
+    /// Set `from` and `sender` on a JSON message sent on behalf of the mailbox
+    fn set_on_behalf(&self, message: &mut serde_json::Value, sender: Option<&str>) {
+        if let Some((from, sender)) = self.on_behalf(sender) {
+            message["from"] = serde_json::json!(from);
+            message["sender"] = serde_json::json!(sender);
+        }
+    }
+
+    /// Base URL of mail requests
+    fn mail_root(&self) -> String {
+        format!("https://graph.microsoft.com/v1.0{}", self.mailbox_path())
+    }
+
+    /// `from` and `sender` for mail sent from the routed mailbox on behalf of
+    /// `sender`; `None` (or the user's own mailbox) sends as the mailbox itself
+    fn on_behalf(&self, sender: Option<&str>) -> Option<(Recipient, Recipient)> {
+        let recipient = |address: &str| Recipient {
+            email_address: EmailAddress {
+                address: address.to_string(),
+                name: None,
+            },
+        };
+        Some((recipient(self.mailbox.as_deref()?), recipient(sender?)))
+    }
+
     /// Check if the client is authenticated
     pub fn is_authenticated(&self) -> bool {
         self.tokens.read().unwrap().refresh_token().is_some()
@@ -285,6 +393,195 @@ impl TeamsClient {
         }
     }
 
+    /// List Outlook contacts by display name, following pages up to `limit`
+    pub async fn get_contacts(&self, limit: usize) -> Result<Contacts> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let mut url = format!(
+            "https://graph.microsoft.com/v1.0/me/contacts?$top={}&$orderby=displayName",
+            limit.clamp(1, MAIL_PAGE_SIZE)
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+
+        let mut contacts = Vec::new();
+        loop {
+            let res = self.http.get(&url).headers(headers.clone()).send().await?;
+
+            if !res.status().is_success() {
+                let status = res.status();
+                let body = res.text().await?;
+                return Err(anyhow!("Failed to get contacts: {} - {}", status, body));
+            }
+
+            let body = res.text().await?;
+            let page: Contacts = serde_json::from_str(&body).context("Failed to parse contacts")?;
+            contacts.extend(page.value);
+            match page.next_link {
+                Some(next) if contacts.len() < limit => url = next,
+                _ => break,
+            }
+        }
+        contacts.truncate(limit);
+        Ok(Contacts {
+            next_link: None,
+            value: contacts,
+        })
+    }
+
+    /// Get an Outlook contact
+    pub async fn get_contact(&self, contact_id: &str) -> Result<Contact> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!(
+            "https://graph.microsoft.com/v1.0/me/contacts/{}",
+            contact_id
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+
+        let res = self.http.get(&url).headers(headers).send().await?;
+
+        if res.status().is_success() {
+            let body = res.text().await?;
+            serde_json::from_str(&body).context("Failed to parse contact")
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!("Failed to get contact: {} - {}", status, body))
+        }
+    }
+
+    /// Create an Outlook contact
+    pub async fn create_contact(&self, contact: &Contact) -> Result<Contact> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = "https://graph.microsoft.com/v1.0/me/contacts";
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+        headers.insert(
+            HeaderName::from_static("content-type"),
+            HeaderValue::from_static("application/json"),
+        );
+
+        let res = self
+            .http
+            .post(url)
+            .headers(headers)
+            .body(serde_json::to_string(contact)?)
+            .send()
+            .await?;
+
+        if res.status().is_success() {
+            let body = res.text().await?;
+            serde_json::from_str(&body).context("Failed to parse contact")
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!("Failed to create contact: {} - {}", status, body))
+        }
+    }
+
+    /// Update an Outlook contact; only the fields set in `changes` are sent
+    pub async fn update_contact(&self, contact_id: &str, changes: &Contact) -> Result<Contact> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!(
+            "https://graph.microsoft.com/v1.0/me/contacts/{}",
+            contact_id
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+        headers.insert(
+            HeaderName::from_static("content-type"),
+            HeaderValue::from_static("application/json"),
+        );
+
+        let res = self
+            .http
+            .patch(&url)
+            .headers(headers)
+            .body(serde_json::to_string(changes)?)
+            .send()
+            .await?;
+
+        if res.status().is_success() {
+            let body = res.text().await?;
+            serde_json::from_str(&body).context("Failed to parse contact")
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!("Failed to update contact: {} - {}", status, body))
+        }
+    }
+
+    /// Delete an Outlook contact
+    pub async fn delete_contact(&self, contact_id: &str) -> Result<()> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!(
+            "https://graph.microsoft.com/v1.0/me/contacts/{}",
+            contact_id
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+
+        let res = self.http.delete(&url).headers(headers).send().await?;
+
+        if res.status().is_success() {
+            Ok(())
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!("Failed to delete contact: {} - {}", status, body))
+        }
+    }
+
+    /// People ranked by relevance to the signed-in user (who they mail and meet with),
+    /// optionally matching a name or address
+    pub async fn get_people(&self, query: Option<&str>, limit: usize) -> Result<People> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let mut url = format!("https://graph.microsoft.com/v1.0/me/people?$top={}", limit);
+        if let Some(query) = query {
+            url.push_str(&format!(
+                "&$search={}",
+                urlencoding::encode(&search_phrase(query))
+            ));
+        }
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+
+        let res = self.http.get(&url).headers(headers).send().await?;
+
+        if res.status().is_success() {
+            let body = res.text().await?;
+            serde_json::from_str(&body).context("Failed to parse people")
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!("Failed to get people: {} - {}", status, body))
+        }
+    }
+
     /// Get a user by their ID (object_id from MRI)
     pub async fn get_user_by_id(&self, user_id: &str) -> Result<Option<Profile>> {
         let token = self.get_token(SCOPE_GRAPH).await?;
@@ -321,8 +618,6 @@ impl TeamsClient {
         thread_id: &str,
         message_id: Option<u64>,
     ) -> Result<Conversations> {
-        let token = self.get_token(SCOPE_IC3).await?;
-
         let thread_part = match message_id {
             Some(msg_id) => format!("{};messageid={}", thread_id, msg_id),
             None => thread_id.to_string(),
@@ -332,6 +627,12 @@ impl TeamsClient {
             "https://teams.microsoft.com/api/chatsvc/emea/v1/users/ME/conversations/{}/messages?pageSize=200",
             thread_part
         );
+        self.get_conversations_page(&url).await
+    }
+
+    /// Get a page of conversation messages from a paging link of an earlier page
+    pub async fn get_conversations_page(&self, url: &str) -> Result<Conversations> {
+        let token = self.get_token(SCOPE_IC3).await?;
 
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -339,7 +640,7 @@ impl TeamsClient {
             HeaderValue::from_str(&format!("Bearer {}", token.value))?,
         );
 
-        let res = self.http.get(&url).headers(headers).send().await?;
+        let res = self.http.get(url).headers(headers).send().await?;
 
         if res.status().is_success() {
             let body = res.text().await?;
@@ -355,6 +656,40 @@ impl TeamsClient {
         }
     }
 
+    /// Get a single message of a conversation by its ID
+    pub async fn get_conversation_message(
+        &self,
+        thread_id: &str,
+        message_id: &str,
+    ) -> Result<Option<Message>> {
+        let token = self.get_token(SCOPE_IC3).await?;
+        let url = format!(
+            "https://teams.microsoft.com/api/chatsvc/emea/v1/users/ME/conversations/{}/messages/{}",
+            thread_id, message_id
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+
+        let res = self.http.get(&url).headers(headers).send().await?;
+
+        if res.status().is_success() {
+            let body = res.text().await?;
+            Ok(Some(
+                serde_json::from_str(&body).context("Failed to parse message")?,
+            ))
+        } else if res.status() == 404 {
+            Ok(None)
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!("Failed to get message: {} - {}", status, body))
+        }
+    }
+
     /// Get team channel conversations
     pub async fn get_team_conversations(
         &self,
@@ -731,6 +1066,31 @@ impl TeamsClient {
         conversation_id: &str,
         content: &str,
         subject: Option<&str>,
+    ) -> Result<String> {
+        self.post_message(conversation_id, content, subject, None)
+            .await
+    }
+
+    /// Send a message carrying card attachments to a chat or channel conversation.
+    /// `content` is the HTML body shown alongside the cards and in notifications.
+    pub async fn send_card_message(
+        &self,
+        conversation_id: &str,
+        content: &str,
+        attachments: &[serde_json::Value],
+        subject: Option<&str>,
+    ) -> Result<String> {
+        self.post_message(conversation_id, content, subject, Some(attachments))
+            .await
+    }
+
+    /// Post a message to a conversation, with card attachments when given
+    async fn post_message(
+        &self,
+        conversation_id: &str,
+        content: &str,
+        subject: Option<&str>,
+        attachments: Option<&[serde_json::Value]>,
     ) -> Result<String> {
         let token = self.get_token(SCOPE_IC3).await?;
         let me = self.get_me().await?;
@@ -752,6 +1112,17 @@ impl TeamsClient {
             .format("%Y-%m-%dT%H:%M:%S%.3fZ")
             .to_string();
 
+        // chatsvc expects `properties.cards` as a JSON-encoded string
+        let cards: Vec<serde_json::Value> = attachments
+            .unwrap_or_default()
+            .iter()
+            .map(|a| {
+                let mut card = a.clone();
+                card["cardClientId"] = serde_json::json!(rand::random::<u64>().to_string());
+                card
+            })
+            .collect();
+
         let body = serde_json::json!({
             "id": "-1",
             "type": "Message",
@@ -773,7 +1144,7 @@ impl TeamsClient {
                 "importance": "",
                 "subject": subject,
                 "title": "",
-                "cards": "[]",
+                "cards": serde_json::to_string(&cards)?,
                 "links": "[]",
                 "mentions": "[]",
                 "onbehalfof": null,
@@ -798,15 +1169,32 @@ impl TeamsClient {
         } else {
             let status = res.status();
             let body = res.text().await?;
-            Err(anyhow!("Failed to send message: {} - {}", status, body))
+            let what = if attachments.is_some() {
+                "card"
+            } else {
+                "message"
+            };
+            Err(anyhow!("Failed to send {}: {} - {}", what, status, body))
         }
     }
 
-    /// Create a new chat (1:1 or group) using Graph API
-    pub async fn create_chat(&self, members: Vec<&str>, topic: Option<&str>) -> Result<GraphChat> {
-        let token = self.get_token(SCOPE_GRAPH).await?;
+    /// Submit an Adaptive Card action (Action.Submit / Action.Execute) back to the bot
+    /// that posted the card
+    pub async fn submit_card_action(
+        &self,
+        conversation_id: &str,
+        message_id: &str,
+        bot_id: &str,
+        verb: Option<&str>,
+        value: serde_json::Value,
+    ) -> Result<()> {
+        let token = self.get_token(SCOPE_IC3).await?;
         let me = self.get_me().await?;
-        let url = "https://graph.microsoft.com/v1.0/chats";
+
+        let url = format!(
+            "https://teams.microsoft.com/api/chatsvc/emea/v1/users/ME/conversations/{}/messages/{}/invoke",
+            conversation_id, message_id
+        );
 
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -818,26 +1206,89 @@ impl TeamsClient {
             HeaderValue::from_static("application/json"),
         );
 
-        let chat_type = if members.len() == 1 {
-            "oneOnOne"
-        } else {
-            "group"
+        // Action.Execute goes through the universal action invoke, Action.Submit
+        // is delivered to the bot as a message carrying the submitted value
+        let (name, value) = match verb {
+            Some(verb) => (
+                "adaptiveCard/action",
+                serde_json::json!({
+                    "action": {
+                        "type": "Action.Execute",
+                        "verb": verb,
+                        "data": value
+                    }
+                }),
+            ),
+            None => ("message/submitAction", value),
         };
 
-        // Build members list including self
-        let mut all_members: Vec<serde_json::Value> = vec![serde_json::json!({
-            "@odata.type": "#microsoft.graph.aadUserConversationMember",
-            "roles": ["owner"],
-            "user@odata.bind": format!("https://graph.microsoft.com/v1.0/users('{}')", me.id)
-        })];
-
-        for member in members {
-            all_members.push(serde_json::json!({
-                "@odata.type": "#microsoft.graph.aadUserConversationMember",
-                "roles": ["owner"],
-                "user@odata.bind": format!("https://graph.microsoft.com/v1.0/users('{}')", member)
-            }));
-        }
+        let body = serde_json::json!({
+            "type": "invoke",
+            "name": name,
+            "from": format!("8:orgid:{}", me.id),
+            "recipient": bot_id,
+            "conversationid": conversation_id,
+            "replyToId": message_id,
+            "value": value
+        });
+
+        let res = self
+            .http
+            .post(&url)
+            .headers(headers)
+            .body(body.to_string())
+            .send()
+            .await?;
+
+        if res.status().is_success() || res.status().as_u16() == 202 {
+            Ok(())
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!(
+                "Failed to submit card action: {} - {}",
+                status,
+                body
+            ))
+        }
+    }
+
+    /// Create a new chat (1:1 or group) using Graph API
+    pub async fn create_chat(&self, members: Vec<&str>, topic: Option<&str>) -> Result<GraphChat> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let me = self.get_me().await?;
+        let url = "https://graph.microsoft.com/v1.0/chats";
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+        headers.insert(
+            HeaderName::from_static("content-type"),
+            HeaderValue::from_static("application/json"),
+        );
+
+        let chat_type = if members.len() == 1 {
+            "oneOnOne"
+        } else {
+            "group"
+        };
+
+        // Build members list including self
+        let mut all_members: Vec<serde_json::Value> = vec![serde_json::json!({
+            "@odata.type": "#microsoft.graph.aadUserConversationMember",
+            "roles": ["owner"],
+            "user@odata.bind": format!("https://graph.microsoft.com/v1.0/users('{}')", me.id)
+        })];
+
+        for member in members {
+            all_members.push(serde_json::json!({
+                "@odata.type": "#microsoft.graph.aadUserConversationMember",
+                "roles": ["owner"],
+                "user@odata.bind": format!("https://graph.microsoft.com/v1.0/users('{}')", member)
+            }));
+        }
 
         let mut body = serde_json::json!({
             "chatType": chat_type,
@@ -1170,6 +1621,78 @@ impl TeamsClient {
         }
     }
 
+    /// Set the current user's preferred presence (what they pick in Teams)
+    pub async fn set_preferred_presence(&self, request: &SetPresenceRequest) -> Result<()> {
+        self.post_presence_action("setUserPreferredPresence", &serde_json::to_value(request)?)
+            .await
+    }
+
+    /// Clear the current user's preferred presence
+    pub async fn clear_preferred_presence(&self) -> Result<()> {
+        self.post_presence_action("clearUserPreferredPresence", &serde_json::json!({}))
+            .await
+    }
+
+    /// Set the presence of this app's session, aggregated by Teams with the user's other sessions
+    pub async fn set_session_presence(
+        &self,
+        availability: &str,
+        activity: &str,
+        expiration: Option<String>,
+    ) -> Result<()> {
+        let request = SetPresenceRequest {
+            session_id: Some(TEAMS_CLIENT_ID.to_string()),
+            availability: availability.to_string(),
+            activity: activity.to_string(),
+            expiration_duration: expiration,
+        };
+        self.post_presence_action("setPresence", &serde_json::to_value(&request)?)
+            .await
+    }
+
+    /// Clear the presence of this app's session
+    pub async fn clear_session_presence(&self) -> Result<()> {
+        let body = serde_json::json!({ "sessionId": TEAMS_CLIENT_ID });
+        self.post_presence_action("clearPresence", &body).await
+    }
+
+    /// Set (or clear, with an empty message) the current user's status message
+    pub async fn set_status_message(&self, status: &PresenceStatusMessage) -> Result<()> {
+        let body = serde_json::json!({ "statusMessage": status });
+        self.post_presence_action("setStatusMessage", &body).await
+    }
+
+    async fn post_presence_action(&self, action: &str, body: &serde_json::Value) -> Result<()> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!("https://graph.microsoft.com/v1.0/me/presence/{}", action);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+        headers.insert(
+            HeaderName::from_static("content-type"),
+            HeaderValue::from_static("application/json"),
+        );
+
+        let res = self
+            .http
+            .post(&url)
+            .headers(headers)
+            .body(serde_json::to_string(body)?)
+            .send()
+            .await?;
+
+        if res.status().is_success() {
+            Ok(())
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!("Failed to {}: {} - {}", action, status, body))
+        }
+    }
+
     /// Get presence for multiple users by their IDs
     pub async fn get_presence(&self, user_ids: Vec<&str>) -> Result<GraphPresences> {
         let token = self.get_token(SCOPE_GRAPH).await?;
@@ -1209,10 +1732,26 @@ impl TeamsClient {
 
     // ==================== OUTLOOK MAIL ====================
 
-    /// Get mail folders
+    /// Get top-level mail folders
     pub async fn get_mail_folders(&self) -> Result<MailFolders> {
+        self.get_folder_pages(format!("{}/mailFolders?$top=100", self.mail_root()))
+            .await
+    }
+
+    /// Get the direct subfolders of a mail folder (ID or well-known name)
+    pub async fn get_child_mail_folders(&self, folder_id: &str) -> Result<MailFolders> {
+        self.get_folder_pages(format!(
+            "{}/mailFolders/{}/childFolders?$top=100",
+            self.mail_root(),
+            folder_id
+        ))
+        .await
+    }
+
+    /// Get a mail folder (ID or well-known name) with its item counts
+    pub async fn get_mail_folder(&self, folder_id: &str) -> Result<MailFolder> {
         let token = self.get_token(SCOPE_GRAPH).await?;
-        let url = "https://graph.microsoft.com/v1.0/me/mailFolders";
+        let url = format!("{}/mailFolders/{}", self.mail_root(), folder_id);
 
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -1220,96 +1759,104 @@ impl TeamsClient {
             HeaderValue::from_str(&format!("Bearer {}", token.value))?,
         );
 
-        let res = self.http.get(url).headers(headers).send().await?;
+        let res = self.http.get(&url).headers(headers).send().await?;
 
         if res.status().is_success() {
             let body = res.text().await?;
-            serde_json::from_str(&body).context("Failed to parse mail folders")
+            serde_json::from_str(&body).context("Failed to parse mail folder")
         } else {
             let status = res.status();
             let body = res.text().await?;
-            Err(anyhow!("Failed to get mail folders: {} - {}", status, body))
+            Err(anyhow!("Failed to get mail folder: {} - {}", status, body))
         }
     }
 
-    /// Get mail messages from inbox or a specific folder
-    pub async fn get_mail_messages(
-        &self,
-        folder: Option<&str>,
-        limit: usize,
-    ) -> Result<MailMessages> {
+    async fn get_folder_pages(&self, url: String) -> Result<MailFolders> {
         let token = self.get_token(SCOPE_GRAPH).await?;
 
-        let url = match folder {
-            Some(f) => format!(
-                "https://graph.microsoft.com/v1.0/me/mailFolders/{}/messages?$top={}&$orderby=receivedDateTime desc",
-                f, limit
-            ),
-            None => format!(
-                "https://graph.microsoft.com/v1.0/me/messages?$top={}&$orderby=receivedDateTime desc",
-                limit
-            ),
-        };
-
         let mut headers = HeaderMap::new();
         headers.insert(
             HeaderName::from_static("authorization"),
             HeaderValue::from_str(&format!("Bearer {}", token.value))?,
         );
 
-        let res = self.http.get(&url).headers(headers).send().await?;
+        let mut url = url;
+        let mut folders: Option<MailFolders> = None;
+        loop {
+            let res = self.http.get(&url).headers(headers.clone()).send().await?;
+
+            if !res.status().is_success() {
+                let status = res.status();
+                let body = res.text().await?;
+                return Err(anyhow!("Failed to get mail folders: {} - {}", status, body));
+            }
 
-        if res.status().is_success() {
-            let body = res.text().await?;
-            serde_json::from_str(&body).context("Failed to parse mail messages")
-        } else {
-            let status = res.status();
             let body = res.text().await?;
-            Err(anyhow!(
-                "Failed to get mail messages: {} - {}",
-                status,
-                body
-            ))
+            let page: MailFolders =
+                serde_json::from_str(&body).context("Failed to parse mail folders")?;
+            let next = page.next_link.clone();
+            match folders.as_mut() {
+                Some(all) => all.value.extend(page.value),
+                None => folders = Some(page),
+            }
+            match next {
+                Some(next) => url = next,
+                None => break,
+            }
         }
+        Ok(folders.expect("at least one page was fetched"))
     }
 
-    /// Get a specific mail message
-    pub async fn get_mail_message(&self, message_id: &str) -> Result<MailMessage> {
+    /// Create a mail folder, at the top level or under `parent_id`
+    pub async fn create_mail_folder(
+        &self,
+        parent_id: Option<&str>,
+        name: &str,
+    ) -> Result<MailFolder> {
         let token = self.get_token(SCOPE_GRAPH).await?;
-        let url = format!(
-            "https://graph.microsoft.com/v1.0/me/messages/{}",
-            message_id
-        );
+        let url = match parent_id {
+            Some(parent) => format!("{}/mailFolders/{}/childFolders", self.mail_root(), parent),
+            None => format!("{}/mailFolders", self.mail_root()),
+        };
 
         let mut headers = HeaderMap::new();
         headers.insert(
             HeaderName::from_static("authorization"),
             HeaderValue::from_str(&format!("Bearer {}", token.value))?,
         );
+        headers.insert(
+            HeaderName::from_static("content-type"),
+            HeaderValue::from_static("application/json"),
+        );
 
-        let res = self.http.get(&url).headers(headers).send().await?;
+        let request = serde_json::json!({ "displayName": name });
+
+        let res = self
+            .http
+            .post(&url)
+            .headers(headers)
+            .body(serde_json::to_string(&request)?)
+            .send()
+            .await?;
 
         if res.status().is_success() {
             let body = res.text().await?;
-            serde_json::from_str(&body).context("Failed to parse mail message")
+            serde_json::from_str(&body).context("Failed to parse mail folder")
         } else {
             let status = res.status();
             let body = res.text().await?;
-            Err(anyhow!("Failed to get mail message: {} - {}", status, body))
+            Err(anyhow!(
+                "Failed to create mail folder: {} - {}",
+                status,
+                body
+            ))
         }
     }
 
-    /// Send an email
-    pub async fn send_mail(
-        &self,
-        to: Vec<&str>,
-        subject: &str,
-        body: &str,
-        cc: Option<Vec<&str>>,
-        content_type: &str,
-    ) -> Result<()> {
+    /// Rename a mail folder
+    pub async fn rename_mail_folder(&self, folder_id: &str, name: &str) -> Result<MailFolder> {
         let token = self.get_token(SCOPE_GRAPH).await?;
-        let url = "https://graph.microsoft.com/v1.0/me/sendMail";
+        let url = format!("{}/mailFolders/{}", self.mail_root(), folder_id);
 
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -1321,65 +1868,34 @@ impl TeamsClient {
             HeaderValue::from_static("application/json"),
         );
 
-        let to_recipients: Vec<Recipient> = to
-            .iter()
-            .map(|email| Recipient {
-                email_address: EmailAddress {
-                    address: email.to_string(),
-                    name: None,
-                },
-            })
-            .collect();
-
-        let cc_recipients: Option<Vec<Recipient>> = cc.map(|emails| {
-            emails
-                .iter()
-                .map(|email| Recipient {
-                    email_address: EmailAddress {
-                        address: email.to_string(),
-                        name: None,
-                    },
-                })
-                .collect()
-        });
-
-        let request = SendMailRequest {
-            message: SendMailMessage {
-                subject: subject.to_string(),
-                body: ItemBody {
-                    content_type: content_type.to_string(),
-                    content: body.to_string(),
-                },
-                to_recipients,
-                cc_recipients,
-            },
-            save_to_sent_items: true,
-        };
+        let request = serde_json::json!({ "displayName": name });
 
         let res = self
             .http
-            .post(url)
+            .patch(&url)
             .headers(headers)
             .body(serde_json::to_string(&request)?)
             .send()
             .await?;
 
-        if res.status().is_success() || res.status().as_u16() == 202 {
-            Ok(())
+        if res.status().is_success() {
+            let body = res.text().await?;
+            serde_json::from_str(&body).context("Failed to parse mail folder")
         } else {
             let status = res.status();
             let body = res.text().await?;
-            Err(anyhow!("Failed to send mail: {} - {}", status, body))
+            Err(anyhow!(
+                "Failed to rename mail folder: {} - {}",
+                status,
+                body
+            ))
         }
     }
 
-    /// Search mail messages
-    pub async fn search_mail(&self, query: &str, limit: usize) -> Result<MailMessages> {
+    /// Delete a mail folder (its contents move to Deleted Items)
+    pub async fn delete_mail_folder(&self, folder_id: &str) -> Result<()> {
         let token = self.get_token(SCOPE_GRAPH).await?;
-        let url = format!(
-            "https://graph.microsoft.com/v1.0/me/messages?$search=\"{}\"\u{0026}$top={}",
-            query, limit
-        );
+        let url = format!("{}/mailFolders/{}", self.mail_root(), folder_id);
 
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -1387,46 +1903,409 @@ impl TeamsClient {
             HeaderValue::from_str(&format!("Bearer {}", token.value))?,
         );
 
-        let res = self.http.get(&url).headers(headers).send().await?;
+        let res = self.http.delete(&url).headers(headers).send().await?;
 
         if res.status().is_success() {
-            let body = res.text().await?;
-            serde_json::from_str(&body).context("Failed to parse mail search results")
+            Ok(())
         } else {
             let status = res.status();
             let body = res.text().await?;
-            Err(anyhow!("Failed to search mail: {} - {}", status, body))
+            Err(anyhow!(
+                "Failed to delete mail folder: {} - {}",
+                status,
+                body
+            ))
         }
     }
 
-    /// Search calendar events specifically
-    pub async fn search_calendar(&self, query: &str, limit: usize) -> Result<CalendarEvents> {
-        let token = self.get_token(SCOPE_GRAPH).await?;
-        // Calendar events don't support $search well, so we use $filter with contains
-        // Using lowercase for case-insensitive contains if supported by the endpoint,
-        // or just providing the query as is.
-        let url = format!(
-            "https://graph.microsoft.com/v1.0/me/events?$filter=contains(subject, '{}')&$top={}",
-            query, limit
+    /// Get mail messages from inbox or a specific folder
+    pub async fn get_mail_messages(
+        &self,
+        folder: Option<&str>,
+        filter: &MailFilter,
+        limit: usize,
+    ) -> Result<MailMessages> {
+        let base = match folder {
+            Some(f) => format!("{}/mailFolders/{}/messages", self.mail_root(), f),
+            None => format!("{}/messages", self.mail_root()),
+        };
+        let mut url = format!(
+            "{}?$top={}&$orderby=receivedDateTime desc",
+            base,
+            limit.clamp(1, MAIL_PAGE_SIZE)
         );
+        if let Some(odata) = filter.to_odata() {
+            url.push_str(&format!("&$filter={}", urlencoding::encode(&odata)));
+        }
+
+        self.get_mail_pages(url, limit, "mail messages").await
+    }
 
+    /// Fetch message pages, following `@odata.nextLink` until `limit` messages
+    async fn get_mail_pages(&self, url: String, limit: usize, what: &str) -> Result<MailMessages> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
         let mut headers = HeaderMap::new();
         headers.insert(
             HeaderName::from_static("authorization"),
             HeaderValue::from_str(&format!("Bearer {}", token.value))?,
         );
 
-        let res = self.http.get(&url).headers(headers).send().await?;
+        let mut url = url;
+        let mut messages: Option<MailMessages> = None;
+        loop {
+            let res = self.http.get(&url).headers(headers.clone()).send().await?;
 
-        if res.status().is_success() {
-            let body = res.text().await?;
-            serde_json::from_str(&body).context("Failed to parse calendar search results")
-        } else {
-            let status = res.status();
-            let body = res.text().await?;
-            Err(anyhow!("Failed to search calendar: {} - {}", status, body))
-        }
-    }
+            if !res.status().is_success() {
+                let status = res.status();
+                let body = res.text().await?;
+                return Err(anyhow!("Failed to get {}: {} - {}", what, status, body));
+            }
+
+            let body = res.text().await?;
+            let page: MailMessages =
+                serde_json::from_str(&body).with_context(|| format!("Failed to parse {}", what))?;
+            let next = page.next_link.clone();
+            let collected = match messages.as_mut() {
+                Some(all) => {
+                    all.value.extend(page.value);
+                    all.next_link = page.next_link;
+                    all
+                }
+                None => messages.insert(page),
+            };
+
+            match next {
+                Some(next) if collected.value.len() < limit => url = next,
+                _ => {
+                    collected.value.truncate(limit);
+                    break;
+                }
+            }
+        }
+        Ok(messages.expect("at least one page was fetched"))
+    }
+
+    /// Get a specific mail message
+    pub async fn get_mail_message(&self, message_id: &str) -> Result<MailMessage> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!("{}/messages/{}", self.mail_root(), message_id);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+
+        let res = self.http.get(&url).headers(headers).send().await?;
+
+        if res.status().is_success() {
+            let body = res.text().await?;
+            serde_json::from_str(&body).context("Failed to parse mail message")
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!("Failed to get mail message: {} - {}", status, body))
+        }
+    }
+
+    /// List the IDs and receive times of a folder's messages, oldest first
+    pub async fn get_mail_message_ids(&self, folder_id: &str) -> Result<Vec<MailMessage>> {
+        let url = format!(
+            "{}/mailFolders/{}/messages?$top={}&$select=id,receivedDateTime,isRead,subject&$orderby=receivedDateTime asc",
+            self.mail_root(), folder_id, MAIL_PAGE_SIZE
+        );
+        Ok(self
+            .get_mail_pages(url, usize::MAX, "mail messages")
+            .await?
+            .value)
+    }
+
+    /// Run a mail delta query on a folder, from a saved delta link or from the
+    /// folder's current state (without listing it), following pages until Graph
+    /// returns the next delta link. Returns the changed
+    /// messages (removed ones carry `removed`) and that link, or `None` when the
+    /// saved link has expired and sync must restart.
+    pub async fn get_mail_delta(
+        &self,
+        folder_id: &str,
+        delta_link: Option<&str>,
+    ) -> Result<Option<(Vec<MailMessage>, String)>> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let mut url = match delta_link {
+            Some(link) => link.to_string(),
+            None => format!(
+                "{}/mailFolders/{}/messages/delta?$select=subject,from,isRead,receivedDateTime,hasAttachments,importance&$deltatoken=latest",
+                self.mail_root(), folder_id
+            ),
+        };
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+        headers.insert(
+            HeaderName::from_static("prefer"),
+            HeaderValue::from_static("odata.maxpagesize=100"),
+        );
+
+        let mut messages = Vec::new();
+        loop {
+            let res = self.http.get(&url).headers(headers.clone()).send().await?;
+
+            if res.status().as_u16() == 410 {
+                return Ok(None);
+            }
+            if !res.status().is_success() {
+                let status = res.status();
+                let body = res.text().await?;
+                return Err(anyhow!("Failed to sync mail: {} - {}", status, body));
+            }
+
+            let body = res.text().await?;
+            let page: MailMessages =
+                serde_json::from_str(&body).context("Failed to parse mail delta")?;
+            messages.extend(page.value);
+            match (page.next_link, page.delta_link) {
+                (Some(next), _) => url = next,
+                (None, Some(delta)) => return Ok(Some((messages, delta))),
+                (None, None) => return Err(anyhow!("Mail delta ended without a delta link")),
+            }
+        }
+    }
+
+    /// Get the RFC 822 (MIME) source of a message
+    pub async fn get_mail_mime(&self, message_id: &str) -> Result<Vec<u8>> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!("{}/messages/{}/$value", self.mail_root(), message_id);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+
+        let res = self.http.get(&url).headers(headers).send().await?;
+
+        if res.status().is_success() {
+            Ok(res.bytes().await?.to_vec())
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!(
+                "Failed to get message source: {} - {}",
+                status,
+                body
+            ))
+        }
+    }
+
+    /// Upload an RFC 822 message into a folder. Graph stores MIME uploads as drafts.
+    pub async fn import_mail_mime(&self, folder_id: &str, mime: &[u8]) -> Result<MailMessage> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!("{}/mailFolders/{}/messages", self.mail_root(), folder_id);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+        // MIME uploads are sent base64-encoded as text/plain
+        headers.insert(
+            HeaderName::from_static("content-type"),
+            HeaderValue::from_static("text/plain"),
+        );
+
+        let res = self
+            .http
+            .post(&url)
+            .headers(headers)
+            .body(base64::Engine::encode(
+                &base64::engine::general_purpose::STANDARD,
+                mime,
+            ))
+            .send()
+            .await?;
+
+        if res.status().is_success() {
+            let body = res.text().await?;
+            serde_json::from_str(&body).context("Failed to parse imported message")
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!("Failed to import message: {} - {}", status, body))
+        }
+    }
+
+    /// Get every message in a conversation, across all folders, oldest first,
+    /// with plain-text bodies
+    pub async fn get_conversation_messages(
+        &self,
+        conversation_id: &str,
+    ) -> Result<Vec<MailMessage>> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let filter = format!(
+            "conversationId eq '{}'",
+            conversation_id.replace('\'', "''")
+        );
+        // Combining this filter with $orderby is rejected, so sort locally
+        let mut url = format!(
+            "{}/messages?$filter={}&$top=50&$select=id,subject,from,toRecipients,ccRecipients,bccRecipients,receivedDateTime,sentDateTime,isRead,isDraft,hasAttachments,importance,webLink,conversationId,internetMessageId,internetMessageHeaders,body,uniqueBody,bodyPreview",
+            self.mail_root(), urlencoding::encode(&filter)
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+        // Plain-text bodies keep line breaks for terminal rendering
+        headers.insert(
+            HeaderName::from_static("prefer"),
+            HeaderValue::from_static("outlook.body-content-type=\"text\""),
+        );
+
+        let mut messages = Vec::new();
+        loop {
+            let res = self.http.get(&url).headers(headers.clone()).send().await?;
+
+            if !res.status().is_success() {
+                let status = res.status();
+                let body = res.text().await?;
+                return Err(anyhow!("Failed to get conversation: {} - {}", status, body));
+            }
+
+            let body = res.text().await?;
+            let page: MailMessages =
+                serde_json::from_str(&body).context("Failed to parse conversation messages")?;
+            messages.extend(page.value);
+            match page.next_link {
+                Some(next) => url = next,
+                None => break,
+            }
+        }
+
+        order_thread(&mut messages);
+        Ok(messages)
+    }
+
+    /// Send an email
+    pub async fn send_mail(
+        &self,
+        to: Vec<&str>,
+        subject: &str,
+        body: &str,
+        cc: Option<Vec<&str>>,
+        content_type: &str,
+        sender: Option<&str>,
+    ) -> Result<()> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!("{}/sendMail", self.mail_root());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+        headers.insert(
+            HeaderName::from_static("content-type"),
+            HeaderValue::from_static("application/json"),
+        );
+
+        let to_recipients: Vec<Recipient> = to
+            .iter()
+            .map(|email| Recipient {
+                email_address: EmailAddress {
+                    address: email.to_string(),
+                    name: None,
+                },
+            })
+            .collect();
+
+        let cc_recipients: Option<Vec<Recipient>> = cc.map(|emails| {
+            emails
+                .iter()
+                .map(|email| Recipient {
+                    email_address: EmailAddress {
+                        address: email.to_string(),
+                        name: None,
+                    },
+                })
+                .collect()
+        });
+
+        let on_behalf = self.on_behalf(sender);
+        let request = SendMailRequest {
+            message: SendMailMessage {
+                subject: subject.to_string(),
+                body: ItemBody {
+                    content_type: content_type.to_string(),
+                    content: body.to_string(),
+                },
+                to_recipients,
+                cc_recipients,
+                from: on_behalf.as_ref().map(|(from, _)| from.clone()),
+                sender: on_behalf.map(|(_, sender)| sender),
+            },
+            save_to_sent_items: true,
+        };
+
+        let res = self
+            .http
+            .post(url)
+            .headers(headers)
+            .body(serde_json::to_string(&request)?)
+            .send()
+            .await?;
+
+        if res.status().is_success() || res.status().as_u16() == 202 {
+            Ok(())
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!("Failed to send mail: {} - {}", status, body))
+        }
+    }
+
+    /// Search mail messages
+    pub async fn search_mail(&self, query: &str, limit: usize) -> Result<MailMessages> {
+        let url = format!(
+            "{}/messages?$search={}&$top={}",
+            self.mail_root(),
+            urlencoding::encode(&search_phrase(query)),
+            limit.clamp(1, MAIL_PAGE_SIZE)
+        );
+        self.get_mail_pages(url, limit, "mail search results").await
+    }
+
+    /// Search calendar events specifically
+    pub async fn search_calendar(&self, query: &str, limit: usize) -> Result<CalendarEvents> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        // Calendar events don't support $search well, so we use $filter with contains
+        // Using lowercase for case-insensitive contains if supported by the endpoint,
+        // or just providing the query as is.
+        let url = format!(
+            "https://graph.microsoft.com/v1.0/me/events?$filter=contains(subject, '{}')&$top={}",
+            query, limit
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+
+        let res = self.http.get(&url).headers(headers).send().await?;
+
+        if res.status().is_success() {
+            let body = res.text().await?;
+            serde_json::from_str(&body).context("Failed to parse calendar search results")
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!("Failed to search calendar: {} - {}", status, body))
+        }
+    }
 
     /// Create a draft email message
     pub async fn create_draft(
@@ -1436,9 +2315,10 @@ impl TeamsClient {
         body: &str,
         cc: Option<Vec<&str>>,
         content_type: &str,
+        sender: Option<&str>,
     ) -> Result<MailMessage> {
         let token = self.get_token(SCOPE_GRAPH).await?;
-        let url = "https://graph.microsoft.com/v1.0/me/messages";
+        let url = format!("{}/messages", self.mail_root());
 
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -1472,6 +2352,7 @@ impl TeamsClient {
                 .collect()
         });
 
+        let on_behalf = self.on_behalf(sender);
         let request = CreateDraftRequest {
             subject: subject.to_string(),
             body: ItemBody {
@@ -1480,6 +2361,8 @@ impl TeamsClient {
             },
             to_recipients,
             cc_recipients,
+            from: on_behalf.as_ref().map(|(from, _)| from.clone()),
+            sender: on_behalf.map(|(_, sender)| sender),
         };
 
         let res = self
@@ -1501,20 +2384,20 @@ impl TeamsClient {
     }
 
     /// Reply to an email
+    #[allow(clippy::too_many_arguments)]
     pub async fn reply_mail(
         &self,
         message_id: &str,
         body: &str,
+        content_type: &str,
         reply_all: bool,
         cc: Option<Vec<&str>>,
         bcc: Option<Vec<&str>>,
+        sender: Option<&str>,
     ) -> Result<()> {
         let token = self.get_token(SCOPE_GRAPH).await?;
         let endpoint = if reply_all { "replyAll" } else { "reply" };
-        let url = format!(
-            "https://graph.microsoft.com/v1.0/me/messages/{}/{}",
-            message_id, endpoint
-        );
+        let url = format!("{}/messages/{}/{}", self.mail_root(), message_id, endpoint);
 
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -1552,13 +2435,9 @@ impl TeamsClient {
                 .collect()
         });
 
-        // Build request with optional CC and BCC
-        let mut message = serde_json::json!({
-            "body": {
-                "contentType": "Text",
-                "content": body
-            }
-        });
+        // Build request with optional CC and BCC. The body goes in `comment`, as for
+        // reply drafts, so the quoted original is kept
+        let mut message = serde_json::json!({});
 
         if let Some(cc) = cc_recipients {
             message["ccRecipients"] = serde_json::json!(cc);
@@ -1566,8 +2445,10 @@ impl TeamsClient {
         if let Some(bcc) = bcc_recipients {
             message["bccRecipients"] = serde_json::json!(bcc);
         }
+        self.set_on_behalf(&mut message, sender);
 
         let request = serde_json::json!({
+            "comment": reply_comment(body, content_type),
             "message": message
         });
 
@@ -1594,12 +2475,10 @@ impl TeamsClient {
         message_id: &str,
         to: Vec<&str>,
         comment: Option<&str>,
+        sender: Option<&str>,
     ) -> Result<()> {
         let token = self.get_token(SCOPE_GRAPH).await?;
-        let url = format!(
-            "https://graph.microsoft.com/v1.0/me/messages/{}/forward",
-            message_id
-        );
+        let url = format!("{}/messages/{}/forward", self.mail_root(), message_id);
 
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -1622,9 +2501,12 @@ impl TeamsClient {
             })
             .collect();
 
+        let mut message = serde_json::json!({});
+        self.set_on_behalf(&mut message, sender);
         let request = serde_json::json!({
             "comment": comment.unwrap_or(""),
-            "toRecipients": to_recipients
+            "toRecipients": to_recipients,
+            "message": message
         });
 
         let res = self
@@ -1644,13 +2526,413 @@ impl TeamsClient {
         }
     }
 
-    /// Delete an email
-    pub async fn delete_mail(&self, message_id: &str) -> Result<()> {
-        let token = self.get_token(SCOPE_GRAPH).await?;
-        let url = format!(
-            "https://graph.microsoft.com/v1.0/me/messages/{}",
-            message_id
-        );
+    /// Create a reply (or reply-all) draft quoting the original message
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_reply_draft(
+        &self,
+        message_id: &str,
+        body: &str,
+        content_type: &str,
+        reply_all: bool,
+        cc: Option<Vec<&str>>,
+        bcc: Option<Vec<&str>>,
+        sender: Option<&str>,
+    ) -> Result<MailMessage> {
+        let endpoint = if reply_all {
+            "createReplyAll"
+        } else {
+            "createReply"
+        };
+        let recipients = |emails: Vec<&str>| -> Vec<Recipient> {
+            emails
+                .iter()
+                .map(|email| Recipient {
+                    email_address: EmailAddress {
+                        address: email.to_string(),
+                        name: None,
+                    },
+                })
+                .collect()
+        };
+
+        let mut message = serde_json::json!({});
+        if let Some(cc) = cc {
+            message["ccRecipients"] = serde_json::json!(recipients(cc));
+        }
+        if let Some(bcc) = bcc {
+            message["bccRecipients"] = serde_json::json!(recipients(bcc));
+        }
+        self.set_on_behalf(&mut message, sender);
+        let request = serde_json::json!({
+            "comment": reply_comment(body, content_type),
+            "message": message
+        });
+
+        self.create_response_draft(message_id, endpoint, &request)
+            .await
+    }
+
+    /// Create a forward draft of a message
+    pub async fn create_forward_draft(
+        &self,
+        message_id: &str,
+        to: Vec<&str>,
+        comment: Option<&str>,
+        sender: Option<&str>,
+    ) -> Result<MailMessage> {
+        let to_recipients: Vec<serde_json::Value> = to
+            .iter()
+            .map(|email| {
+                serde_json::json!({
+                    "emailAddress": {
+                        "address": email
+                    }
+                })
+            })
+            .collect();
+        let mut message = serde_json::json!({});
+        self.set_on_behalf(&mut message, sender);
+        let request = serde_json::json!({
+            "comment": comment.unwrap_or(""),
+            "toRecipients": to_recipients,
+            "message": message
+        });
+
+        self.create_response_draft(message_id, "createForward", &request)
+            .await
+    }
+
+    async fn create_response_draft(
+        &self,
+        message_id: &str,
+        endpoint: &str,
+        request: &serde_json::Value,
+    ) -> Result<MailMessage> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!("{}/messages/{}/{}", self.mail_root(), message_id, endpoint);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+        headers.insert(
+            HeaderName::from_static("content-type"),
+            HeaderValue::from_static("application/json"),
+        );
+
+        let res = self
+            .http
+            .post(&url)
+            .headers(headers)
+            .body(serde_json::to_string(request)?)
+            .send()
+            .await?;
+
+        if res.status().is_success() {
+            let body = res.text().await?;
+            serde_json::from_str(&body).context("Failed to parse draft response")
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!("Failed to create draft: {} - {}", status, body))
+        }
+    }
+
+    /// Send an existing draft
+    pub async fn send_draft(&self, message_id: &str) -> Result<()> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!("{}/messages/{}/send", self.mail_root(), message_id);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+        headers.insert(
+            HeaderName::from_static("content-length"),
+            HeaderValue::from_static("0"),
+        );
+
+        let res = self.http.post(&url).headers(headers).send().await?;
+
+        if res.status().is_success() || res.status().as_u16() == 202 {
+            Ok(())
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!("Failed to send draft: {} - {}", status, body))
+        }
+    }
+
+    /// Attach a file to a draft. Files up to 3 MB are sent inline as a
+    /// `fileAttachment`; larger ones go through an upload session.
+    /// Inline attachments get `name` as content ID, for `cid:` references.
+    pub async fn add_mail_attachment(
+        &self,
+        message_id: &str,
+        name: &str,
+        content_type: &str,
+        content: &[u8],
+        is_inline: bool,
+    ) -> Result<()> {
+        if content.len() > MAX_INLINE_ATTACHMENT {
+            return self
+                .upload_mail_attachment(message_id, name, content_type, content, is_inline)
+                .await;
+        }
+
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!("{}/messages/{}/attachments", self.mail_root(), message_id);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+        headers.insert(
+            HeaderName::from_static("content-type"),
+            HeaderValue::from_static("application/json"),
+        );
+
+        let request = file_attachment(name, content_type, content, is_inline);
+        let res = self
+            .http
+            .post(&url)
+            .headers(headers)
+            .body(serde_json::to_string(&request)?)
+            .send()
+            .await?;
+
+        if res.status().is_success() {
+            Ok(())
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!("Failed to attach {}: {} - {}", name, status, body))
+        }
+    }
+
+    async fn upload_mail_attachment(
+        &self,
+        message_id: &str,
+        name: &str,
+        content_type: &str,
+        content: &[u8],
+        is_inline: bool,
+    ) -> Result<()> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!(
+            "{}/messages/{}/attachments/createUploadSession",
+            self.mail_root(),
+            message_id
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+        headers.insert(
+            HeaderName::from_static("content-type"),
+            HeaderValue::from_static("application/json"),
+        );
+
+        let mut item = serde_json::json!({
+            "attachmentType": "file",
+            "name": name,
+            "size": content.len(),
+            "contentType": content_type,
+            "isInline": is_inline
+        });
+        if is_inline {
+            item["contentId"] = serde_json::json!(name);
+        }
+        let request = serde_json::json!({ "AttachmentItem": item });
+
+        let res = self
+            .http
+            .post(&url)
+            .headers(headers)
+            .body(serde_json::to_string(&request)?)
+            .send()
+            .await?;
+
+        if !res.status().is_success() {
+            let status = res.status();
+            let body = res.text().await?;
+            return Err(anyhow!(
+                "Failed to create upload session for {}: {} - {}",
+                name,
+                status,
+                body
+            ));
+        }
+        let session: serde_json::Value = res.json().await?;
+        let upload_url = session["uploadUrl"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Upload session for {} has no uploadUrl", name))?;
+
+        // The upload URL is pre-authenticated: no authorization header
+        let total = content.len();
+        for (i, chunk) in content.chunks(UPLOAD_CHUNK_SIZE).enumerate() {
+            let start = i * UPLOAD_CHUNK_SIZE;
+            let end = start + chunk.len() - 1;
+            let res = self
+                .http
+                .put(upload_url)
+                .header("content-type", "application/octet-stream")
+                .header(
+                    "content-range",
+                    format!("bytes {}-{}/{}", start, end, total),
+                )
+                .body(chunk.to_vec())
+                .send()
+                .await?;
+
+            if !res.status().is_success() {
+                let status = res.status();
+                let body = res.text().await?;
+                return Err(anyhow!(
+                    "Failed to upload {} (bytes {}-{}): {} - {}",
+                    name,
+                    start,
+                    end,
+                    status,
+                    body
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Update properties of a message (PATCH)
+    pub async fn update_mail(
+        &self,
+        message_id: &str,
+        changes: &serde_json::Value,
+    ) -> Result<MailMessage> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!("{}/messages/{}", self.mail_root(), message_id);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+        headers.insert(
+            HeaderName::from_static("content-type"),
+            HeaderValue::from_static("application/json"),
+        );
+
+        let res = self
+            .http
+            .patch(&url)
+            .headers(headers)
+            .body(serde_json::to_string(changes)?)
+            .send()
+            .await?;
+
+        if res.status().is_success() {
+            let body = res.text().await?;
+            serde_json::from_str(&body).context("Failed to parse updated message")
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!("Failed to update mail: {} - {}", status, body))
+        }
+    }
+
+    /// Run Graph requests through JSON `$batch`, 20 at a time, retrying
+    /// throttled requests. Responses come back in request order.
+    pub async fn batch(&self, requests: Vec<BatchRequest>) -> Result<Vec<BatchResponse>> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = "https://graph.microsoft.com/v1.0/$batch";
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+        headers.insert(
+            HeaderName::from_static("content-type"),
+            HeaderValue::from_static("application/json"),
+        );
+
+        let order: Vec<String> = requests.iter().map(|r| r.id.clone()).collect();
+        let mut done: std::collections::HashMap<String, BatchResponse> =
+            std::collections::HashMap::new();
+
+        for chunk in requests.chunks(BATCH_SIZE) {
+            let mut pending = chunk.to_vec();
+            for attempt in 0..=BATCH_RETRIES {
+                let request = serde_json::json!({ "requests": pending });
+                let res = self
+                    .http
+                    .post(url)
+                    .headers(headers.clone())
+                    .body(serde_json::to_string(&request)?)
+                    .send()
+                    .await?;
+
+                // The whole batch can be throttled too; retry it after the same backoff
+                if matches!(res.status().as_u16(), 429 | 503) && attempt < BATCH_RETRIES {
+                    let wait = res
+                        .headers()
+                        .get("retry-after")
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(|v| v.parse::<u64>().ok())
+                        .unwrap_or(5);
+                    tokio::time::sleep(std::time::Duration::from_secs(wait)).await;
+                    continue;
+                }
+                if !res.status().is_success() {
+                    let status = res.status();
+                    let body = res.text().await?;
+                    return Err(anyhow!("Failed to run batch: {} - {}", status, body));
+                }
+                let body = res.text().await?;
+                let batch: BatchResponses =
+                    serde_json::from_str(&body).context("Failed to parse batch response")?;
+
+                let mut retry_after = 0;
+                let mut throttled = Vec::new();
+                for response in batch.responses {
+                    if response.status == 429 && attempt < BATCH_RETRIES {
+                        let wait = response
+                            .headers
+                            .iter()
+                            .find(|(k, _)| k.eq_ignore_ascii_case("retry-after"))
+                            .and_then(|(_, v)| v.parse::<u64>().ok())
+                            .unwrap_or(5);
+                        retry_after = retry_after.max(wait);
+                        throttled.push(response.id);
+                    } else {
+                        done.insert(response.id.clone(), response);
+                    }
+                }
+
+                if throttled.is_empty() {
+                    break;
+                }
+                pending.retain(|r| throttled.contains(&r.id));
+                tokio::time::sleep(std::time::Duration::from_secs(retry_after)).await;
+            }
+        }
+
+        order
+            .into_iter()
+            .map(|id| {
+                done.remove(&id)
+                    .ok_or_else(|| anyhow!("Batch response missing request {}", id))
+            })
+            .collect()
+    }
+
+    /// Delete an email
+    pub async fn delete_mail(&self, message_id: &str) -> Result<()> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!("{}/messages/{}", self.mail_root(), message_id);
 
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -1671,10 +2953,112 @@ impl TeamsClient {
 
     /// Move an email to a folder
     pub async fn move_mail(&self, message_id: &str, folder_id: &str) -> Result<MailMessage> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!("{}/messages/{}/move", self.mail_root(), message_id);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+        headers.insert(
+            HeaderName::from_static("content-type"),
+            HeaderValue::from_static("application/json"),
+        );
+
+        let request = serde_json::json!({
+            "destinationId": folder_id
+        });
+
+        let res = self
+            .http
+            .post(&url)
+            .headers(headers)
+            .body(serde_json::to_string(&request)?)
+            .send()
+            .await?;
+
+        if res.status().is_success() {
+            let body = res.text().await?;
+            serde_json::from_str(&body).context("Failed to parse moved message")
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!("Failed to move mail: {} - {}", status, body))
+        }
+    }
+
+    /// List inbox rules
+    pub async fn get_message_rules(&self) -> Result<MessageRules> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!("{}/mailFolders/inbox/messageRules", self.mail_root());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+
+        let res = self.http.get(url).headers(headers).send().await?;
+
+        if res.status().is_success() {
+            let body = res.text().await?;
+            serde_json::from_str(&body).context("Failed to parse inbox rules")
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!("Failed to get inbox rules: {} - {}", status, body))
+        }
+    }
+
+    /// Create an inbox rule
+    pub async fn create_message_rule(&self, rule: &MessageRule) -> Result<MessageRule> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!("{}/mailFolders/inbox/messageRules", self.mail_root());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+        headers.insert(
+            HeaderName::from_static("content-type"),
+            HeaderValue::from_static("application/json"),
+        );
+
+        let res = self
+            .http
+            .post(url)
+            .headers(headers)
+            .body(serde_json::to_string(&rule.desired())?)
+            .send()
+            .await?;
+
+        if res.status().is_success() {
+            let body = res.text().await?;
+            serde_json::from_str(&body).context("Failed to parse inbox rule")
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!(
+                "Failed to create inbox rule: {} - {}",
+                status,
+                body
+            ))
+        }
+    }
+
+    /// Update an inbox rule (PATCH); only the fields set in `rule` change
+    pub async fn update_message_rule(
+        &self,
+        rule_id: &str,
+        rule: &MessageRule,
+    ) -> Result<MessageRule> {
         let token = self.get_token(SCOPE_GRAPH).await?;
         let url = format!(
-            "https://graph.microsoft.com/v1.0/me/messages/{}/move",
-            message_id
+            "{}/mailFolders/inbox/messageRules/{}",
+            self.mail_root(),
+            rule_id
         );
 
         let mut headers = HeaderMap::new();
@@ -1687,35 +3071,129 @@ impl TeamsClient {
             HeaderValue::from_static("application/json"),
         );
 
-        let request = serde_json::json!({
-            "destinationId": folder_id
-        });
-
         let res = self
             .http
-            .post(&url)
+            .patch(&url)
+            .headers(headers)
+            .body(serde_json::to_string(&rule.desired())?)
+            .send()
+            .await?;
+
+        if res.status().is_success() {
+            let body = res.text().await?;
+            serde_json::from_str(&body).context("Failed to parse inbox rule")
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!(
+                "Failed to update inbox rule: {} - {}",
+                status,
+                body
+            ))
+        }
+    }
+
+    /// Delete an inbox rule
+    pub async fn delete_message_rule(&self, rule_id: &str) -> Result<()> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!(
+            "{}/mailFolders/inbox/messageRules/{}",
+            self.mail_root(),
+            rule_id
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+
+        let res = self.http.delete(&url).headers(headers).send().await?;
+
+        if res.status().is_success() {
+            Ok(())
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!(
+                "Failed to delete inbox rule: {} - {}",
+                status,
+                body
+            ))
+        }
+    }
+
+    /// Get mailbox settings (automatic replies, time zone, working hours)
+    pub async fn get_mailbox_settings(&self) -> Result<MailboxSettings> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!("{}/mailboxSettings", self.mail_root());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+
+        let res = self.http.get(url).headers(headers).send().await?;
+
+        if res.status().is_success() {
+            let body = res.text().await?;
+            serde_json::from_str(&body).context("Failed to parse mailbox settings")
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!(
+                "Failed to get mailbox settings: {} - {}",
+                status,
+                body
+            ))
+        }
+    }
+
+    /// Update mailbox settings (PATCH); only the fields set in `settings` change
+    pub async fn update_mailbox_settings(
+        &self,
+        settings: &MailboxSettings,
+    ) -> Result<MailboxSettings> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!("{}/mailboxSettings", self.mail_root());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+        headers.insert(
+            HeaderName::from_static("content-type"),
+            HeaderValue::from_static("application/json"),
+        );
+
+        let res = self
+            .http
+            .patch(url)
             .headers(headers)
-            .body(serde_json::to_string(&request)?)
+            .body(serde_json::to_string(settings)?)
             .send()
             .await?;
 
         if res.status().is_success() {
             let body = res.text().await?;
-            serde_json::from_str(&body).context("Failed to parse moved message")
+            serde_json::from_str(&body).context("Failed to parse mailbox settings")
         } else {
             let status = res.status();
             let body = res.text().await?;
-            Err(anyhow!("Failed to move mail: {} - {}", status, body))
+            Err(anyhow!(
+                "Failed to update mailbox settings: {} - {}",
+                status,
+                body
+            ))
         }
     }
 
     /// Mark email as read or unread
     pub async fn mark_mail(&self, message_id: &str, is_read: bool) -> Result<()> {
         let token = self.get_token(SCOPE_GRAPH).await?;
-        let url = format!(
-            "https://graph.microsoft.com/v1.0/me/messages/{}",
-            message_id
-        );
+        let url = format!("{}/messages/{}", self.mail_root(), message_id);
 
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -1751,10 +3229,7 @@ impl TeamsClient {
     /// Get email attachments
     pub async fn get_mail_attachments(&self, message_id: &str) -> Result<MailAttachments> {
         let token = self.get_token(SCOPE_GRAPH).await?;
-        let url = format!(
-            "https://graph.microsoft.com/v1.0/me/messages/{}/attachments",
-            message_id
-        );
+        let url = format!("{}/messages/{}/attachments", self.mail_root(), message_id);
 
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -1782,8 +3257,10 @@ impl TeamsClient {
     ) -> Result<(String, Vec<u8>)> {
         let token = self.get_token(SCOPE_GRAPH).await?;
         let url = format!(
-            "https://graph.microsoft.com/v1.0/me/messages/{}/attachments/{}",
-            message_id, attachment_id
+            "{}/messages/{}/attachments/{}",
+            self.mail_root(),
+            message_id,
+            attachment_id
         );
 
         let mut headers = HeaderMap::new();
@@ -2103,6 +3580,183 @@ impl TeamsClient {
         }
     }
 
+    /// Look up the online meeting behind a Teams join URL
+    pub async fn get_online_meeting_by_join_url(
+        &self,
+        join_url: &str,
+    ) -> Result<Option<OnlineMeetingInfo>> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let filter = format!("JoinWebUrl eq '{}'", join_url.replace('\'', "''"));
+        let url = format!(
+            "https://graph.microsoft.com/v1.0/me/onlineMeetings?$filter={}",
+            urlencoding::encode(&filter)
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+
+        let res = self.http.get(&url).headers(headers).send().await?;
+
+        if res.status().is_success() {
+            let body = res.text().await?;
+            let meetings: OnlineMeetings =
+                serde_json::from_str(&body).context("Failed to parse online meetings")?;
+            Ok(meetings.value.into_iter().next())
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!(
+                "Failed to get online meeting: {} - {}",
+                status,
+                body
+            ))
+        }
+    }
+
+    /// List transcripts of an online meeting
+    pub async fn get_meeting_transcripts(&self, meeting_id: &str) -> Result<MeetingTranscripts> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!(
+            "https://graph.microsoft.com/v1.0/me/onlineMeetings/{}/transcripts",
+            meeting_id
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+
+        let res = self.http.get(&url).headers(headers).send().await?;
+
+        if res.status().is_success() {
+            let body = res.text().await?;
+            serde_json::from_str(&body).context("Failed to parse meeting transcripts")
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!(
+                "Failed to get meeting transcripts: {} - {}",
+                status,
+                body
+            ))
+        }
+    }
+
+    /// Download a meeting transcript as WebVTT
+    pub async fn get_meeting_transcript_content(
+        &self,
+        meeting_id: &str,
+        transcript_id: &str,
+    ) -> Result<String> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!(
+            "https://graph.microsoft.com/v1.0/me/onlineMeetings/{}/transcripts/{}/content?$format=text/vtt",
+            meeting_id, transcript_id
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+
+        let res = self.http.get(&url).headers(headers).send().await?;
+
+        if res.status().is_success() {
+            res.text().await.context("Failed to read transcript")
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!(
+                "Failed to download transcript: {} - {}",
+                status,
+                body
+            ))
+        }
+    }
+
+    /// List recordings of an online meeting; `None` when Graph does not know the
+    /// meeting or only shows its recordings to the organizer (403)
+    pub async fn get_meeting_recordings(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Option<MeetingRecordings>> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!(
+            "https://graph.microsoft.com/v1.0/me/onlineMeetings/{}/recordings",
+            meeting_id
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+
+        let res = self.http.get(&url).headers(headers).send().await?;
+
+        if res.status().is_success() {
+            let body = res.text().await?;
+            Ok(Some(
+                serde_json::from_str(&body).context("Failed to parse meeting recordings")?,
+            ))
+        } else if matches!(res.status().as_u16(), 403 | 404) {
+            Ok(None)
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!(
+                "Failed to get meeting recordings: {} - {}",
+                status,
+                body
+            ))
+        }
+    }
+
+    /// Download the content of a meeting recording
+    pub async fn download_meeting_recording(
+        &self,
+        meeting_id: &str,
+        recording_id: &str,
+    ) -> Result<(String, Vec<u8>)> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!(
+            "https://graph.microsoft.com/v1.0/me/onlineMeetings/{}/recordings/{}/content",
+            meeting_id, recording_id
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+
+        let res = self.http.get(&url).headers(headers).send().await?;
+
+        if res.status().is_success() {
+            let content_type = res
+                .headers()
+                .get("content-type")
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or("video/mp4")
+                .to_string();
+            let bytes = res.bytes().await?.to_vec();
+            Ok((content_type, bytes))
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!(
+                "Failed to download recording: {} - {}",
+                status,
+                body
+            ))
+        }
+    }
+
     /// Create a calendar event
     pub async fn create_calendar_event(
         &self,
@@ -2139,6 +3793,50 @@ impl TeamsClient {
         }
     }
 
+    /// Find events in the primary calendar with the given iCalendar UID,
+    /// either as their own iCalUId or as the UID recorded on import
+    pub async fn find_events_by_uid(&self, uid: &str) -> Result<Vec<CalendarEvent>> {
+        let uid = uid.replace('\'', "''");
+        let by_ical = format!("iCalUId eq '{}'", uid);
+        let events = self.get_events_filtered(&by_ical).await?;
+        if !events.is_empty() {
+            return Ok(events);
+        }
+
+        let by_property = format!(
+            "singleValueExtendedProperties/Any(ep: ep/id eq '{}' and ep/value eq '{}')",
+            ICS_UID_PROPERTY, uid
+        );
+        self.get_events_filtered(&by_property).await
+    }
+
+    async fn get_events_filtered(&self, filter: &str) -> Result<Vec<CalendarEvent>> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = format!(
+            "https://graph.microsoft.com/v1.0/me/events?$filter={}&$top=10",
+            urlencoding::encode(filter)
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+
+        let res = self.http.get(&url).headers(headers).send().await?;
+
+        if res.status().is_success() {
+            let body = res.text().await?;
+            let events: CalendarEvents =
+                serde_json::from_str(&body).context("Failed to parse calendar events")?;
+            Ok(events.value)
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!("Failed to search events: {} - {}", status, body))
+        }
+    }
+
     /// RSVP to a calendar event
     pub async fn rsvp_calendar_event(
         &self,
@@ -2194,6 +3892,92 @@ impl TeamsClient {
         }
     }
 
+    /// Graph URL of an event in the user's own, another user's, or a specific calendar
+    fn event_url(event_id: &str, user_id: Option<&str>, calendar_id: Option<&str>) -> String {
+        match (user_id, calendar_id) {
+            (Some(user), _) => format!(
+                "https://graph.microsoft.com/v1.0/users/{}/events/{}",
+                user, event_id
+            ),
+            (None, Some(calendar)) => format!(
+                "https://graph.microsoft.com/v1.0/me/calendars/{}/events/{}",
+                calendar, event_id
+            ),
+            (None, None) => format!("https://graph.microsoft.com/v1.0/me/events/{}", event_id),
+        }
+    }
+
+    /// Get a calendar event from a shared or secondary calendar
+    pub async fn get_calendar_event_in(
+        &self,
+        event_id: &str,
+        user_id: Option<&str>,
+        calendar_id: Option<&str>,
+    ) -> Result<CalendarEvent> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = Self::event_url(event_id, user_id, calendar_id);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+
+        let res = self.http.get(&url).headers(headers).send().await?;
+
+        if res.status().is_success() {
+            let body = res.text().await?;
+            serde_json::from_str(&body).context("Failed to parse calendar event")
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!(
+                "Failed to get calendar event: {} - {}",
+                status,
+                body
+            ))
+        }
+    }
+
+    /// Update a calendar event (only the fields set in `request` change)
+    pub async fn update_calendar_event(
+        &self,
+        event_id: &str,
+        request: &UpdateEventRequest,
+        user_id: Option<&str>,
+        calendar_id: Option<&str>,
+    ) -> Result<CalendarEvent> {
+        let token = self.get_token(SCOPE_GRAPH).await?;
+        let url = Self::event_url(event_id, user_id, calendar_id);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_str(&format!("Bearer {}", token.value))?,
+        );
+        headers.insert(
+            HeaderName::from_static("content-type"),
+            HeaderValue::from_static("application/json"),
+        );
+
+        let res = self
+            .http
+            .patch(&url)
+            .headers(headers)
+            .body(serde_json::to_string(request)?)
+            .send()
+            .await?;
+
+        if res.status().is_success() {
+            let body = res.text().await?;
+            serde_json::from_str(&body).context("Failed to parse updated event")
+        } else {
+            let status = res.status();
+            let body = res.text().await?;
+            Err(anyhow!("Failed to update event: {} - {}", status, body))
+        }
+    }
+
     /// Delete a calendar event
     pub async fn delete_calendar_event(&self, event_id: &str) -> Result<()> {
         let token = self.get_token(SCOPE_GRAPH).await?;
@@ -2347,3 +4131,76 @@ impl TeamsClient {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mail(id: &str, conversation: &str, sent: &str) -> MailMessage {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "conversationId": conversation,
+            "sentDateTime": sent,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_mailbox_routing() {
+        let own = TeamsClient::new(&Config::default()).unwrap();
+        assert_eq!(own.mailbox_path(), "/me");
+        assert_eq!(own.mail_root(), "https://graph.microsoft.com/v1.0/me");
+        assert!(own.on_behalf(Some("alice@contoso.com")).is_none());
+
+        let shared = TeamsClient::new(&Config::default())
+            .unwrap()
+            .with_mailbox(Some("support@contoso.com"));
+        assert_eq!(shared.mailbox_path(), "/users/support%40contoso.com");
+        assert!(shared.on_behalf(None).is_none());
+
+        let mut message = serde_json::json!({"subject": "Hi"});
+        shared.set_on_behalf(&mut message, Some("alice@contoso.com"));
+        assert_eq!(
+            message["from"]["emailAddress"]["address"],
+            "support@contoso.com"
+        );
+        assert_eq!(
+            message["sender"]["emailAddress"]["address"],
+            "alice@contoso.com"
+        );
+    }
+
+    #[test]
+    fn test_file_attachment() {
+        let file = file_attachment("report.pdf", "application/pdf", b"hi", false);
+        assert_eq!(file["contentBytes"], "aGk=");
+        assert_eq!(file["isInline"], false);
+        assert!(file.get("contentId").is_none());
+
+        let image = file_attachment("logo.png", "image/png", b"", true);
+        assert_eq!(image["contentId"], "logo.png");
+    }
+
+    #[test]
+    fn test_order_thread() {
+        let mut reply = mail("2", "c1", "2026-10-02T09:00:00Z");
+        reply.internet_message_headers = Some(vec![InternetMessageHeader {
+            name: "in-reply-to".to_string(),
+            value: "<first@contoso.com>".to_string(),
+        }]);
+        let mut messages = vec![
+            mail("3", "c1", "2026-10-03T09:00:00Z"),
+            reply,
+            mail("1", "c1", "2026-10-01T09:00:00Z"),
+        ];
+        order_thread(&mut messages);
+
+        let ids: Vec<_> = messages.iter().filter_map(|m| m.id.as_deref()).collect();
+        assert_eq!(ids, vec!["1", "2", "3"]);
+        assert_eq!(
+            messages[1].in_reply_to.as_deref(),
+            Some("<first@contoso.com>")
+        );
+        assert_eq!(messages[0].in_reply_to, None);
+    }
+}
@@ -31,7 +31,11 @@ impl Cache {
     pub fn save<T: Serialize>(&self, filename: &str, data: &T) -> Result<()> {
         let path = self.file_path(filename);
         let content = serde_json::to_string_pretty(data).context("Failed to serialize data")?;
-        fs::write(&path, content)
+        // Write beside the target and rename over it, so readers never see a partial file
+        let tmp = path.with_extension(format!("tmp.{}", std::process::id()));
+        fs::write(&tmp, content)
+            .with_context(|| format!("Failed to write cache file: {:?}", tmp))?;
+        fs::rename(&tmp, &path)
             .with_context(|| format!("Failed to write cache file: {:?}", path))?;
         Ok(())
     }
@@ -84,3 +88,18 @@ pub const TEAMS_FILE: &str = "teams.json";
 pub const CHATS_FILE: &str = "chats.json";
 pub const USERS_FILE: &str = "users.json";
 pub const ME_FILE: &str = "me.json";
+
+/// Mail delta sync state file of a consumer (`watch`, `tui`, a `mail sync --name`...)
+pub fn mail_sync_file(consumer: &str) -> String {
+    let name: String = consumer
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("mail_sync_{}.json", name)
+}
@@ -0,0 +1,371 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use chrono_tz::Tz;
+use colored::{ColoredString, Colorize};
+
+use crate::types::CalendarEvent;
+
+const GUTTER: usize = 7;
+const MIN_COLUMN: usize = 12;
+const SLOT_MINUTES: i64 = 30;
+const ONLINE_MARK: char = '◆';
+
+/// One column of the agenda: a calendar's events on a given day
+pub struct AgendaColumn<'a> {
+    pub title: String,
+    pub day: NaiveDate,
+    pub events: &'a [CalendarEvent],
+}
+
+/// An event clipped to a column's day, in local wall-clock time
+#[derive(Debug, Clone)]
+struct Block {
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+    title: String,
+    response: String,
+    online: bool,
+}
+
+/// Render columns side by side on a shared time axis, with overlapping
+/// events in a column split into lanes
+pub fn render_agenda(columns: &[AgendaColumn], tz: Tz) -> String {
+    let layouts: Vec<(Vec<Block>, Vec<Block>)> = columns
+        .iter()
+        .map(|c| day_blocks(c.events, c.day, tz))
+        .collect();
+
+    // Working day, widened to fit every timed event
+    let mut first_hour = 8;
+    let mut last_hour = 18;
+    for block in layouts.iter().flat_map(|(_, timed)| timed) {
+        first_hour = first_hour.min(block.start.hour());
+        let end_hour = if block.end.time() == NaiveTime::MIN {
+            24
+        } else {
+            block.end.hour() + u32::from(block.end.minute() > 0)
+        };
+        last_hour = last_hour.max(end_hour);
+    }
+
+    let total_width = std::env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.parse::<usize>().ok())
+        .unwrap_or(120);
+    let width = ((total_width.saturating_sub(GUTTER)) / columns.len().max(1))
+        .saturating_sub(1)
+        .max(MIN_COLUMN);
+
+    let mut out = String::new();
+
+    // Header
+    out.push_str(&" ".repeat(GUTTER));
+    for column in columns {
+        out.push_str(&format!("│{}", fit(&column.title, width).bold()));
+    }
+    out.push('\n');
+    out.push_str(&"─".repeat(GUTTER));
+    for _ in columns {
+        out.push_str(&format!("┼{}", "─".repeat(width)));
+    }
+    out.push('\n');
+
+    // All-day events
+    if layouts.iter().any(|(all_day, _)| !all_day.is_empty()) {
+        out.push_str(&fit("all-day", GUTTER).dimmed().to_string());
+        for (all_day, _) in &layouts {
+            let titles: Vec<String> = all_day.iter().map(label).collect();
+            let cell = fit(&titles.join(", "), width);
+            let cell = match all_day.first() {
+                Some(block) => paint(cell, &block.response),
+                None => cell.normal(),
+            };
+            out.push_str(&format!("│{}", cell));
+        }
+        out.push('\n');
+    }
+
+    // Time slots
+    let groups: Vec<Vec<Vec<Vec<Block>>>> = layouts
+        .into_iter()
+        .map(|(_, timed)| {
+            overlap_groups(timed)
+                .into_iter()
+                .map(assign_lanes)
+                .collect()
+        })
+        .collect();
+    let slots = (last_hour - first_hour) as i64 * 60 / SLOT_MINUTES;
+    for slot in 0..slots {
+        let offset = Duration::minutes(first_hour as i64 * 60 + slot * SLOT_MINUTES);
+        let gutter = NaiveTime::MIN + offset;
+        let gutter = fit(&gutter.format("%H:%M").to_string(), GUTTER);
+        if slot % 2 == 0 {
+            out.push_str(&gutter);
+        } else {
+            out.push_str(&gutter.dimmed().to_string());
+        }
+
+        for (column, column_groups) in columns.iter().zip(&groups) {
+            let slot_start = column.day.and_time(NaiveTime::MIN) + offset;
+            let slot_end = slot_start + Duration::minutes(SLOT_MINUTES);
+            let lanes = column_groups
+                .iter()
+                .find(|lanes| {
+                    lanes
+                        .iter()
+                        .flatten()
+                        .any(|b| b.start < slot_end && b.end > slot_start)
+                })
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            out.push('│');
+            out.push_str(&render_slot(lanes, slot_start, slot_end, width));
+        }
+        out.push('\n');
+    }
+
+    out.push_str(&legend());
+    out
+}
+
+/// One column's cell for a time slot, split evenly between its lanes
+fn render_slot(
+    lanes: &[Vec<Block>],
+    slot_start: NaiveDateTime,
+    slot_end: NaiveDateTime,
+    width: usize,
+) -> String {
+    if lanes.is_empty() {
+        return " ".repeat(width);
+    }
+
+    let lane_width = width / lanes.len();
+    let mut out = String::new();
+    for (i, lane) in lanes.iter().enumerate() {
+        // The last lane absorbs the rounding remainder
+        let w = if i + 1 == lanes.len() {
+            width - lane_width * i
+        } else {
+            lane_width
+        };
+        let block = lane
+            .iter()
+            .find(|b| b.start < slot_end && b.end > slot_start);
+        let cell = match block {
+            Some(block) => {
+                let first_slot = block.start >= slot_start || slot_start.time() == NaiveTime::MIN;
+                let second_slot =
+                    !first_slot && block.start >= slot_start - Duration::minutes(SLOT_MINUTES);
+                let text = if first_slot {
+                    format!("▌{}", label(block))
+                } else if second_slot {
+                    format!(
+                        "▌{}-{}",
+                        block.start.format("%H:%M"),
+                        block.end.format("%H:%M")
+                    )
+                } else {
+                    "▌".to_string()
+                };
+                paint(fit(&text, w), &block.response).to_string()
+            }
+            None => " ".repeat(w),
+        };
+        out.push_str(&cell);
+    }
+    out
+}
+
+/// Split a calendar's events into all-day entries and timed blocks clipped to `day`
+fn day_blocks(events: &[CalendarEvent], day: NaiveDate, tz: Tz) -> (Vec<Block>, Vec<Block>) {
+    let day_start = day.and_time(NaiveTime::MIN);
+    let day_end = day_start + Duration::days(1);
+    let mut all_day = Vec::new();
+    let mut timed = Vec::new();
+
+    for event in events {
+        let (Some(start), Some(end)) = (&event.start, &event.end) else {
+            continue;
+        };
+        let is_all_day = event.is_all_day.unwrap_or(false);
+        // All-day events carry their dates as-is; timed events come back in UTC
+        let (start, end) = if is_all_day {
+            match (parse_naive(&start.date_time), parse_naive(&end.date_time)) {
+                (Some(s), Some(e)) => (s, e),
+                _ => continue,
+            }
+        } else {
+            match (
+                parse_local(&start.date_time, tz),
+                parse_local(&end.date_time, tz),
+            ) {
+                (Some(s), Some(e)) => (s, e),
+                _ => continue,
+            }
+        };
+        if start >= day_end || end <= day_start || event.is_cancelled == Some(true) {
+            continue;
+        }
+
+        let block = Block {
+            start: start.max(day_start),
+            end: end.min(day_end),
+            title: event.subject.clone().unwrap_or_default(),
+            response: event
+                .response_status
+                .as_ref()
+                .and_then(|r| r.response.clone())
+                .unwrap_or_default(),
+            online: event.is_online_meeting == Some(true),
+        };
+        if is_all_day {
+            all_day.push(block);
+        } else {
+            timed.push(block);
+        }
+    }
+
+    timed.sort_by_key(|b| (b.start, b.end));
+    (all_day, timed)
+}
+
+/// Split blocks (sorted by start) into groups of transitively overlapping events,
+/// so that a clash only narrows the columns for as long as it lasts
+fn overlap_groups(blocks: Vec<Block>) -> Vec<Vec<Block>> {
+    let mut groups: Vec<Vec<Block>> = Vec::new();
+    let mut group_end = None;
+    for block in blocks {
+        match (groups.last_mut(), group_end) {
+            (Some(group), Some(end)) if block.start < end => {
+                group_end = Some(end.max(block.end));
+                group.push(block);
+            }
+            _ => {
+                group_end = Some(block.end);
+                groups.push(vec![block]);
+            }
+        }
+    }
+    groups
+}
+
+/// Greedily place blocks (sorted by start) in the first lane that is free
+fn assign_lanes(blocks: Vec<Block>) -> Vec<Vec<Block>> {
+    let mut lanes: Vec<Vec<Block>> = Vec::new();
+    for block in blocks {
+        let free = lanes
+            .iter_mut()
+            .find(|lane| lane.last().is_none_or(|last| last.end <= block.start));
+        match free {
+            Some(lane) => lane.push(block),
+            None => lanes.push(vec![block]),
+        }
+    }
+    lanes
+}
+
+fn parse_naive(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f").ok()
+}
+
+fn parse_local(value: &str, tz: Tz) -> Option<NaiveDateTime> {
+    parse_naive(value).map(|utc| utc.and_utc().with_timezone(&tz).naive_local())
+}
+
+fn label(block: &Block) -> String {
+    if block.online {
+        format!("{}{}", ONLINE_MARK, block.title)
+    } else {
+        block.title.clone()
+    }
+}
+
+/// Color a cell by the user's response to the event
+fn paint(text: String, response: &str) -> ColoredString {
+    match response {
+        "organizer" => text.blue(),
+        "accepted" => text.green(),
+        "tentativelyAccepted" => text.yellow(),
+        "declined" => text.dimmed(),
+        _ => text.red(),
+    }
+}
+
+fn legend() -> String {
+    format!(
+        "{} {} {} {} {} {} online meeting\n",
+        "▌organizer".blue(),
+        "▌accepted".green(),
+        "▌tentative".yellow(),
+        "▌not responded".red(),
+        "▌declined".dimmed(),
+        ONLINE_MARK
+    )
+}
+
+/// Truncate or pad text to exactly `width` characters
+fn fit(text: &str, width: usize) -> String {
+    let count = text.chars().count();
+    if count > width {
+        let mut cut: String = text.chars().take(width.saturating_sub(1)).collect();
+        if width > 0 {
+            cut.push('…');
+        }
+        cut
+    } else {
+        format!("{}{}", text, " ".repeat(width - count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(start: &str, end: &str) -> Block {
+        let at = |t: &str| {
+            NaiveDate::from_ymd_opt(2024, 5, 6)
+                .unwrap()
+                .and_time(NaiveTime::parse_from_str(t, "%H:%M").unwrap())
+        };
+        Block {
+            start: at(start),
+            end: at(end),
+            title: format!("{}-{}", start, end),
+            response: "accepted".into(),
+            online: false,
+        }
+    }
+
+    #[test]
+    fn test_assign_lanes() {
+        let lanes = assign_lanes(vec![
+            block("09:00", "10:00"),
+            block("09:30", "11:00"),
+            block("10:00", "10:30"),
+            block("10:15", "10:45"),
+        ]);
+        let titles: Vec<Vec<&str>> = lanes
+            .iter()
+            .map(|lane| lane.iter().map(|b| b.title.as_str()).collect())
+            .collect();
+        assert_eq!(
+            titles,
+            vec![
+                vec!["09:00-10:00", "10:00-10:30"],
+                vec!["09:30-11:00"],
+                vec!["10:15-10:45"],
+            ]
+        );
+        assert_eq!(
+            overlap_groups(vec![
+                block("09:00", "10:00"),
+                block("09:30", "10:30"),
+                block("14:00", "15:00"),
+            ])
+            .len(),
+            2
+        );
+        assert_eq!(fit("Standup", 5), "Stan…");
+        assert_eq!(fit("1:1", 5), "1:1  ");
+    }
+}
@@ -1,22 +1,37 @@
 use anyhow::Result;
-use clap::{Args, Subcommand};
+use clap::{Args, Subcommand, ValueEnum};
+use colored::Colorize;
 use serde::Serialize;
 use tabled::Tabled;
 
 use crate::api::TeamsClient;
 use crate::config::Config;
 use crate::types::{
-    AttendeeRequest, CreateEventRequest, DateTimeZone, EmailAddressSimple, EventBody, Location,
+    AttendeeRequest, CalendarEvent, CreateEventRequest, DateTimeZone, EmailAddressSimple,
+    EventBody, Location, PatternedRecurrence, RecurrencePattern, RecurrenceRange,
+    UpdateEventRequest,
 };
 
-use super::output::{print_output, print_single, print_success};
-use super::utils::truncate;
+use super::agenda::{render_agenda, AgendaColumn};
+use super::datetime::{
+    format_in_tz, graph_time_utc, now_in, parse_datetime, parse_duration, parse_when,
+    parse_zone_name, resolve_tz, to_graph_utc, When,
+};
+use super::ics::{events_to_ics, is_ics_attachment, parse_ics};
+use super::output::{print_error, print_output, print_single, print_success, print_warning};
+use super::people::resolve_addresses;
+use super::utils::{html_escape, markdown_to_html, truncate};
 use super::OutputFormat;
 
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday};
+use chrono_tz::Tz;
 
 #[derive(Args, Debug)]
 pub struct CalendarCommand {
+    /// IANA time zone for dates and times, e.g. Europe/Paris (defaults to config, then system)
+    #[arg(long, global = true)]
+    pub tz: Option<String>,
+
     #[command(subcommand)]
     pub command: CalendarSubcommand,
 }
@@ -29,9 +44,13 @@ pub enum CalendarSubcommand {
         #[arg(short, long)]
         calendar_id: Option<String>,
 
-        /// User ID/Email to use (for shared calendars)
-        #[arg(short, long)]
+        /// User IDs/Emails to use (for shared calendars), comma-separated
+        #[arg(short, long, visible_alias = "user")]
         user_id: Option<String>,
+
+        /// How to lay out the events
+        #[arg(short, long, value_enum, default_value_t)]
+        view: AgendaView,
     },
 
     /// List this week's calendar events
@@ -40,18 +59,22 @@ pub enum CalendarSubcommand {
         #[arg(short, long)]
         calendar_id: Option<String>,
 
-        /// User ID/Email to use (for shared calendars)
-        #[arg(short, long)]
+        /// User IDs/Emails to use (for shared calendars), comma-separated
+        #[arg(short, long, visible_alias = "user")]
         user_id: Option<String>,
+
+        /// How to lay out the events
+        #[arg(short, long, value_enum, default_value_t)]
+        view: AgendaView,
     },
 
     /// List calendar events in a date range
     List {
-        /// Start date (YYYY-MM-DD)
+        /// Start date (YYYY-MM-DD, today, next mon, -2d, ...)
         #[arg(short, long)]
         start: String,
 
-        /// End date (YYYY-MM-DD)
+        /// End date, inclusive (YYYY-MM-DD, tomorrow, +1w, ...)
         #[arg(short, long)]
         end: String,
 
@@ -76,11 +99,42 @@ pub enum CalendarSubcommand {
         #[arg(short, long)]
         users: String,
 
-        /// Date (YYYY-MM-DD), defaults to today
+        /// Date (YYYY-MM-DD, tomorrow, next fri, ...), defaults to today
         #[arg(short, long)]
         date: Option<String>,
     },
 
+    /// Find meeting times when attendees are free
+    FindTime {
+        /// Attendee emails, comma-separated
+        #[arg(short, long)]
+        attendees: String,
+
+        /// Meeting duration (30m, 45m, 1h, ...)
+        #[arg(short, long, default_value = "30m")]
+        duration: String,
+
+        /// Search window: today, tomorrow, this week, next week, a date, or START..END
+        #[arg(short, long, default_value = "this week")]
+        within: String,
+
+        /// Your working hours, HH:MM-HH:MM (defaults to your Outlook working hours)
+        #[arg(long)]
+        working_hours: Option<String>,
+
+        /// Number of candidate slots to show
+        #[arg(short, long, default_value = "5")]
+        limit: usize,
+
+        /// Book the best slot as a Teams meeting
+        #[arg(long)]
+        book: bool,
+
+        /// Meeting title when booking
+        #[arg(short = 'T', long, default_value = "Meeting")]
+        title: String,
+    },
+
     /// List available calendars
     Calendars,
 
@@ -90,15 +144,19 @@ pub enum CalendarSubcommand {
         #[arg(short = 'T', long)]
         title: String,
 
-        /// Start datetime (YYYY-MM-DDTHH:MM)
+        /// Start (YYYY-MM-DDTHH:MM, tomorrow 14:00, next mon 9:30, +2h, ...)
         #[arg(short, long)]
         start: String,
 
-        /// End datetime (YYYY-MM-DDTHH:MM)
+        /// End (same formats as --start)
+        #[arg(short, long, conflicts_with = "duration")]
+        end: Option<String>,
+
+        /// Duration instead of --end (30m, 1h, 1h30m), defaults to 30m
         #[arg(short, long)]
-        end: String,
+        duration: Option<String>,
 
-        /// Attendees (comma-separated emails)
+        /// Attendees (comma-separated names or emails)
         #[arg(short, long)]
         attendees: Option<String>,
 
@@ -113,6 +171,85 @@ pub enum CalendarSubcommand {
         /// Description/body
         #[arg(short, long)]
         body: Option<String>,
+
+        /// Repeat: daily, weekdays, weekly[:mon,wed], monthly[:15] or yearly
+        #[arg(short, long)]
+        repeat: Option<String>,
+
+        /// Repeat every N days/weeks/months/years
+        #[arg(long, default_value = "1", requires = "repeat")]
+        interval: u32,
+
+        /// Last date of the series (YYYY-MM-DD, next fri, +3w, ...)
+        #[arg(long, requires = "repeat", conflicts_with = "count")]
+        until: Option<String>,
+
+        /// Number of occurrences in the series
+        #[arg(long, requires = "repeat")]
+        count: Option<u32>,
+    },
+
+    /// Update an existing calendar event
+    Update {
+        /// Event ID
+        event_id: String,
+
+        /// New title
+        #[arg(short = 'T', long)]
+        title: Option<String>,
+
+        /// New start (same formats as create); keeps the current duration by default
+        #[arg(short, long)]
+        start: Option<String>,
+
+        /// New end
+        #[arg(short, long, conflicts_with = "duration")]
+        end: Option<String>,
+
+        /// New duration (30m, 1h, ...)
+        #[arg(short, long)]
+        duration: Option<String>,
+
+        /// New location
+        #[arg(short, long)]
+        location: Option<String>,
+
+        /// Attendees to add (comma-separated emails)
+        #[arg(long)]
+        add_attendee: Option<String>,
+
+        /// Attendees to remove (comma-separated emails)
+        #[arg(long)]
+        remove_attendee: Option<String>,
+
+        /// New description/body
+        #[arg(short, long)]
+        body: Option<String>,
+
+        /// Make it a Teams meeting (Graph cannot remove one once added)
+        #[arg(long)]
+        teams: bool,
+
+        /// Message emailed to attendees along with the update, from the
+        /// calendar owner's mailbox
+        #[arg(short, long)]
+        message: Option<String>,
+
+        /// Only this occurrence (the ID must be an occurrence of a series)
+        #[arg(long, conflicts_with = "series")]
+        this: bool,
+
+        /// The whole series the event belongs to
+        #[arg(long)]
+        series: bool,
+
+        /// Calendar ID (for secondary calendars)
+        #[arg(short, long)]
+        calendar_id: Option<String>,
+
+        /// User ID/Email (for shared calendars)
+        #[arg(short, long)]
+        user_id: Option<String>,
     },
 
     /// RSVP to an event
@@ -133,6 +270,14 @@ pub enum CalendarSubcommand {
     Delete {
         /// Event ID
         event_id: String,
+
+        /// Only this occurrence (the ID must be an occurrence of a series)
+        #[arg(long, conflicts_with = "series")]
+        this: bool,
+
+        /// The whole series the event belongs to
+        #[arg(long)]
+        series: bool,
     },
 
     /// Get the join URL for a Teams meeting
@@ -140,6 +285,134 @@ pub enum CalendarSubcommand {
         /// Event ID (optional - joins next meeting if not provided)
         event_id: Option<String>,
     },
+
+    /// Read the meeting chat of a Teams meeting
+    Chat {
+        /// Event ID
+        event_id: Option<String>,
+
+        /// Use the current or next Teams meeting instead of an event ID
+        #[arg(long, conflicts_with = "event_id")]
+        next: bool,
+
+        /// Maximum number of messages to retrieve
+        #[arg(short, long, default_value = "50")]
+        limit: usize,
+    },
+
+    /// Post a message to the meeting chat of a Teams meeting
+    Post {
+        /// Event ID (or message if --next is used)
+        event_id_or_message: String,
+
+        /// Message content (when event_id is provided)
+        message: Option<String>,
+
+        /// Use the current or next Teams meeting instead of an event ID
+        #[arg(long)]
+        next: bool,
+
+        /// Treat message as Markdown and convert to HTML
+        #[arg(short, long)]
+        markdown: bool,
+    },
+
+    /// List or download the transcripts of a Teams meeting
+    Transcript {
+        /// Event ID
+        event_id: Option<String>,
+
+        /// Use the current or next Teams meeting instead of an event ID
+        #[arg(long, conflicts_with = "event_id")]
+        next: bool,
+
+        /// List available transcripts instead of downloading one
+        #[arg(short, long)]
+        list: bool,
+
+        /// Transcript ID to download (defaults to the latest)
+        #[arg(long)]
+        id: Option<String>,
+
+        /// Convert the VTT transcript to plain text with speaker labels
+        #[arg(short, long)]
+        text: bool,
+
+        /// Output file path (prints to stdout if not specified)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// List or download the recordings of a Teams meeting
+    Recordings {
+        /// Event ID
+        event_id: Option<String>,
+
+        /// Use the current or next Teams meeting instead of an event ID
+        #[arg(long, conflicts_with = "event_id")]
+        next: bool,
+
+        /// Recording ID to download
+        #[arg(short, long)]
+        download: Option<String>,
+
+        /// Output file path (use - for stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Export events to an iCalendar (.ics) file
+    Export {
+        /// Start date (YYYY-MM-DD, today, next mon, ...)
+        #[arg(short, long, default_value = "today")]
+        start: String,
+
+        /// End date, inclusive (YYYY-MM-DD, +30d, ...)
+        #[arg(short, long, default_value = "+30d")]
+        end: String,
+
+        /// Calendar ID to use (defaults to primary calendar)
+        #[arg(short, long)]
+        calendar_id: Option<String>,
+
+        /// Export every occurrence instead of one event with an RRULE per series
+        #[arg(long)]
+        expand: bool,
+
+        /// Output file path (prints to stdout if not specified)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Import events from an iCalendar (.ics) file or a mail invite
+    Import {
+        /// .ics file to import (use - for stdin)
+        #[arg(required_unless_present = "from_mail")]
+        file: Option<String>,
+
+        /// Import the .ics attachments of a mail message
+        #[arg(long, conflicts_with = "file")]
+        from_mail: Option<String>,
+
+        /// Show what would be imported without creating events
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Copy the file's attendees onto the new events (sends them invitations)
+        #[arg(long)]
+        invite_attendees: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum, Default)]
+pub enum AgendaView {
+    /// Table of events
+    #[default]
+    List,
+    /// One time column per calendar, a section per day
+    Timeline,
+    /// Days (and calendars) side by side on a shared time axis
+    Grid,
 }
 
 #[derive(Debug, Serialize, Tabled)]
@@ -154,6 +427,53 @@ struct EventRow {
     location: String,
     #[tabled(rename = "Status")]
     status: String,
+    #[tabled(rename = "Repeat")]
+    repeat: String,
+}
+
+#[derive(Debug, Serialize, Tabled)]
+struct SlotRow {
+    #[tabled(rename = "#")]
+    rank: usize,
+    #[tabled(rename = "Start")]
+    start: String,
+    #[tabled(rename = "End")]
+    end: String,
+    #[tabled(rename = "Available")]
+    available: String,
+    #[tabled(rename = "Conflicts")]
+    conflicts: String,
+}
+
+#[derive(Debug, Serialize, Tabled)]
+struct TranscriptRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "Created")]
+    created: String,
+}
+
+#[derive(Debug, Serialize, Tabled)]
+struct RecordingRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "Created")]
+    created: String,
+    #[tabled(rename = "Source")]
+    source: String,
+    #[tabled(skip)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Tabled)]
+struct ImportRow {
+    #[tabled(rename = "Subject")]
+    subject: String,
+    #[tabled(rename = "Start")]
+    start: String,
+    #[tabled(rename = "Result")]
+    result: String,
 }
 
 #[derive(Debug, Serialize, Tabled)]
@@ -169,95 +489,295 @@ struct CalendarRow {
 }
 
 pub async fn execute(cmd: CalendarCommand, config: &Config, format: OutputFormat) -> Result<()> {
+    let tz = resolve_tz(cmd.tz.as_deref(), config)?;
+
     match cmd.command {
         CalendarSubcommand::Today {
             calendar_id,
             user_id,
-        } => today(config, calendar_id, user_id, format).await,
+            view,
+        } => agenda(config, calendar_id, user_id, 1, view, tz, format).await,
         CalendarSubcommand::Week {
             calendar_id,
             user_id,
-        } => week(config, calendar_id, user_id, format).await,
+            view,
+        } => agenda(config, calendar_id, user_id, 8, view, tz, format).await,
         CalendarSubcommand::List {
             start,
             end,
             calendar_id,
-        } => list(config, &start, &end, calendar_id, format).await,
+        } => list(config, &start, &end, calendar_id, tz, format).await,
         CalendarSubcommand::Show {
             event_id,
             calendar_id,
-        } => show(config, &event_id, calendar_id, format).await,
+        } => show(config, &event_id, calendar_id, tz, format).await,
+        CalendarSubcommand::FindTime {
+            attendees,
+            duration,
+            within,
+            working_hours,
+            limit,
+            book,
+            title,
+        } => {
+            let options = FindTimeOptions {
+                duration: parse_duration(&duration)?,
+                within,
+                working_hours,
+                limit,
+                book,
+                title,
+            };
+            find_time(config, &attendees, options, tz, format).await
+        }
         CalendarSubcommand::Calendars => calendars(config, format).await,
         CalendarSubcommand::FreeBusy { users, date } => {
-            free_busy(config, &users, date, format).await
+            free_busy(config, &users, date, tz, format).await
         }
         CalendarSubcommand::Create {
             title,
             start,
             end,
+            duration,
             attendees,
             location,
             teams,
             body,
+            repeat,
+            interval,
+            until,
+            count,
         } => {
+            let (start, end) = resolve_span(&start, end.as_deref(), duration.as_deref(), tz)?;
+            let recurrence = repeat
+                .map(|r| parse_recurrence(&r, interval, until.as_deref(), count, &start))
+                .transpose()?;
             create(
-                config, &title, &start, &end, attendees, location, teams, body, format,
+                config, &title, start, end, attendees, location, teams, body, recurrence, tz,
+                format,
             )
             .await
         }
+        CalendarSubcommand::Update {
+            event_id,
+            title,
+            start,
+            end,
+            duration,
+            location,
+            add_attendee,
+            remove_attendee,
+            body,
+            teams,
+            message,
+            this,
+            series,
+            calendar_id,
+            user_id,
+        } => {
+            let changes = EventChanges {
+                title,
+                start,
+                end,
+                duration,
+                location,
+                add_attendees: split_emails(add_attendee.as_deref()),
+                remove_attendees: split_emails(remove_attendee.as_deref()),
+                body,
+                teams,
+                message,
+            };
+            let target = EventTarget {
+                user_id,
+                calendar_id,
+            };
+            update(config, &event_id, changes, this, series, target, tz, format).await
+        }
         CalendarSubcommand::Rsvp {
             event_id,
             response,
             comment,
         } => rsvp(config, &event_id, &response, comment).await,
-        CalendarSubcommand::Delete { event_id } => delete(config, &event_id).await,
-        CalendarSubcommand::Join { event_id } => join(config, event_id, format).await,
+        CalendarSubcommand::Delete {
+            event_id,
+            this,
+            series,
+        } => delete(config, &event_id, this, series).await,
+        CalendarSubcommand::Join { event_id } => join(config, event_id, tz, format).await,
+        CalendarSubcommand::Chat {
+            event_id,
+            next,
+            limit,
+        } => chat(config, event_id, next, limit, tz, format).await,
+        CalendarSubcommand::Post {
+            event_id_or_message,
+            message,
+            next,
+            markdown,
+        } => post(config, event_id_or_message, message, next, markdown, tz).await,
+        CalendarSubcommand::Transcript {
+            event_id,
+            next,
+            list,
+            id,
+            text,
+            output,
+        } => transcript(config, event_id, next, list, id, text, output, tz, format).await,
+        CalendarSubcommand::Recordings {
+            event_id,
+            next,
+            download,
+            output,
+        } => recordings(config, event_id, next, download, output, tz, format).await,
+        CalendarSubcommand::Export {
+            start,
+            end,
+            calendar_id,
+            expand,
+            output,
+        } => export(config, &start, &end, calendar_id, expand, output, tz).await,
+        CalendarSubcommand::Import {
+            file,
+            from_mail,
+            dry_run,
+            invite_attendees,
+        } => {
+            import(
+                config,
+                file,
+                from_mail,
+                dry_run,
+                invite_attendees,
+                tz,
+                format,
+            )
+            .await
+        }
     }
 }
 
-async fn today(
-    config: &Config,
-    calendar_id: Option<String>,
-    user_id: Option<String>,
-    format: OutputFormat,
-) -> Result<()> {
-    let client = TeamsClient::new(config)?;
-    let now = chrono::Utc::now();
-    let start = now.format("%Y-%m-%dT00:00:00Z").to_string();
-    let end = now.format("%Y-%m-%dT23:59:59Z").to_string();
+/// Local-midnight bounds of `days` days starting today, as Graph UTC strings
+fn day_bounds(tz: Tz, days: i64) -> Result<(String, String)> {
+    let today = now_in(tz).date_naive();
+    let start = When::Day(today).start(tz)?;
+    let end = When::Day(today + Duration::days(days - 1)).end(tz)?;
+    Ok((to_graph_utc(&start), to_graph_utc(&end)))
+}
 
-    let events = if let Some(u_id) = user_id {
-        client.get_user_calendar_view(&u_id, &start, &end).await?
-    } else if let Some(id) = calendar_id {
-        client.get_calendar_events_for_id(&id, &start, &end).await?
-    } else {
-        client.get_calendar_today().await?
+/// Resolve an event's start and end from `--start` plus `--end` or `--duration`
+fn resolve_span(
+    start: &str,
+    end: Option<&str>,
+    duration: Option<&str>,
+    tz: Tz,
+) -> Result<(DateTime<Tz>, DateTime<Tz>)> {
+    let start_dt = parse_datetime(start, tz)?;
+    let end_dt = match (end, duration) {
+        (Some(end), _) => parse_datetime(end, tz)?,
+        (None, Some(d)) => start_dt + parse_duration(d)?,
+        (None, None) => start_dt + Duration::minutes(30),
     };
-    display_events(events.value, format);
-    Ok(())
+    if end_dt <= start_dt {
+        anyhow::bail!("End must be after start");
+    }
+    Ok((start_dt, end_dt))
 }
 
-async fn week(
+/// Graph date-time in the event's own zone, so Outlook shows the intended wall-clock time
+fn to_graph_local(dt: &DateTime<Tz>) -> DateTimeZone {
+    DateTimeZone {
+        date_time: dt.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        time_zone: dt.timezone().name().to_string(),
+    }
+}
+
+/// Events of the next `days` days, for one or several calendars
+#[allow(clippy::too_many_arguments)]
+async fn agenda(
     config: &Config,
     calendar_id: Option<String>,
     user_id: Option<String>,
+    days: i64,
+    view: AgendaView,
+    tz: Tz,
     format: OutputFormat,
 ) -> Result<()> {
     let client = TeamsClient::new(config)?;
-    let now = chrono::Utc::now();
-    let start = now.format("%Y-%m-%dT00:00:00Z").to_string();
-    let end = (now + chrono::Duration::days(7))
-        .format("%Y-%m-%dT23:59:59Z")
-        .to_string();
+    let (start, end) = day_bounds(tz, days)?;
 
-    let events = if let Some(u_id) = user_id {
-        client.get_user_calendar_view(&u_id, &start, &end).await?
+    let mut calendars = Vec::new();
+    if let Some(users) = user_id {
+        for user in split_emails(Some(&users)) {
+            let events = client.get_user_calendar_view(&user, &start, &end).await?;
+            calendars.push((user, events.value));
+        }
     } else if let Some(id) = calendar_id {
-        client.get_calendar_events_for_id(&id, &start, &end).await?
+        let events = client.get_calendar_events_for_id(&id, &start, &end).await?;
+        calendars.push(("Calendar".to_string(), events.value));
     } else {
-        client.get_calendar_week().await?
-    };
-    display_events(events.value, format);
+        let events = client.get_calendar_events(&start, &end).await?;
+        calendars.push(("Me".to_string(), events.value));
+    }
+
+    if calendars.len() == 1
+        && (matches!(view, AgendaView::List) || matches!(format, OutputFormat::Json))
+    {
+        if let Some((_, events)) = calendars.pop() {
+            display_events(events, tz, format);
+        }
+        return Ok(());
+    }
+    if matches!(format, OutputFormat::Json) {
+        let by_user: serde_json::Map<String, serde_json::Value> = calendars
+            .into_iter()
+            .map(|(user, events)| Ok((user, serde_json::to_value(events)?)))
+            .collect::<Result<_>>()?;
+        print_single(&by_user, format);
+        return Ok(());
+    }
+
+    let today = now_in(tz).date_naive();
+    let dates: Vec<NaiveDate> = (0..days).map(|d| today + Duration::days(d)).collect();
+    match view {
+        AgendaView::List => {
+            for (user, events) in calendars {
+                println!("{}", user.bold());
+                display_events(events, tz, format);
+            }
+        }
+        AgendaView::Timeline => {
+            for day in dates {
+                println!("{}", day.format("%A %d %B").to_string().bold());
+                let columns: Vec<AgendaColumn> = calendars
+                    .iter()
+                    .map(|(user, events)| AgendaColumn {
+                        title: user.clone(),
+                        day,
+                        events,
+                    })
+                    .collect();
+                println!("{}", render_agenda(&columns, tz));
+            }
+        }
+        AgendaView::Grid => {
+            let calendars = &calendars;
+            let columns: Vec<AgendaColumn> = dates
+                .iter()
+                .flat_map(|&day| {
+                    calendars.iter().map(move |(user, events)| AgendaColumn {
+                        title: if calendars.len() == 1 {
+                            day.format("%a %d").to_string()
+                        } else {
+                            format!("{} {}", day.format("%a %d"), user)
+                        },
+                        day,
+                        events,
+                    })
+                })
+                .collect();
+            print!("{}", render_agenda(&columns, tz));
+        }
+    }
     Ok(())
 }
 
@@ -266,11 +786,13 @@ async fn list(
     start: &str,
     end: &str,
     calendar_id: Option<String>,
+    tz: Tz,
     format: OutputFormat,
 ) -> Result<()> {
     let client = TeamsClient::new(config)?;
-    let start_dt = format!("{}T00:00:00Z", start);
-    let end_dt = format!("{}T23:59:59Z", end);
+    let now = now_in(tz);
+    let start_dt = to_graph_utc(&parse_when(start, now)?.start(tz)?);
+    let end_dt = to_graph_utc(&parse_when(end, now)?.end(tz)?);
 
     let events = if let Some(id) = calendar_id {
         client
@@ -279,11 +801,11 @@ async fn list(
     } else {
         client.get_calendar_events(&start_dt, &end_dt).await?
     };
-    display_events(events.value, format);
+    display_events(events.value, tz, format);
     Ok(())
 }
 
-fn display_events(events: Vec<crate::types::CalendarEvent>, format: OutputFormat) {
+fn display_events(events: Vec<CalendarEvent>, tz: Tz, format: OutputFormat) {
     match format {
         OutputFormat::Json => {
             print_single(&events, format);
@@ -299,7 +821,7 @@ fn display_events(events: Vec<crate::types::CalendarEvent>, format: OutputFormat
                 .map(|e| {
                     let time = e
                         .start
-                        .map(|s| format_local_time(&s.date_time, "%m-%d %H:%M"))
+                        .map(|s| format_in_tz(&s.date_time, "%m-%d %H:%M", tz))
                         .unwrap_or_default();
 
                     let location = e
@@ -319,12 +841,21 @@ fn display_events(events: Vec<crate::types::CalendarEvent>, format: OutputFormat
                         .and_then(|r| r.response)
                         .unwrap_or_else(|| "none".to_string());
 
+                    let repeat = match e.event_type.as_deref() {
+                        Some("seriesMaster") => "series",
+                        Some("occurrence") => "occurrence",
+                        Some("exception") => "exception",
+                        _ => "",
+                    }
+                    .to_string();
+
                     EventRow {
                         id: truncate(&e.id.unwrap_or_default(), 12),
                         time,
                         subject: truncate(&e.subject.unwrap_or_default(), 35),
                         location: truncate(&location, 20),
                         status,
+                        repeat,
                     }
                 })
                 .collect();
@@ -338,6 +869,7 @@ async fn show(
     config: &Config,
     event_id: &str,
     _calendar_id: Option<String>,
+    tz: Tz,
     format: OutputFormat,
 ) -> Result<()> {
     let client = TeamsClient::new(config)?;
@@ -352,16 +884,35 @@ async fn show(
             println!("Subject: {}", event.subject.unwrap_or_default());
 
             if let Some(start) = event.start {
-                println!("Start: {} ({})", start.date_time, start.time_zone);
+                println!(
+                    "Start: {} ({})",
+                    format_in_tz(&start.date_time, "%Y-%m-%d %H:%M", tz),
+                    tz
+                );
             }
             if let Some(end) = event.end {
-                println!("End: {} ({})", end.date_time, end.time_zone);
+                println!(
+                    "End: {} ({})",
+                    format_in_tz(&end.date_time, "%Y-%m-%d %H:%M", tz),
+                    tz
+                );
             }
 
             if let Some(loc) = event.location.and_then(|l| l.display_name) {
                 println!("Location: {}", loc);
             }
 
+            if let Some(recurrence) = &event.recurrence {
+                println!("Repeats: {}", describe_recurrence(recurrence));
+            }
+            if let Some(master_id) = &event.series_master_id {
+                println!(
+                    "Series: {} ({})",
+                    master_id,
+                    event.event_type.as_deref().unwrap_or("occurrence")
+                );
+            }
+
             if let Some(organizer) = event.organizer.and_then(|o| o.email_address) {
                 let name = organizer.name.unwrap_or_default();
                 let email = organizer.address.unwrap_or_default();
@@ -392,6 +943,9 @@ async fn show(
                     .or(event.online_meeting_url)
                 {
                     println!("Join URL: {}", url);
+                    if let Some(thread_id) = meeting_thread_id(&url) {
+                        println!("Chat ID: {}", thread_id);
+                    }
                 }
             }
 
@@ -411,39 +965,40 @@ async fn show(
 async fn create(
     config: &Config,
     title: &str,
-    start: &str,
-    end: &str,
+    start: DateTime<Tz>,
+    end: DateTime<Tz>,
     attendees: Option<String>,
     location: Option<String>,
     teams: bool,
     body: Option<String>,
+    recurrence: Option<PatternedRecurrence>,
+    tz: Tz,
     format: OutputFormat,
 ) -> Result<()> {
     let client = TeamsClient::new(config)?;
 
-    // Parse attendees
-    let attendee_list: Option<Vec<AttendeeRequest>> = attendees.map(|a| {
-        a.split(',')
-            .map(|email| AttendeeRequest {
-                email_address: EmailAddressSimple {
-                    name: None,
-                    address: Some(email.trim().to_string()),
-                },
-                attendee_type: "required".to_string(),
-            })
-            .collect()
-    });
+    // Attendees may be names, resolved through people ranking, then the directory
+    let attendee_list: Option<Vec<AttendeeRequest>> = match attendees {
+        Some(a) => Some(
+            resolve_addresses(&client, &a)
+                .await?
+                .into_iter()
+                .map(|email| AttendeeRequest {
+                    email_address: EmailAddressSimple {
+                        name: None,
+                        address: Some(email),
+                    },
+                    attendee_type: "required".to_string(),
+                })
+                .collect(),
+        ),
+        None => None,
+    };
 
     let request = CreateEventRequest {
         subject: title.to_string(),
-        start: DateTimeZone {
-            date_time: format!("{}:00", start),
-            time_zone: "UTC".to_string(),
-        },
-        end: DateTimeZone {
-            date_time: format!("{}:00", end),
-            time_zone: "UTC".to_string(),
-        },
+        start: to_graph_local(&start),
+        end: to_graph_local(&end),
         body: body.map(|b| EventBody {
             content_type: "text".to_string(),
             content: b,
@@ -459,6 +1014,9 @@ async fn create(
         } else {
             None
         },
+        recurrence,
+        is_all_day: None,
+        single_value_extended_properties: None,
     };
 
     let event = client.create_calendar_event(request).await?;
@@ -472,6 +1030,12 @@ async fn create(
                 "Event created: {}",
                 event.subject.unwrap_or_default()
             ));
+            println!(
+                "When: {} - {} ({})",
+                start.format("%Y-%m-%d %H:%M"),
+                end.format("%H:%M"),
+                tz
+            );
             if let Some(id) = event.id {
                 println!("ID: {}", id);
             }
@@ -504,45 +1068,441 @@ async fn rsvp(
     Ok(())
 }
 
-async fn delete(config: &Config, event_id: &str) -> Result<()> {
-    let client = TeamsClient::new(config)?;
-    client.delete_calendar_event(event_id).await?;
-    print_success("Event deleted");
-    Ok(())
+/// Fields to change with `calendar update`
+struct EventChanges {
+    title: Option<String>,
+    start: Option<String>,
+    end: Option<String>,
+    duration: Option<String>,
+    location: Option<String>,
+    add_attendees: Vec<String>,
+    remove_attendees: Vec<String>,
+    body: Option<String>,
+    teams: bool,
+    message: Option<String>,
 }
 
-async fn join(config: &Config, event_id: Option<String>, format: OutputFormat) -> Result<()> {
-    let client = TeamsClient::new(config)?;
+/// Which calendar an event lives in (own, secondary or shared)
+#[derive(Default)]
+struct EventTarget {
+    user_id: Option<String>,
+    calendar_id: Option<String>,
+}
 
-    let event = if let Some(id) = event_id {
-        client.get_calendar_event(&id).await?
-    } else {
-        // Find the next meeting with a join URL
-        let events = client.get_calendar_today().await?;
-        let now = chrono::Utc::now();
+fn split_emails(list: Option<&str>) -> Vec<String> {
+    list.map(|l| {
+        l.split(',')
+            .map(|e| e.trim().to_string())
+            .filter(|e| !e.is_empty())
+            .collect()
+    })
+    .unwrap_or_default()
+}
 
-        events
-            .value
-            .into_iter()
-            .find(|e| {
-                e.is_online_meeting == Some(true)
-                    && e.start
-                        .as_ref()
-                        .map(|s| {
-                            chrono::DateTime::parse_from_rfc3339(&format!("{}Z", s.date_time))
-                                .map(|dt| dt > now - chrono::Duration::minutes(30))
-                                .unwrap_or(false)
-                        })
-                        .unwrap_or(false)
-            })
-            .ok_or_else(|| anyhow::anyhow!("No upcoming Teams meetings found today"))?
+#[allow(clippy::too_many_arguments)]
+async fn update(
+    config: &Config,
+    event_id: &str,
+    changes: EventChanges,
+    this: bool,
+    series: bool,
+    target: EventTarget,
+    tz: Tz,
+    format: OutputFormat,
+) -> Result<()> {
+    // A --message on its own only notifies the attendees
+    let message_only = changes.title.is_none()
+        && changes.start.is_none()
+        && changes.end.is_none()
+        && changes.duration.is_none()
+        && changes.location.is_none()
+        && changes.add_attendees.is_empty()
+        && changes.remove_attendees.is_empty()
+        && changes.body.is_none()
+        && !changes.teams;
+    if message_only && changes.message.is_none() {
+        print_error(
+            "Nothing to update. Pass at least one of --title, --start, --end, --location, --message, ...",
+        );
+        return Ok(());
+    }
+
+    let user_id = target.user_id.as_deref();
+    let calendar_id = target.calendar_id.as_deref();
+    // The notification goes out from the mailbox that owns the calendar
+    let client = TeamsClient::new(config)?.with_mailbox(user_id);
+    let (event_id, what) = resolve_scope(&client, event_id, this, series, &target).await?;
+    let current = client
+        .get_calendar_event_in(&event_id, user_id, calendar_id)
+        .await?;
+
+    let mut request = UpdateEventRequest {
+        subject: changes.title,
+        location: changes.location.map(|l| Location {
+            display_name: Some(l),
+            location_uri: None,
+        }),
+        body: changes.body.map(|b| EventBody {
+            content_type: "text".to_string(),
+            content: b,
+        }),
+        ..Default::default()
     };
 
-    let join_url = event
-        .online_meeting
-        .and_then(|m| m.join_url)
-        .or(event.online_meeting_url)
-        .ok_or_else(|| anyhow::anyhow!("No join URL found for this event"))?;
+    if changes.teams {
+        request.is_online_meeting = Some(true);
+        request.online_meeting_provider = Some("teamsForBusiness".to_string());
+    }
+
+    // Rescheduling keeps the current duration unless --end or --duration says otherwise
+    if changes.start.is_some() || changes.end.is_some() || changes.duration.is_some() {
+        let current_start = current.start.as_ref().and_then(graph_time_utc);
+        let current_end = current.end.as_ref().and_then(graph_time_utc);
+        let (Some(current_start), Some(current_end)) = (current_start, current_end) else {
+            anyhow::bail!("Could not read the current start/end of this event");
+        };
+
+        let start = match &changes.start {
+            Some(s) => parse_datetime(s, tz)?,
+            None => current_start.with_timezone(&tz),
+        };
+        let end = match (&changes.end, &changes.duration) {
+            (Some(e), _) => parse_datetime(e, tz)?,
+            (None, Some(d)) => start + parse_duration(d)?,
+            (None, None) => start + (current_end - current_start),
+        };
+        if end <= start {
+            anyhow::bail!("End must be after start");
+        }
+        request.start = Some(to_graph_local(&start));
+        request.end = Some(to_graph_local(&end));
+    }
+
+    if !changes.add_attendees.is_empty() || !changes.remove_attendees.is_empty() {
+        let mut attendees: Vec<AttendeeRequest> = current
+            .attendees
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|a| {
+                Some(AttendeeRequest {
+                    email_address: a.email_address?,
+                    attendee_type: a.attendee_type.unwrap_or_else(|| "required".to_string()),
+                })
+            })
+            .collect();
+
+        let matches = |a: &AttendeeRequest, email: &str| {
+            a.email_address
+                .address
+                .as_deref()
+                .is_some_and(|addr| addr.eq_ignore_ascii_case(email))
+        };
+
+        for email in &changes.remove_attendees {
+            let before = attendees.len();
+            attendees.retain(|a| !matches(a, email));
+            if attendees.len() == before {
+                print_warning(&format!("{} is not an attendee", email));
+            }
+        }
+        for email in &changes.add_attendees {
+            if !attendees.iter().any(|a| matches(a, email)) {
+                attendees.push(AttendeeRequest {
+                    email_address: EmailAddressSimple {
+                        name: None,
+                        address: Some(email.clone()),
+                    },
+                    attendee_type: "required".to_string(),
+                });
+            }
+        }
+        request.attendees = Some(attendees);
+    }
+
+    let event = if message_only {
+        current
+    } else {
+        client
+            .update_calendar_event(&event_id, &request, user_id, calendar_id)
+            .await?
+    };
+
+    if let Some(message) = changes.message {
+        let recipients: Vec<String> = event
+            .attendees
+            .iter()
+            .flatten()
+            .filter_map(|a| a.email_address.as_ref()?.address.clone())
+            .collect();
+        if recipients.is_empty() {
+            print_warning("No attendees to notify");
+        } else {
+            let subject = format!("Updated: {}", event.subject.as_deref().unwrap_or_default());
+            client
+                .send_mail(
+                    recipients.iter().map(|r| r.as_str()).collect(),
+                    &subject,
+                    &message,
+                    None,
+                    "Text",
+                    None,
+                )
+                .await?;
+        }
+    }
+
+    match format {
+        OutputFormat::Json => print_single(&event, format),
+        _ => {
+            print_success(&format!(
+                "{} updated: {}",
+                what,
+                event.subject.unwrap_or_default()
+            ));
+            if let (Some(start), Some(end)) = (&event.start, &event.end) {
+                println!(
+                    "When: {} - {} ({})",
+                    format_in_tz(&start.date_time, "%Y-%m-%d %H:%M", tz),
+                    format_in_tz(&end.date_time, "%H:%M", tz),
+                    tz
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn delete(config: &Config, event_id: &str, this: bool, series: bool) -> Result<()> {
+    let client = TeamsClient::new(config)?;
+    let (target, what) =
+        resolve_scope(&client, event_id, this, series, &EventTarget::default()).await?;
+    client.delete_calendar_event(&target).await?;
+    print_success(&format!("{} deleted", what));
+    Ok(())
+}
+
+/// Resolve which event a `--this`/`--series` scoped command applies to.
+/// Returns the event ID to act on and a label for messages.
+async fn resolve_scope(
+    client: &TeamsClient,
+    event_id: &str,
+    this: bool,
+    series: bool,
+    target: &EventTarget,
+) -> Result<(String, &'static str)> {
+    if !this && !series {
+        return Ok((event_id.to_string(), "Event"));
+    }
+
+    let event = client
+        .get_calendar_event_in(
+            event_id,
+            target.user_id.as_deref(),
+            target.calendar_id.as_deref(),
+        )
+        .await?;
+    let event_type = event.event_type.as_deref().unwrap_or("singleInstance");
+
+    if series {
+        return match (event_type, event.series_master_id) {
+            ("seriesMaster", _) => Ok((event_id.to_string(), "Series")),
+            (_, Some(master_id)) => Ok((master_id, "Series")),
+            _ => anyhow::bail!("This event is not part of a recurring series"),
+        };
+    }
+
+    if event_type == "seriesMaster" {
+        anyhow::bail!(
+            "This ID is the series itself. Use an occurrence ID from `calendar list` with --this"
+        );
+    }
+    Ok((event_id.to_string(), "Occurrence"))
+}
+
+/// Build a recurrence from `--repeat`, anchored on the first occurrence
+fn parse_recurrence(
+    repeat: &str,
+    interval: u32,
+    until: Option<&str>,
+    count: Option<u32>,
+    start: &DateTime<Tz>,
+) -> Result<PatternedRecurrence> {
+    let tz = start.timezone();
+    let (kind, arg) = match repeat.split_once(':') {
+        Some((kind, arg)) => (kind.trim().to_lowercase(), Some(arg.trim())),
+        None => (repeat.trim().to_lowercase(), None),
+    };
+
+    let weekday_name = |d: Weekday| {
+        match d {
+            Weekday::Mon => "monday",
+            Weekday::Tue => "tuesday",
+            Weekday::Wed => "wednesday",
+            Weekday::Thu => "thursday",
+            Weekday::Fri => "friday",
+            Weekday::Sat => "saturday",
+            Weekday::Sun => "sunday",
+        }
+        .to_string()
+    };
+
+    let mut pattern = RecurrencePattern {
+        pattern_type: String::new(),
+        interval: interval.max(1),
+        days_of_week: None,
+        day_of_month: None,
+        month: None,
+        first_day_of_week: None,
+        index: None,
+    };
+
+    match kind.as_str() {
+        "daily" => pattern.pattern_type = "daily".to_string(),
+        "weekdays" => {
+            pattern.pattern_type = "weekly".to_string();
+            pattern.days_of_week = Some(
+                ["monday", "tuesday", "wednesday", "thursday", "friday"]
+                    .map(String::from)
+                    .to_vec(),
+            );
+        }
+        "weekly" => {
+            let days = match arg {
+                Some(list) => list
+                    .split(',')
+                    .map(|d| {
+                        d.trim()
+                            .parse::<Weekday>()
+                            .map(weekday_name)
+                            .map_err(|_| anyhow::anyhow!("Unknown weekday '{}'", d.trim()))
+                    })
+                    .collect::<Result<Vec<_>>>()?,
+                None => vec![weekday_name(start.weekday())],
+            };
+            pattern.pattern_type = "weekly".to_string();
+            pattern.days_of_week = Some(days);
+            pattern.first_day_of_week = Some("monday".to_string());
+        }
+        "monthly" => {
+            let day = match arg {
+                Some(d) => d
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|d| (1..=31).contains(d))
+                    .ok_or_else(|| anyhow::anyhow!("Invalid day of month '{}'", d))?,
+                None => start.day(),
+            };
+            pattern.pattern_type = "absoluteMonthly".to_string();
+            pattern.day_of_month = Some(day);
+        }
+        "yearly" => {
+            pattern.pattern_type = "absoluteYearly".to_string();
+            pattern.day_of_month = Some(start.day());
+            pattern.month = Some(start.month());
+        }
+        other => anyhow::bail!(
+            "Unknown repeat '{}'. Use daily, weekdays, weekly[:mon,wed], monthly[:15] or yearly",
+            other
+        ),
+    }
+
+    let start_date = start.date_naive().format("%Y-%m-%d").to_string();
+    let range = match (until, count) {
+        (Some(until), _) => {
+            let end = parse_when(until, now_in(tz))?.start(tz)?.date_naive();
+            if end < start.date_naive() {
+                anyhow::bail!("--until must not be before the first occurrence");
+            }
+            RecurrenceRange {
+                range_type: "endDate".to_string(),
+                start_date,
+                end_date: Some(end.format("%Y-%m-%d").to_string()),
+                number_of_occurrences: None,
+                recurrence_time_zone: Some(tz.name().to_string()),
+            }
+        }
+        (None, Some(n)) => RecurrenceRange {
+            range_type: "numbered".to_string(),
+            start_date,
+            end_date: None,
+            number_of_occurrences: Some(n),
+            recurrence_time_zone: Some(tz.name().to_string()),
+        },
+        (None, None) => RecurrenceRange {
+            range_type: "noEnd".to_string(),
+            start_date,
+            end_date: None,
+            number_of_occurrences: None,
+            recurrence_time_zone: Some(tz.name().to_string()),
+        },
+    };
+
+    Ok(PatternedRecurrence { pattern, range })
+}
+
+/// Human-readable summary of a recurrence, e.g. "every week on monday, wednesday until 2026-12-31"
+fn describe_recurrence(recurrence: &PatternedRecurrence) -> String {
+    let pattern = &recurrence.pattern;
+    let unit = match pattern.pattern_type.as_str() {
+        "daily" => "day",
+        "weekly" => "week",
+        "absoluteMonthly" | "relativeMonthly" => "month",
+        _ => "year",
+    };
+    let mut text = if pattern.interval > 1 {
+        format!("every {} {}s", pattern.interval, unit)
+    } else {
+        format!("every {}", unit)
+    };
+
+    match pattern.pattern_type.as_str() {
+        "weekly" | "relativeMonthly" | "relativeYearly" => {
+            if let Some(days) = &pattern.days_of_week {
+                text.push_str(&format!(" on {}", days.join(", ")));
+            }
+        }
+        "absoluteMonthly" | "absoluteYearly" => {
+            if let Some(day) = pattern.day_of_month.filter(|d| *d > 0) {
+                text.push_str(&format!(" on day {}", day));
+            }
+        }
+        _ => {}
+    }
+
+    let range = &recurrence.range;
+    match range.range_type.as_str() {
+        "endDate" => {
+            if let Some(end) = &range.end_date {
+                text.push_str(&format!(" until {}", end));
+            }
+        }
+        "numbered" => {
+            if let Some(n) = range.number_of_occurrences {
+                text.push_str(&format!(", {} times", n));
+            }
+        }
+        _ => {}
+    }
+    text
+}
+
+async fn join(
+    config: &Config,
+    event_id: Option<String>,
+    tz: Tz,
+    format: OutputFormat,
+) -> Result<()> {
+    let client = TeamsClient::new(config)?;
+
+    let event = if let Some(id) = event_id {
+        client.get_calendar_event(&id).await?
+    } else {
+        next_meeting(&client, tz).await?
+    };
+
+    let join_url = event_join_url(&event)
+        .ok_or_else(|| anyhow::anyhow!("No join URL found for this event"))?;
 
     match format {
         OutputFormat::Json => {
@@ -564,16 +1524,761 @@ async fn join(config: &Config, event_id: Option<String>, format: OutputFormat) -
     Ok(())
 }
 
+/// Find the current or next Teams meeting today (started less than 30 minutes ago or later)
+async fn next_meeting(client: &TeamsClient, tz: Tz) -> Result<CalendarEvent> {
+    let (start, end) = day_bounds(tz, 1)?;
+    let events = client.get_calendar_events(&start, &end).await?;
+    let now = chrono::Utc::now();
+
+    events
+        .value
+        .into_iter()
+        .find(|e| {
+            e.is_online_meeting == Some(true)
+                && e.start
+                    .as_ref()
+                    .map(|s| {
+                        chrono::DateTime::parse_from_rfc3339(&format!("{}Z", s.date_time))
+                            .map(|dt| dt > now - chrono::Duration::minutes(30))
+                            .unwrap_or(false)
+                    })
+                    .unwrap_or(false)
+        })
+        .ok_or_else(|| anyhow::anyhow!("No upcoming Teams meetings found today"))
+}
+
+/// Resolve an event from its ID, or the current/next meeting when `next` is set
+async fn resolve_event(
+    client: &TeamsClient,
+    event_id: Option<&str>,
+    next: bool,
+    tz: Tz,
+) -> Result<CalendarEvent> {
+    match event_id {
+        Some(id) if !next => client.get_calendar_event(id).await,
+        _ if next => next_meeting(client, tz).await,
+        _ => anyhow::bail!("Provide an event ID or use --next"),
+    }
+}
+
+pub(super) fn event_join_url(event: &CalendarEvent) -> Option<String> {
+    event
+        .online_meeting
+        .as_ref()
+        .and_then(|m| m.join_url.clone())
+        .or_else(|| event.online_meeting_url.clone())
+}
+
+/// Extract the meeting chat thread ID (`19:meeting_...@thread.v2`) from a Teams join URL
+fn meeting_thread_id(join_url: &str) -> Option<String> {
+    let (_, rest) = join_url.split_once("/meetup-join/")?;
+    let segment = rest.split(['/', '?']).next()?;
+    let thread_id = urlencoding::decode(segment).ok()?.into_owned();
+    thread_id.starts_with("19:").then_some(thread_id)
+}
+
+/// Resolve the chat thread of a Teams meeting event
+async fn resolve_meeting_thread(client: &TeamsClient, event: &CalendarEvent) -> Result<String> {
+    let join_url = event_join_url(event)
+        .ok_or_else(|| anyhow::anyhow!("This event is not a Teams meeting"))?;
+
+    if let Some(thread_id) = meeting_thread_id(&join_url) {
+        return Ok(thread_id);
+    }
+
+    // Short join links (/meet/...) don't embed the thread; ask Graph for the meeting
+    client
+        .get_online_meeting_by_join_url(&join_url)
+        .await?
+        .and_then(|m| m.chat_info)
+        .and_then(|c| c.thread_id)
+        .ok_or_else(|| anyhow::anyhow!("Could not find the chat for this meeting"))
+}
+
+async fn chat(
+    config: &Config,
+    event_id: Option<String>,
+    next: bool,
+    limit: usize,
+    tz: Tz,
+    format: OutputFormat,
+) -> Result<()> {
+    let client = TeamsClient::new(config)?;
+    let event = resolve_event(&client, event_id.as_deref(), next, tz).await?;
+    let thread_id = resolve_meeting_thread(&client, &event).await?;
+
+    if !matches!(format, OutputFormat::Json) {
+        println!(
+            "Meeting chat: {} ({})",
+            event.subject.unwrap_or_default(),
+            thread_id
+        );
+    }
+    super::chats::messages(config, &thread_id, limit, format).await
+}
+
+async fn post(
+    config: &Config,
+    event_id_or_message: String,
+    message: Option<String>,
+    next: bool,
+    markdown: bool,
+    tz: Tz,
+) -> Result<()> {
+    // With --next, the first positional arg is the message, not event_id
+    let (event_id, content) = if next {
+        (None, Some(event_id_or_message))
+    } else {
+        (Some(event_id_or_message), message)
+    };
+
+    let Some(content) = content.filter(|c| !c.is_empty()) else {
+        print_error("Message cannot be empty");
+        return Ok(());
+    };
+
+    let client = TeamsClient::new(config)?;
+    let event = resolve_event(&client, event_id.as_deref(), next, tz).await?;
+    let thread_id = resolve_meeting_thread(&client, &event).await?;
+
+    let html_body = if markdown {
+        markdown_to_html(&content)
+    } else {
+        format!("<p>{}</p>", html_escape(&content))
+    };
+
+    client.send_message(&thread_id, &html_body, None).await?;
+    print_success(&format!(
+        "Message posted to meeting chat: {}",
+        event.subject.unwrap_or_default()
+    ));
+    Ok(())
+}
+
+/// Resolve the Graph online meeting ID of a Teams meeting event
+async fn resolve_online_meeting_id(client: &TeamsClient, event: &CalendarEvent) -> Result<String> {
+    let join_url = event_join_url(event)
+        .ok_or_else(|| anyhow::anyhow!("This event is not a Teams meeting"))?;
+
+    client
+        .get_online_meeting_by_join_url(&join_url)
+        .await?
+        .map(|m| m.id)
+        .ok_or_else(|| anyhow::anyhow!("Could not find the online meeting for this event"))
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn transcript(
+    config: &Config,
+    event_id: Option<String>,
+    next: bool,
+    list: bool,
+    transcript_id: Option<String>,
+    text: bool,
+    output: Option<String>,
+    tz: Tz,
+    format: OutputFormat,
+) -> Result<()> {
+    let client = TeamsClient::new(config)?;
+    let event = resolve_event(&client, event_id.as_deref(), next, tz).await?;
+    let meeting_id = resolve_online_meeting_id(&client, &event).await?;
+    let transcripts = client.get_meeting_transcripts(&meeting_id).await?.value;
+
+    if list {
+        let rows: Vec<TranscriptRow> = transcripts
+            .into_iter()
+            .map(|t| TranscriptRow {
+                id: t.id,
+                created: t
+                    .created_date_time
+                    .map(|c| format_in_tz(&c, "%Y-%m-%d %H:%M", tz))
+                    .unwrap_or_default(),
+            })
+            .collect();
+        print_output(&rows, format);
+        return Ok(());
+    }
+
+    let transcript_id = match transcript_id {
+        Some(id) => id,
+        None => transcripts
+            .iter()
+            .max_by(|a, b| a.created_date_time.cmp(&b.created_date_time))
+            .map(|t| t.id.clone())
+            .ok_or_else(|| anyhow::anyhow!("No transcripts found for this meeting"))?,
+    };
+
+    let vtt = client
+        .get_meeting_transcript_content(&meeting_id, &transcript_id)
+        .await?;
+    let content = if text { vtt_to_text(&vtt) } else { vtt };
+
+    match output {
+        Some(path) if path != "-" => {
+            std::fs::write(&path, &content)?;
+            print_success(&format!("Transcript saved to {}", path));
+        }
+        _ => print!("{}", content),
+    }
+    Ok(())
+}
+
+/// Convert a Teams WebVTT transcript to plain text, one `Speaker: text` line per turn
+fn vtt_to_text(vtt: &str) -> String {
+    let mut turns: Vec<(String, String)> = Vec::new();
+
+    let lines: Vec<&str> = vtt.lines().map(str::trim).collect();
+    for (i, &line) in lines.iter().enumerate() {
+        // A cue identifier (numeric, or Teams' `<guid>/12-0`) is the line just before its timing
+        let is_cue_id = lines.get(i + 1).is_some_and(|next| next.contains("-->"));
+        if line.is_empty()
+            || line == "WEBVTT"
+            || line.contains("-->")
+            || line.starts_with("NOTE")
+            || is_cue_id
+        {
+            continue;
+        }
+
+        // Teams marks speakers with voice spans: <v Jane Doe>Hello</v>
+        let (speaker, text) = match line
+            .strip_prefix("<v ")
+            .and_then(|rest| rest.split_once('>'))
+        {
+            Some((speaker, text)) => (speaker.to_string(), text.trim_end_matches("</v>")),
+            None => (String::new(), line),
+        };
+
+        match turns.last_mut() {
+            Some((last, existing)) if *last == speaker || speaker.is_empty() => {
+                existing.push(' ');
+                existing.push_str(text);
+            }
+            _ => turns.push((speaker, text.to_string())),
+        }
+    }
+
+    turns
+        .into_iter()
+        .map(|(speaker, text)| {
+            if speaker.is_empty() {
+                format!("{}\n", text)
+            } else {
+                format!("{}: {}\n", speaker, text)
+            }
+        })
+        .collect()
+}
+
+async fn recordings(
+    config: &Config,
+    event_id: Option<String>,
+    next: bool,
+    download: Option<String>,
+    output: Option<String>,
+    tz: Tz,
+    format: OutputFormat,
+) -> Result<()> {
+    let client = TeamsClient::new(config)?;
+    let event = resolve_event(&client, event_id.as_deref(), next, tz).await?;
+    let meeting_id = resolve_online_meeting_id(&client, &event).await?;
+
+    // Graph only exposes recordings to the organizer; attendees find them in the meeting chat
+    let mut rows: Vec<RecordingRow> = match client.get_meeting_recordings(&meeting_id).await? {
+        Some(recordings) => recordings
+            .value
+            .into_iter()
+            .map(|r| RecordingRow {
+                id: r.id,
+                created: r
+                    .created_date_time
+                    .map(|c| format_in_tz(&c, "%Y-%m-%d %H:%M", tz))
+                    .unwrap_or_default(),
+                source: "graph".to_string(),
+                url: None,
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+    if rows.is_empty() {
+        let thread_id = resolve_meeting_thread(&client, &event).await?;
+        let start = event.start.as_ref().and_then(graph_time_utc);
+        rows = chat_recordings(&client, &thread_id, start, tz).await?;
+    }
+
+    let Some(recording_id) = download else {
+        print_output(&rows, format);
+        return Ok(());
+    };
+
+    let row = rows
+        .iter()
+        .find(|r| r.id == recording_id)
+        .ok_or_else(|| anyhow::anyhow!("Recording not found: {}", recording_id))?;
+
+    let (content_type, bytes) = match &row.url {
+        Some(url) => client.download_sharepoint_file(url).await?,
+        None => {
+            client
+                .download_meeting_recording(&meeting_id, &row.id)
+                .await?
+        }
+    };
+
+    if output.as_deref() == Some("-") {
+        use std::io::Write;
+        std::io::stdout().write_all(&bytes)?;
+        std::io::stdout().flush()?;
+    } else {
+        let output_path = output.unwrap_or_else(|| {
+            let subject = event.subject.as_deref().unwrap_or("recording");
+            format!(
+                "{}.{}",
+                subject.replace(['/', '\\'], "_"),
+                recording_extension(&content_type, row.url.as_deref())
+            )
+        });
+        std::fs::write(&output_path, &bytes)?;
+        print_success(&format!(
+            "Downloaded {} ({}, {} bytes)",
+            output_path,
+            content_type,
+            bytes.len()
+        ));
+    }
+    Ok(())
+}
+
+/// File extension of a downloaded recording: from its content type, else from the
+/// file name in its SharePoint link, else mp4 (what Teams records)
+fn recording_extension(content_type: &str, url: Option<&str>) -> String {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+    let known = match mime.as_str() {
+        "video/mp4" => Some("mp4"),
+        "video/webm" => Some("webm"),
+        "video/quicktime" => Some("mov"),
+        "video/x-matroska" => Some("mkv"),
+        "audio/mp4" | "audio/x-m4a" => Some("m4a"),
+        "audio/mpeg" => Some("mp3"),
+        _ => None,
+    };
+    if let Some(ext) = known {
+        return ext.to_string();
+    }
+
+    url.and_then(|url| {
+        let path = url.split(['?', '#']).next()?;
+        let name = path.rsplit('/').next()?;
+        let (_, ext) = name.rsplit_once('.')?;
+        (!ext.is_empty() && ext.len() <= 5 && ext.chars().all(|c| c.is_ascii_alphanumeric()))
+            .then(|| ext.to_lowercase())
+    })
+    .unwrap_or_else(|| "mp4".to_string())
+}
+
+/// Meeting chat pages searched for recordings, newest first
+const RECORDING_CHAT_PAGES: usize = 10;
+
+/// Find recording links posted by Teams into a meeting chat, paging back
+/// until one is found or the messages predate the meeting's `start`
+async fn chat_recordings(
+    client: &TeamsClient,
+    thread_id: &str,
+    start: Option<DateTime<Utc>>,
+    tz: Tz,
+) -> Result<Vec<RecordingRow>> {
+    let url_pattern = regex::Regex::new(r#"https://[^"'\s<>]+sharepoint\.com[^"'\s<>]*"#)?;
+    let mut page = client.get_conversations(thread_id, None).await?;
+    let mut rows = Vec::new();
+
+    for pages in 1.. {
+        let before_start = start.is_some_and(|start| {
+            page.messages.iter().any(|m| {
+                m.original_arrival_time
+                    .as_deref()
+                    .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                    .is_some_and(|t| t < start)
+            })
+        });
+        let older = page.metadata.take().and_then(|m| m.backward_link);
+
+        rows.extend(
+            page.messages
+                .into_iter()
+                .filter(|m| {
+                    m.message_type
+                        .as_deref()
+                        .is_some_and(|t| t.contains("CallRecording"))
+                })
+                .filter_map(|m| {
+                    let content = m.content.as_deref()?;
+                    let url = url_pattern.find(content)?.as_str().replace("&amp;", "&");
+                    Some(RecordingRow {
+                        id: m.id.unwrap_or_default(),
+                        created: m
+                            .original_arrival_time
+                            .map(|t| format_in_tz(&t, "%Y-%m-%d %H:%M", tz))
+                            .unwrap_or_default(),
+                        source: "chat".to_string(),
+                        url: Some(url),
+                    })
+                }),
+        );
+
+        match older {
+            Some(link) if rows.is_empty() && !before_start && pages < RECORDING_CHAT_PAGES => {
+                page = client.get_conversations_page(&link).await?;
+            }
+            _ => break,
+        }
+    }
+    Ok(rows)
+}
+
+struct FindTimeOptions {
+    duration: Duration,
+    within: String,
+    working_hours: Option<String>,
+    limit: usize,
+    book: bool,
+    title: String,
+}
+
+/// Weekly working hours in a given zone
+#[derive(Clone)]
+struct WorkingHours {
+    days: Vec<Weekday>,
+    start: NaiveTime,
+    end: NaiveTime,
+    tz: Tz,
+}
+
+impl WorkingHours {
+    /// Monday to Friday between `start` and `end`
+    fn weekdays(start: NaiveTime, end: NaiveTime, tz: Tz) -> Self {
+        Self {
+            days: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+            start,
+            end,
+            tz,
+        }
+    }
+
+    /// Parse `HH:MM-HH:MM`
+    fn parse(range: &str, tz: Tz) -> Result<Self> {
+        let (start, end) = range
+            .split_once('-')
+            .ok_or_else(|| anyhow::anyhow!("Working hours must look like 09:00-18:00"))?;
+        let start = NaiveTime::parse_from_str(start.trim(), "%H:%M")?;
+        let end = NaiveTime::parse_from_str(end.trim(), "%H:%M")?;
+        if end <= start {
+            anyhow::bail!("Working hours must end after they start");
+        }
+        Ok(Self::weekdays(start, end, tz))
+    }
+
+    /// Read `workingHours` from a getSchedule entry. Windows zone names (e.g.
+    /// "Romance Standard Time") are mapped to IANA; hours in a zone that can't be
+    /// resolved are ignored with a warning rather than read in the wrong zone.
+    fn from_schedule(value: &serde_json::Value, email: &str) -> Option<Self> {
+        let time = |key: &str| {
+            value
+                .get(key)
+                .and_then(|t| t.as_str())
+                .and_then(|t| NaiveTime::parse_from_str(t, "%H:%M:%S%.f").ok())
+        };
+        let days = value
+            .get("daysOfWeek")?
+            .as_array()?
+            .iter()
+            .filter_map(|d| d.as_str()?.parse::<Weekday>().ok())
+            .collect();
+        let zone = value
+            .get("timeZone")
+            .and_then(|t| t.get("name"))
+            .and_then(|n| n.as_str())
+            .unwrap_or_default();
+        let Some(tz) = parse_zone_name(zone) else {
+            print_warning(&format!(
+                "Ignoring the working hours of {}: unknown time zone '{}'",
+                email, zone
+            ));
+            return None;
+        };
+
+        Some(Self {
+            days,
+            start: time("startTime")?,
+            end: time("endTime")?,
+            tz,
+        })
+    }
+
+    fn contains(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+        let local_start = start.with_timezone(&self.tz);
+        let local_end = end.with_timezone(&self.tz);
+        self.days.contains(&local_start.weekday())
+            && local_start.date_naive() == local_end.date_naive()
+            && local_start.time() >= self.start
+            && local_end.time() <= self.end
+    }
+}
+
+/// Busy periods and working hours of one attendee
+struct AttendeeAvailability {
+    email: String,
+    busy: Vec<(DateTime<Utc>, DateTime<Utc>)>,
+    tentative: Vec<(DateTime<Utc>, DateTime<Utc>)>,
+    hours: Option<WorkingHours>,
+}
+
+impl AttendeeAvailability {
+    fn from_schedule(item: &serde_json::Value) -> Self {
+        let parse_time = |evt: &serde_json::Value, key: &str| {
+            evt.get(key)
+                .and_then(|t| t.get("dateTime"))
+                .and_then(|d| d.as_str())
+                .and_then(|d| NaiveDateTime::parse_from_str(d, "%Y-%m-%dT%H:%M:%S%.f").ok())
+                .map(|d| d.and_utc())
+        };
+
+        let mut busy = Vec::new();
+        let mut tentative = Vec::new();
+        for evt in item
+            .get("scheduleItems")
+            .and_then(|i| i.as_array())
+            .into_iter()
+            .flatten()
+        {
+            let (Some(start), Some(end)) = (parse_time(evt, "start"), parse_time(evt, "end"))
+            else {
+                continue;
+            };
+            match evt.get("status").and_then(|s| s.as_str()).unwrap_or("busy") {
+                "free" | "workingElsewhere" => {}
+                "tentative" => tentative.push((start, end)),
+                _ => busy.push((start, end)),
+            }
+        }
+
+        let email = item
+            .get("scheduleId")
+            .and_then(|id| id.as_str())
+            .unwrap_or("Unknown")
+            .to_string();
+        Self {
+            hours: item
+                .get("workingHours")
+                .and_then(|h| WorkingHours::from_schedule(h, &email)),
+            email,
+            busy,
+            tentative,
+        }
+    }
+}
+
+fn overlaps(
+    periods: &[(DateTime<Utc>, DateTime<Utc>)],
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> bool {
+    periods.iter().any(|(s, e)| *s < end && *e > start)
+}
+
+/// Resolve a search window such as `this week`, `next week`, `tomorrow` or `mon..fri`
+fn parse_window(within: &str, tz: Tz) -> Result<(DateTime<Tz>, DateTime<Tz>)> {
+    let now = now_in(tz);
+    let today = now.date_naive();
+    let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+
+    let (start, end) = match within.trim().to_lowercase().as_str() {
+        "this week" => (now, When::Day(monday + Duration::days(6)).end(tz)?),
+        "next week" => (
+            When::Day(monday + Duration::days(7)).start(tz)?,
+            When::Day(monday + Duration::days(13)).end(tz)?,
+        ),
+        range => match range.split_once("..") {
+            Some((from, to)) => (
+                parse_when(from, now)?.start(tz)?,
+                parse_when(to, now)?.end(tz)?,
+            ),
+            None => match parse_when(range, now)? {
+                When::At(dt) => (dt, When::Day(dt.date_naive()).end(tz)?),
+                day => (day.start(tz)?, day.end(tz)?),
+            },
+        },
+    };
+
+    // Never propose slots in the past
+    Ok((start.max(now), end))
+}
+
+async fn find_time(
+    config: &Config,
+    attendees: &str,
+    options: FindTimeOptions,
+    tz: Tz,
+    format: OutputFormat,
+) -> Result<()> {
+    let client = TeamsClient::new(config)?;
+    let (window_start, window_end) = parse_window(&options.within, tz)?;
+    if window_end <= window_start {
+        anyhow::bail!("The search window '{}' is already over", options.within);
+    }
+
+    let me = client.get_me().await?;
+    let my_email = me.mail.or(me.user_principal_name).unwrap_or_default();
+    let mut emails: Vec<&str> = attendees
+        .split(',')
+        .map(|a| a.trim())
+        .filter(|a| !a.is_empty())
+        .collect();
+    if !my_email.is_empty() && !emails.iter().any(|e| e.eq_ignore_ascii_case(&my_email)) {
+        emails.push(&my_email);
+    }
+
+    let schedule = client
+        .get_schedule(
+            emails.clone(),
+            &to_graph_utc(&window_start),
+            &to_graph_utc(&window_end),
+        )
+        .await?;
+    let availability: Vec<AttendeeAvailability> = schedule
+        .get("value")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .map(AttendeeAvailability::from_schedule)
+        .collect();
+
+    // Our own hours are a hard limit; other attendees' hours only lower a slot's rank
+    let my_hours = match &options.working_hours {
+        Some(range) => WorkingHours::parse(range, tz)?,
+        None => availability
+            .iter()
+            .find(|a| a.email.eq_ignore_ascii_case(&my_email))
+            .and_then(|a| a.hours.clone())
+            .unwrap_or_else(|| {
+                WorkingHours::weekdays(
+                    NaiveTime::from_hms_opt(9, 0, 0).unwrap_or_default(),
+                    NaiveTime::from_hms_opt(18, 0, 0).unwrap_or_default(),
+                    tz,
+                )
+            }),
+    };
+
+    // Candidates start on the hour or half hour
+    let step = Duration::minutes(30);
+    let mut cursor = window_start.with_timezone(&Utc);
+    let misalignment = cursor.timestamp().rem_euclid(step.num_seconds());
+    if misalignment != 0 {
+        cursor += Duration::seconds(step.num_seconds() - misalignment);
+    }
+    let window_end = window_end.with_timezone(&Utc);
+
+    let mut candidates = Vec::new();
+    while cursor + options.duration <= window_end {
+        let (start, end) = (cursor, cursor + options.duration);
+        cursor += step;
+        if !my_hours.contains(start, end) {
+            continue;
+        }
+
+        let mut conflicts = Vec::new();
+        let mut busy_count = 0;
+        let mut soft_count = 0;
+        for attendee in &availability {
+            if overlaps(&attendee.busy, start, end) {
+                busy_count += 1;
+                conflicts.push(format!("{} (busy)", attendee.email));
+            } else if overlaps(&attendee.tentative, start, end) {
+                soft_count += 1;
+                conflicts.push(format!("{} (tentative)", attendee.email));
+            } else if attendee
+                .hours
+                .as_ref()
+                .is_some_and(|h| !h.contains(start, end))
+            {
+                soft_count += 1;
+                conflicts.push(format!("{} (outside hours)", attendee.email));
+            }
+        }
+        candidates.push((busy_count, soft_count, start, end, conflicts));
+    }
+
+    if candidates.is_empty() {
+        print_error("No candidate slots in the search window");
+        return Ok(());
+    }
+
+    // Fewest hard conflicts first, then fewest soft conflicts, then earliest
+    candidates.sort_by_key(|(busy, soft, start, _, _)| (*busy, *soft, *start));
+
+    if options.book {
+        let (busy, _, start, end, _) = &candidates[0];
+        if *busy > 0 {
+            anyhow::bail!("No slot where every attendee is free; pick one manually");
+        }
+        return create(
+            config,
+            &options.title,
+            start.with_timezone(&tz),
+            end.with_timezone(&tz),
+            Some(attendees.to_string()),
+            None,
+            true,
+            None,
+            None,
+            tz,
+            format,
+        )
+        .await;
+    }
+
+    let total = availability.len();
+    let rows: Vec<SlotRow> = candidates
+        .into_iter()
+        .take(options.limit)
+        .enumerate()
+        .map(|(i, (busy, _, start, end, conflicts))| SlotRow {
+            rank: i + 1,
+            start: start
+                .with_timezone(&tz)
+                .format("%a %m-%d %H:%M")
+                .to_string(),
+            end: end.with_timezone(&tz).format("%H:%M").to_string(),
+            available: format!("{}/{}", total - busy, total),
+            conflicts: conflicts.join(", "),
+        })
+        .collect();
+
+    print_output(&rows, format);
+    Ok(())
+}
+
 async fn free_busy(
     config: &Config,
     users: &str,
     date: Option<String>,
+    tz: Tz,
     format: OutputFormat,
 ) -> Result<()> {
     let client = TeamsClient::new(config)?;
-    let target_date = date.unwrap_or_else(|| chrono::Utc::now().format("%Y-%m-%d").to_string());
-    let start = format!("{}T00:00:00Z", target_date);
-    let end = format!("{}T23:59:59Z", target_date);
+    let day = parse_when(date.as_deref().unwrap_or("today"), now_in(tz))?;
+    let start = to_graph_utc(&day.start(tz)?);
+    let end = to_graph_utc(&day.end(tz)?);
 
     let user_list: Vec<&str> = users.split(',').map(|u| u.trim()).collect();
     let schedule = client.get_schedule(user_list, &start, &end).await?;
@@ -613,8 +2318,8 @@ async fn free_busy(
                                     .and_then(|s| s.as_str())
                                     .unwrap_or("(No subject)");
 
-                                let st = format_local_time(start_time, "%H:%M");
-                                let et = format_local_time(end_time, "%H:%M");
+                                let st = format_in_tz(start_time, "%H:%M", tz);
+                                let et = format_in_tz(end_time, "%H:%M", tz);
 
                                 println!("  - {} - {}: {} [{}]", st, et, subject, status);
                             }
@@ -657,19 +2362,177 @@ async fn calendars(config: &Config, format: OutputFormat) -> Result<()> {
     Ok(())
 }
 
-/// Convert UTC ISO string from Graph API to local time format
-fn format_local_time(utc_str: &str, format_str: &str) -> String {
-    // Graph API sometimes returns YYYY-MM-DDTHH:MM:SS.NNNNNNN
-    // We append Z to treat it as UTC if not present
-    let iso_str = if utc_str.ends_with('Z') {
-        utc_str.to_string()
+async fn export(
+    config: &Config,
+    start: &str,
+    end: &str,
+    calendar_id: Option<String>,
+    expand: bool,
+    output: Option<String>,
+    tz: Tz,
+) -> Result<()> {
+    let client = TeamsClient::new(config)?;
+    let now = now_in(tz);
+    let start_dt = to_graph_utc(&parse_when(start, now)?.start(tz)?);
+    let end_dt = to_graph_utc(&parse_when(end, now)?.end(tz)?);
+
+    let view = if let Some(id) = calendar_id {
+        client
+            .get_calendar_events_for_id(&id, &start_dt, &end_dt)
+            .await?
     } else {
-        format!("{}Z", utc_str)
+        client.get_calendar_events(&start_dt, &end_dt).await?
     };
 
-    if let Ok(dt) = DateTime::parse_from_rfc3339(&iso_str) {
-        dt.with_timezone(&Local).format(format_str).to_string()
-    } else {
-        utc_str.to_string()
+    // Collapse plain occurrences into their series master, which carries the RRULE.
+    // Exceptions (moved or edited occurrences) are kept as standalone events.
+    let mut events = Vec::new();
+    let mut masters = std::collections::HashSet::new();
+    for event in view.value {
+        let master_id = match (&event.series_master_id, event.event_type.as_deref()) {
+            (Some(id), Some("occurrence")) if !expand => id.clone(),
+            _ => {
+                events.push(event);
+                continue;
+            }
+        };
+        if masters.insert(master_id.clone()) {
+            events.push(client.get_calendar_event(&master_id).await?);
+        }
+    }
+
+    let content = events_to_ics(&events);
+    match output {
+        Some(path) if path != "-" => {
+            std::fs::write(&path, &content)?;
+            print_success(&format!("Exported {} event(s) to {}", events.len(), path));
+        }
+        _ => print!("{}", content),
+    }
+    Ok(())
+}
+
+async fn import(
+    config: &Config,
+    file: Option<String>,
+    from_mail: Option<String>,
+    dry_run: bool,
+    invite_attendees: bool,
+    tz: Tz,
+    format: OutputFormat,
+) -> Result<()> {
+    let client = TeamsClient::new(config)?;
+
+    let mut documents = Vec::new();
+    if let Some(message_id) = from_mail {
+        let attachments = client.get_mail_attachments(&message_id).await?;
+        let invites = attachments
+            .value
+            .iter()
+            .filter(|a| is_ics_attachment(&a.name, a.content_type.as_deref()))
+            .filter_map(|a| a.id.as_deref());
+        for attachment_id in invites {
+            let (_, bytes) = client
+                .download_attachment(&message_id, attachment_id)
+                .await?;
+            documents.push(String::from_utf8_lossy(&bytes).into_owned());
+        }
+        if documents.is_empty() {
+            anyhow::bail!("Message {} has no .ics attachment", message_id);
+        }
+    } else if let Some(path) = file {
+        let text = if path == "-" {
+            let mut buffer = String::new();
+            std::io::Read::read_to_string(&mut std::io::stdin(), &mut buffer)?;
+            buffer
+        } else {
+            std::fs::read_to_string(&path)?
+        };
+        documents.push(text);
+    }
+
+    let mut rows = Vec::new();
+    for document in &documents {
+        for event in parse_ics(document)? {
+            let request = event.to_create_request(tz, invite_attendees)?;
+            let subject = request.subject.clone();
+            let start = format_in_tz(
+                &request.start.date_time,
+                "%Y-%m-%d %H:%M",
+                request.start.time_zone.parse().unwrap_or(tz),
+            );
+
+            let existing = match &event.uid {
+                Some(uid) => client.find_events_by_uid(uid).await?,
+                None => Vec::new(),
+            };
+            let result = if event.status.as_deref() == Some("CANCELLED") {
+                "skipped (cancelled)".to_string()
+            } else if !existing.is_empty() {
+                "skipped (already in calendar)".to_string()
+            } else if dry_run {
+                "would create".to_string()
+            } else {
+                let created = client.create_calendar_event(request).await?;
+                format!("created {}", created.id.unwrap_or_default())
+            };
+            rows.push(ImportRow {
+                subject,
+                start,
+                result,
+            });
+        }
+    }
+
+    if rows.is_empty() {
+        print_warning("No events found in the iCalendar data");
+        return Ok(());
+    }
+    print_output(&rows, format);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vtt_to_text() {
+        let vtt = "WEBVTT\n\n1\n00:00:01.000 --> 00:00:03.000\n<v Jane Doe>Hello everyone.</v>\n\n\
+                   2\n00:00:03.000 --> 00:00:05.000\n<v Jane Doe>Let's start.</v>\n\n\
+                   3\n00:00:05.000 --> 00:00:06.000\n<v John Smith>Sounds good.</v>\n";
+        assert_eq!(
+            vtt_to_text(vtt),
+            "Jane Doe: Hello everyone. Let's start.\nJohn Smith: Sounds good.\n"
+        );
+
+        // As Teams writes it: CRLF line endings and GUID-style cue IDs
+        let teams = "WEBVTT\r\n\r\n\
+            3f2c4b8e-8d1a-4c1e-9f5e-2a7b6c9d0e1f/14-0\r\n\
+            00:00:02.350 --> 00:00:04.120\r\n\
+            <v Jane Doe>Good morning, everyone.</v>\r\n\r\n\
+            3f2c4b8e-8d1a-4c1e-9f5e-2a7b6c9d0e1f/14-1\r\n\
+            00:00:04.120 --> 00:00:06.800\r\n\
+            <v Jane Doe>Let's go through the agenda.</v>\r\n\r\n\
+            a91e0d57-6b3c-4f2a-8e4d-1c5b7a9f3e2d/22-0\r\n\
+            00:00:07.010 --> 00:00:08.430\r\n\
+            <v John Smith>Sounds good.</v>\r\n";
+        assert_eq!(
+            vtt_to_text(teams),
+            "Jane Doe: Good morning, everyone. Let's go through the agenda.\nJohn Smith: Sounds good.\n"
+        );
+    }
+
+    #[test]
+    fn test_recording_extension() {
+        assert_eq!(recording_extension("video/webm; codecs=vp9", None), "webm");
+        assert_eq!(
+            recording_extension(
+                "application/octet-stream",
+                Some("https://contoso.sharepoint.com/Recordings/Standup.MOV?web=1")
+            ),
+            "mov"
+        );
+        assert_eq!(recording_extension("application/octet-stream", None), "mp4");
     }
 }
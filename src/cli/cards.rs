@@ -0,0 +1,831 @@
+use anyhow::{bail, Context, Result};
+use serde_json::{Map, Value};
+
+use crate::types::{Card, CardAction, CardContent, CardElement, CardItem, MessageProperties};
+
+use super::utils::strip_html;
+
+/// An action resolved from a card, ready to be executed
+#[derive(Debug, Clone)]
+pub struct ResolvedAction {
+    pub action_type: String,
+    pub title: String,
+    pub url: Option<String>,
+    pub verb: Option<String>,
+    pub data: Option<Value>,
+}
+
+/// Input field declared by an Adaptive Card
+#[derive(Debug, Clone)]
+pub struct CardInput {
+    pub id: String,
+    pub required: bool,
+    pub default: Option<String>,
+}
+
+/// Cards attached to a message, if any
+pub fn message_cards(props: &Option<MessageProperties>) -> &[Card] {
+    props
+        .as_ref()
+        .and_then(|p| p.cards.as_deref())
+        .unwrap_or(&[])
+}
+
+/// Render all cards of a message as structured text lines
+pub fn render_cards(props: &Option<MessageProperties>) -> Vec<String> {
+    message_cards(props).iter().flat_map(render_card).collect()
+}
+
+/// Render a card (Adaptive, hero or thumbnail) as structured text lines
+pub fn render_card(card: &Card) -> Vec<String> {
+    let kind = match card.content_type.as_str() {
+        "application/vnd.microsoft.card.adaptive" => "Adaptive Card",
+        "application/vnd.microsoft.card.hero" => "Hero Card",
+        "application/vnd.microsoft.card.thumbnail" => "Thumbnail Card",
+        _ => "Card",
+    };
+
+    let mut lines = vec![match &card.app_name {
+        Some(app) => format!("[{} from {}]", kind, app),
+        None => format!("[{}]", kind),
+    }];
+    render_content(&card.content, 1, &mut lines);
+    lines
+}
+
+/// Print rendered cards below a message table, one section per message
+pub fn print_card_sections(sections: &[(String, Vec<String>)]) {
+    for (message_id, lines) in sections {
+        println!();
+        println!("Cards in message {}:", message_id);
+        for line in lines {
+            println!("  {}", line);
+        }
+    }
+}
+
+fn render_content(content: &CardContent, depth: usize, out: &mut Vec<String>) {
+    let indent = "  ".repeat(depth);
+
+    if let Some(title) = &content.title {
+        out.push(format!("{}# {}", indent, title));
+    }
+    if let Some(subtitle) = &content.subtitle {
+        out.push(format!("{}{}", indent, subtitle));
+    }
+    if let Some(text) = &content.text {
+        out.push(format!("{}{}", indent, strip_html(text)));
+    }
+    if let Some(body) = &content.body {
+        render_elements(body, depth, out);
+    }
+    if let Some(actions) = &content.actions {
+        render_actions(actions, depth, out);
+    }
+    if let Some(buttons) = &content.buttons {
+        for button in buttons {
+            out.push(format!(
+                "{}[{}] ({})",
+                indent, button.title, button.button_type
+            ));
+        }
+    }
+}
+
+fn render_elements(elements: &[CardItem], depth: usize, out: &mut Vec<String>) {
+    let indent = "  ".repeat(depth);
+
+    for element in elements.iter().filter_map(CardItem::as_element) {
+        match element.element_type.as_str() {
+            "TextBlock" | "RichTextBlock" => {
+                if let Some(text) = &element.text {
+                    out.push(format!("{}{}", indent, text));
+                }
+            }
+            "FactSet" => {
+                for fact in element.facts.iter().flatten() {
+                    out.push(format!("{}{}: {}", indent, fact.title, fact.value));
+                }
+            }
+            "Container" | "Column" => {
+                if let Some(items) = &element.items {
+                    render_elements(items, depth, out);
+                }
+            }
+            "ColumnSet" => {
+                if let Some(columns) = &element.columns {
+                    render_elements(columns, depth, out);
+                }
+            }
+            "Image" => {
+                let label = element
+                    .alt_text
+                    .clone()
+                    .or_else(|| element.url.clone())
+                    .unwrap_or_default();
+                out.push(format!("{}[image: {}]", indent, label));
+            }
+            "ActionSet" => {
+                if let Some(actions) = &element.actions {
+                    render_actions(actions, depth, out);
+                }
+            }
+            t if t.starts_with("Input.") => render_input(element, depth, out),
+            _ => {
+                if let Some(text) = &element.text {
+                    out.push(format!("{}{}", indent, text));
+                }
+            }
+        }
+    }
+}
+
+fn render_input(element: &CardElement, depth: usize, out: &mut Vec<String>) {
+    let indent = "  ".repeat(depth);
+    let id = element.id.as_deref().unwrap_or("?");
+    let label = element
+        .label
+        .as_deref()
+        .or(element.title.as_deref())
+        .or(element.placeholder.as_deref())
+        .unwrap_or(id);
+
+    let mut line = format!("{}{} <{} {}>", indent, label, element.element_type, id);
+    if element.is_required == Some(true) {
+        line.push_str(" (required)");
+    }
+    if let Some(default) = element.value.as_ref().map(value_to_string) {
+        line.push_str(&format!(" = {}", default));
+    }
+    out.push(line);
+
+    for choice in element.choices.iter().flatten() {
+        out.push(format!("{}  - {} ({})", indent, choice.title, choice.value));
+    }
+}
+
+fn render_actions(actions: &[CardAction], depth: usize, out: &mut Vec<String>) {
+    let indent = "  ".repeat(depth);
+
+    for action in actions {
+        let title = action.title.as_deref().unwrap_or("(untitled)");
+        match &action.url {
+            Some(url) => out.push(format!(
+                "{}[{}] ({}) {}",
+                indent, title, action.action_type, url
+            )),
+            None => out.push(format!("{}[{}] ({})", indent, title, action.action_type)),
+        }
+        if let Some(card) = &action.card {
+            render_content(card, depth + 1, out);
+        }
+    }
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Titles of every action a card exposes (including nested ActionSets and ShowCards)
+pub fn action_titles(card: &Card) -> Vec<String> {
+    collect_actions(card).into_iter().map(|a| a.title).collect()
+}
+
+/// Find an action on a card by its title (case-insensitive)
+pub fn find_action(card: &Card, title: &str) -> Option<ResolvedAction> {
+    collect_actions(card)
+        .into_iter()
+        .find(|a| a.title.eq_ignore_ascii_case(title))
+}
+
+fn collect_actions(card: &Card) -> Vec<ResolvedAction> {
+    let mut found = Vec::new();
+    collect_content_actions(&card.content, &mut found);
+
+    for button in card.content.buttons.iter().flatten() {
+        let url = match (button.button_type.as_str(), &button.value) {
+            ("openUrl", Some(Value::String(url))) => Some(url.clone()),
+            _ => None,
+        };
+        found.push(ResolvedAction {
+            action_type: button.button_type.clone(),
+            title: button.title.clone(),
+            url,
+            verb: None,
+            data: button.value.clone(),
+        });
+    }
+
+    found
+}
+
+fn collect_content_actions(content: &CardContent, found: &mut Vec<ResolvedAction>) {
+    if let Some(body) = &content.body {
+        collect_element_actions(body, found);
+    }
+    for action in content.actions.iter().flatten() {
+        push_action(action, found);
+    }
+}
+
+fn collect_element_actions(elements: &[CardItem], found: &mut Vec<ResolvedAction>) {
+    for element in elements.iter().filter_map(CardItem::as_element) {
+        for action in element.actions.iter().flatten() {
+            push_action(action, found);
+        }
+        if let Some(items) = &element.items {
+            collect_element_actions(items, found);
+        }
+        if let Some(columns) = &element.columns {
+            collect_element_actions(columns, found);
+        }
+    }
+}
+
+fn push_action(action: &CardAction, found: &mut Vec<ResolvedAction>) {
+    found.push(ResolvedAction {
+        action_type: action.action_type.clone(),
+        title: action.title.clone().unwrap_or_default(),
+        url: action.url.clone(),
+        verb: action.verb.clone(),
+        data: action.data.clone(),
+    });
+    if let Some(card) = &action.card {
+        collect_content_actions(card, found);
+    }
+}
+
+/// Inputs declared anywhere in a card (including ShowCard sub-cards)
+pub fn collect_inputs(content: &CardContent) -> Vec<CardInput> {
+    let mut inputs = Vec::new();
+    if let Some(body) = &content.body {
+        collect_element_inputs(body, &mut inputs);
+    }
+    for action in content.actions.iter().flatten() {
+        if let Some(card) = &action.card {
+            inputs.extend(collect_inputs(card));
+        }
+    }
+    inputs
+}
+
+fn collect_element_inputs(elements: &[CardItem], inputs: &mut Vec<CardInput>) {
+    for element in elements.iter().filter_map(CardItem::as_element) {
+        if element.element_type.starts_with("Input.") {
+            if let Some(id) = &element.id {
+                inputs.push(CardInput {
+                    id: id.clone(),
+                    required: element.is_required == Some(true),
+                    default: element.value.as_ref().map(value_to_string),
+                });
+            }
+        }
+        if let Some(items) = &element.items {
+            collect_element_inputs(items, inputs);
+        }
+        if let Some(columns) = &element.columns {
+            collect_element_inputs(columns, inputs);
+        }
+    }
+}
+
+/// Build the value submitted with an action: the action's `data` merged with input values.
+/// Explicit `inputs` override the defaults declared on the card.
+pub fn build_submit_value(
+    card: &Card,
+    action: &ResolvedAction,
+    inputs: &[(String, String)],
+) -> Result<Value> {
+    let declared = collect_inputs(&card.content);
+
+    for (key, _) in inputs {
+        if !declared.iter().any(|i| &i.id == key) {
+            let known: Vec<&str> = declared.iter().map(|i| i.id.as_str()).collect();
+            bail!(
+                "Unknown input '{}'. Card inputs: {}",
+                key,
+                if known.is_empty() {
+                    "(none)".to_string()
+                } else {
+                    known.join(", ")
+                }
+            );
+        }
+    }
+
+    let mut value = match &action.data {
+        Some(Value::Object(map)) => map.clone(),
+        Some(other) if declared.is_empty() => return Ok(other.clone()),
+        Some(other) => {
+            let mut map = Map::new();
+            map.insert("data".to_string(), other.clone());
+            map
+        }
+        None => Map::new(),
+    };
+
+    for input in declared {
+        let provided = inputs
+            .iter()
+            .find(|(k, _)| k == &input.id)
+            .map(|(_, v)| v.clone());
+        match provided.or(input.default) {
+            Some(v) => {
+                value.insert(input.id, Value::String(v));
+            }
+            None if input.required => {
+                bail!(
+                    "Input '{}' is required. Pass --input {}=<value>",
+                    input.id,
+                    input.id
+                )
+            }
+            None => {}
+        }
+    }
+
+    Ok(Value::Object(value))
+}
+
+const ADAPTIVE_CARD_CONTENT_TYPE: &str = "application/vnd.microsoft.card.adaptive";
+
+/// Load an Adaptive Card from a JSON file, expanding `${...}` templates with `data_path` if given.
+/// Returns the validated card (the `content` of the attachment).
+pub fn load_card(card_path: &str, data_path: Option<&str>) -> Result<Value> {
+    let raw = std::fs::read_to_string(card_path)
+        .with_context(|| format!("Failed to read card file {}", card_path))?;
+    let mut card: Value = serde_json::from_str(&raw)
+        .with_context(|| format!("Failed to parse card file {}", card_path))?;
+
+    // Accept a full attachment ({"contentType": ..., "content": {...}}) as well as a bare card
+    if card.get("contentType").is_some() {
+        let content_type = card["contentType"].as_str().unwrap_or_default();
+        if content_type != ADAPTIVE_CARD_CONTENT_TYPE {
+            bail!("Unsupported card content type '{}'", content_type);
+        }
+        card = card["content"].take();
+    }
+
+    if let Some(path) = data_path {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read data file {}", path))?;
+        let data: Value = serde_json::from_str(&raw)
+            .with_context(|| format!("Failed to parse data file {}", path))?;
+        card = expand_template(&card, &data);
+    }
+
+    validate_card(&card)?;
+    Ok(card)
+}
+
+/// Wrap a card into the attachment shape Teams expects in `properties.cards`
+pub fn card_attachment(card: &Value) -> Value {
+    serde_json::json!({
+        "contentType": ADAPTIVE_CARD_CONTENT_TYPE,
+        "content": card,
+    })
+}
+
+/// Plain-text summary of a card, used as the message body shown in notifications
+pub fn card_summary(card: &Value) -> String {
+    if let Some(text) = card.get("fallbackText").and_then(|v| v.as_str()) {
+        return text.to_string();
+    }
+    card.get("body")
+        .and_then(|b| b.as_array())
+        .and_then(|body| {
+            body.iter()
+                .find_map(|e| e.get("text").and_then(|t| t.as_str()))
+        })
+        .unwrap_or("Adaptive Card")
+        .to_string()
+}
+
+/// Check that a payload is a well-formed Adaptive Card
+pub fn validate_card(card: &Value) -> Result<()> {
+    let Some(obj) = card.as_object() else {
+        bail!("Card must be a JSON object");
+    };
+    match obj.get("type").and_then(|t| t.as_str()) {
+        Some("AdaptiveCard") => {}
+        Some(other) => bail!("Card type must be 'AdaptiveCard', got '{}'", other),
+        None => bail!("Card is missing \"type\": \"AdaptiveCard\""),
+    }
+    if !obj.get("version").is_some_and(|v| v.is_string()) {
+        bail!("Card is missing a \"version\" string (e.g. \"1.4\")");
+    }
+
+    // Reuse the typed model so structural errors point at the offending field
+    serde_json::from_value::<CardContent>(card.clone()).context("Invalid Adaptive Card")?;
+
+    if let Some(body) = obj.get("body") {
+        let Some(elements) = body.as_array() else {
+            bail!("Card \"body\" must be an array");
+        };
+        validate_elements(elements, "body")?;
+    }
+    if let Some(actions) = obj.get("actions") {
+        let Some(actions) = actions.as_array() else {
+            bail!("Card \"actions\" must be an array");
+        };
+        for (i, action) in actions.iter().enumerate() {
+            if !action.get("type").is_some_and(|t| t.is_string()) {
+                bail!("actions[{}] is missing a \"type\"", i);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn validate_elements(elements: &[Value], path: &str) -> Result<()> {
+    for (i, element) in elements.iter().enumerate() {
+        let here = format!("{}[{}]", path, i);
+        if !element.get("type").is_some_and(|t| t.is_string()) {
+            bail!("{} is missing a \"type\"", here);
+        }
+        // Table columns are width definitions, not elements
+        let is_table = element["type"] == "Table";
+        for key in ["items", "columns"] {
+            if key == "columns" && is_table {
+                continue;
+            }
+            if let Some(children) = element.get(key).and_then(|c| c.as_array()) {
+                validate_elements(children, &format!("{}.{}", here, key))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Expand Adaptive Card Templating expressions against `data`.
+///
+/// Supports `${path}` bindings (with `$root`, `$data` and `$index`), `$data` to rescope
+/// or repeat an element over an array, and `$when` to drop elements conditionally.
+pub fn expand_template(template: &Value, data: &Value) -> Value {
+    let scope = Scope {
+        root: data,
+        data,
+        index: None,
+    };
+    match expand_value(template, &scope) {
+        Expanded::One(v) => v,
+        Expanded::Many(mut v) => v.pop().unwrap_or(Value::Null),
+        Expanded::Dropped => Value::Null,
+    }
+}
+
+struct Scope<'a> {
+    root: &'a Value,
+    data: &'a Value,
+    index: Option<usize>,
+}
+
+enum Expanded {
+    One(Value),
+    Many(Vec<Value>),
+    Dropped,
+}
+
+fn expand_value(value: &Value, scope: &Scope) -> Expanded {
+    match value {
+        Value::String(s) => Expanded::One(expand_string(s, scope)),
+        Value::Array(items) => {
+            let mut out = Vec::new();
+            for item in items {
+                match expand_value(item, scope) {
+                    Expanded::One(v) => out.push(v),
+                    Expanded::Many(vs) => out.extend(vs),
+                    Expanded::Dropped => {}
+                }
+            }
+            Expanded::One(Value::Array(out))
+        }
+        Value::Object(map) => expand_object(map, scope),
+        other => Expanded::One(other.clone()),
+    }
+}
+
+fn expand_object(map: &Map<String, Value>, scope: &Scope) -> Expanded {
+    if let Some(binding) = map.get("$data") {
+        let bound = match binding {
+            Value::String(s) => expand_string(s, scope),
+            other => other.clone(),
+        };
+        let mut rest = map.clone();
+        rest.remove("$data");
+
+        return match &bound {
+            Value::Array(items) => Expanded::Many(
+                items
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, item)| {
+                        let inner = Scope {
+                            root: scope.root,
+                            data: item,
+                            index: Some(i),
+                        };
+                        match expand_object(&rest, &inner) {
+                            Expanded::One(v) => Some(v),
+                            _ => None,
+                        }
+                    })
+                    .collect(),
+            ),
+            item => {
+                let inner = Scope {
+                    root: scope.root,
+                    data: item,
+                    index: scope.index,
+                };
+                expand_object(&rest, &inner)
+            }
+        };
+    }
+
+    if let Some(condition) = map.get("$when") {
+        let keep = match condition {
+            Value::String(s) => is_truthy(&expand_string(s, scope)),
+            other => is_truthy(other),
+        };
+        if !keep {
+            return Expanded::Dropped;
+        }
+    }
+
+    let mut out = Map::new();
+    for (key, value) in map {
+        if key == "$when" {
+            continue;
+        }
+        match expand_value(value, scope) {
+            // A lone binding keeps its JSON type, but the card model reads these as text
+            Expanded::One(v)
+                if is_text_field(map, key) && !v.is_string() && !v.is_array() && !v.is_object() =>
+            {
+                let text = match v {
+                    Value::Null => String::new(),
+                    other => value_to_string(&other),
+                };
+                out.insert(key.clone(), Value::String(text));
+            }
+            Expanded::One(v) => {
+                out.insert(key.clone(), v);
+            }
+            Expanded::Many(vs) => {
+                out.insert(key.clone(), Value::Array(vs));
+            }
+            Expanded::Dropped => {}
+        }
+    }
+    Expanded::One(Value::Object(out))
+}
+
+/// Fields the card model reads as strings
+fn is_text_field(map: &Map<String, Value>, key: &str) -> bool {
+    match key {
+        "text" | "title" | "subtitle" | "altText" => true,
+        // Facts and choices have no "type" and carry their value as text; inputs keep theirs
+        "value" => !map.contains_key("type"),
+        _ => false,
+    }
+}
+
+fn expand_string(s: &str, scope: &Scope) -> Value {
+    // A string that is a single binding keeps the bound value's JSON type
+    if let Some(expr) = s.strip_prefix("${").and_then(|r| r.strip_suffix('}')) {
+        if !expr.contains("${") {
+            return evaluate(expr, scope);
+        }
+    }
+
+    let mut result = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        match evaluate(&rest[start + 2..start + len], scope) {
+            Value::Null => {}
+            value => result.push_str(&value_to_string(&value)),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    result.push_str(rest);
+    Value::String(result)
+}
+
+fn evaluate(expr: &str, scope: &Scope) -> Value {
+    let expr = expr.trim();
+    if let Some(negated) = expr.strip_prefix('!') {
+        return Value::Bool(!is_truthy(&evaluate(negated, scope)));
+    }
+    if expr == "$index" {
+        return scope.index.map(Value::from).unwrap_or(Value::Null);
+    }
+
+    let (mut current, path) = if let Some(p) = expr.strip_prefix("$root") {
+        (scope.root, p)
+    } else if let Some(p) = expr.strip_prefix("$data") {
+        (scope.data, p)
+    } else {
+        (scope.data, expr)
+    };
+
+    for segment in path.split('.').filter(|s| !s.is_empty()) {
+        let (name, indexes) = match segment.find('[') {
+            Some(i) => (&segment[..i], &segment[i..]),
+            None => (segment, ""),
+        };
+        if !name.is_empty() {
+            current = match current.get(name) {
+                Some(v) => v,
+                None => return Value::Null,
+            };
+        }
+        for index in indexes
+            .split(['[', ']'])
+            .filter(|s| !s.is_empty())
+            .filter_map(|s| s.parse::<usize>().ok())
+        {
+            current = match current.get(index) {
+                Some(v) => v,
+                None => return Value::Null,
+            };
+        }
+    }
+    current.clone()
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64() != Some(0.0),
+        Value::String(s) => !s.is_empty() && s != "false",
+        Value::Array(a) => !a.is_empty(),
+        Value::Object(_) => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approval_card() -> Card {
+        serde_json::from_value(serde_json::json!({
+            "appName": "Approvals",
+            "contentType": "application/vnd.microsoft.card.adaptive",
+            "content": {
+                "type": "AdaptiveCard",
+                "version": "1.4",
+                "body": [
+                    {"type": "TextBlock", "text": "Expense report"},
+                    {"type": "FactSet", "facts": [{"title": "Amount", "value": "42 EUR"}]},
+                    {"type": "Input.Text", "id": "comment", "label": "Comment"},
+                    {"type": "Input.ChoiceSet", "id": "reason", "isRequired": true,
+                     "choices": [{"title": "Travel", "value": "travel"}]}
+                ],
+                "actions": [
+                    {"type": "Action.Submit", "title": "Approve", "data": {"decision": "approve"}},
+                    {"type": "Action.OpenUrl", "title": "Details", "url": "https://example.com"}
+                ]
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_render_adaptive_card() {
+        let lines = render_card(&approval_card());
+        assert_eq!(lines[0], "[Adaptive Card from Approvals]");
+        assert!(lines.contains(&"  Expense report".to_string()));
+        assert!(lines.contains(&"  Amount: 42 EUR".to_string()));
+        assert!(lines.contains(&"  Comment <Input.Text comment>".to_string()));
+        assert!(lines.contains(&"  [Approve] (Action.Submit)".to_string()));
+        assert!(lines.contains(&"  [Details] (Action.OpenUrl) https://example.com".to_string()));
+    }
+
+    #[test]
+    fn test_build_submit_value() {
+        let card = approval_card();
+        let action = find_action(&card, "approve").unwrap();
+
+        let err = build_submit_value(&card, &action, &[]).unwrap_err();
+        assert!(err.to_string().contains("'reason' is required"));
+
+        let value = build_submit_value(
+            &card,
+            &action,
+            &[
+                ("reason".to_string(), "travel".to_string()),
+                ("comment".to_string(), "ok".to_string()),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({"decision": "approve", "reason": "travel", "comment": "ok"})
+        );
+
+        assert!(build_submit_value(&card, &action, &[("nope".into(), "x".into())]).is_err());
+    }
+
+    #[test]
+    fn test_expand_template() {
+        let template = serde_json::json!({
+            "type": "AdaptiveCard",
+            "version": "1.4",
+            "body": [
+                {"type": "TextBlock", "text": "Build ${build.number} ${status}"},
+                {"type": "TextBlock", "text": "Failed!", "$when": "${failed}"},
+                {"type": "FactSet", "facts": [
+                    {"$data": "${stages}", "title": "${name}", "value": "${$root.status}"}
+                ]}
+            ],
+            "actions": [{"type": "Action.OpenUrl", "title": "Open", "url": "${build.url}"}]
+        });
+        let data = serde_json::json!({
+            "status": "passed",
+            "failed": false,
+            "build": {"number": 42, "url": "https://ci.example.com/42"},
+            "stages": [{"name": "test"}, {"name": "deploy"}]
+        });
+
+        let card = expand_template(&template, &data);
+        assert_eq!(card["body"][0]["text"], "Build 42 passed");
+        assert_eq!(card["body"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            card["body"][1]["facts"],
+            serde_json::json!([
+                {"title": "test", "value": "passed"},
+                {"title": "deploy", "value": "passed"}
+            ])
+        );
+        assert_eq!(card["actions"][0]["url"], "https://ci.example.com/42");
+        assert!(validate_card(&card).is_ok());
+        assert!(validate_card(&serde_json::json!({"type": "AdaptiveCard"})).is_err());
+    }
+
+    #[test]
+    fn test_expand_numeric_fact() {
+        let template = serde_json::json!({
+            "type": "AdaptiveCard",
+            "version": "1.4",
+            "body": [
+                {"type": "FactSet", "facts": [{"title": "Build", "value": "${n}"}]},
+                {"type": "TextBlock", "text": "${n}"},
+                {"type": "Input.Number", "id": "count", "value": "${n}"}
+            ]
+        });
+        let card = expand_template(&template, &serde_json::json!({"n": 42}));
+        assert_eq!(card["body"][0]["facts"][0]["value"], "42");
+        assert_eq!(card["body"][1]["text"], "42");
+        assert_eq!(card["body"][2]["value"], 42);
+        assert!(validate_card(&card).is_ok());
+    }
+
+    #[test]
+    fn test_lenient_card_model() {
+        let cards = serde_json::json!([{
+            "contentType": "application/vnd.microsoft.card.adaptive",
+            "content": {
+                "type": "AdaptiveCard",
+                "version": "1.5",
+                "body": [
+                    {"type": "Table", "columns": [{"width": 1}, {"width": 2}]},
+                    {"type": "FactSet", "facts": [{"title": "Count", "value": 3}]},
+                    "stray",
+                    {"type": "TextBlock", "text": "Still rendered"}
+                ],
+                "actions": [{"type": "Action.Submit", "title": "Ok"}]
+            }
+        }]);
+        let message: crate::types::Message = serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "from": "8:orgid:bot",
+            "properties": {"cards": cards.to_string()}
+        }))
+        .unwrap();
+        let card = &message.properties.unwrap().cards.unwrap()[0];
+        let body = card.content.body.as_ref().unwrap();
+        assert!(matches!(body[1], CardItem::Element(_)));
+        assert!(matches!(body[2], CardItem::Unknown(_)));
+        let lines = render_card(card);
+        assert!(lines.contains(&"  Count: 3".to_string()));
+        assert!(lines.contains(&"  Still rendered".to_string()));
+        assert_eq!(action_titles(card), vec!["Ok"]);
+
+        let broken: crate::types::Message = serde_json::from_value(serde_json::json!({
+            "id": "2",
+            "from": "8:orgid:bot",
+            "properties": {"cards": "[{\"content\": 1}]"}
+        }))
+        .unwrap();
+        assert!(broken.properties.unwrap().cards.is_none());
+    }
+}
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::io::{self, Read};
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::{Args, Subcommand};
 use serde::Serialize;
 use tabled::Tabled;
@@ -10,8 +10,14 @@ use crate::api::TeamsClient;
 use crate::config::Config;
 use crate::types::Chat;
 
-use super::output::{print_error, print_output, print_single, print_success};
-use super::utils::{html_escape, markdown_to_html, strip_html, truncate};
+use super::cards::{
+    action_titles, build_submit_value, card_attachment, card_summary, find_action, load_card,
+    message_cards, print_card_sections, render_cards,
+};
+use super::output::{print_error, print_info, print_output, print_single, print_success};
+use super::people::resolve_address;
+use super::templates::{Template, TemplateArgs};
+use super::utils::{html_escape, markdown_to_html, parse_key_value, strip_html, truncate};
 use super::OutputFormat;
 
 #[derive(Args, Debug)]
@@ -55,6 +61,7 @@ pub enum ChatsSubcommand {
         chat_id_or_message: Option<String>,
 
         /// Message content (when chat_id is provided)
+        #[arg(conflicts_with = "template")]
         message: Option<String>,
 
         /// Send to a user by name or email (finds or creates 1:1 chat)
@@ -62,20 +69,31 @@ pub enum ChatsSubcommand {
         to: Option<String>,
 
         /// Read message from stdin
-        #[arg(short, long)]
+        #[arg(short, long, conflicts_with = "template")]
         stdin: bool,
 
         /// Read message from file
-        #[arg(long)]
+        #[arg(long, conflicts_with = "template")]
         file: Option<String>,
 
         /// Treat message as Markdown and convert to HTML
-        #[arg(short, long)]
+        #[arg(short, long, conflicts_with = "template")]
         markdown: bool,
 
         /// Send raw HTML without escaping
-        #[arg(long)]
+        #[arg(long, conflicts_with = "template")]
         html: bool,
+
+        /// Send an Adaptive Card from a JSON file (message text becomes optional)
+        #[arg(long, conflicts_with = "template")]
+        card: Option<String>,
+
+        /// JSON data file used to expand ${...} templates in the card
+        #[arg(long, requires = "card")]
+        data: Option<String>,
+
+        #[command(flatten)]
+        template: TemplateArgs,
     },
 
     /// Create a new chat
@@ -198,6 +216,24 @@ pub enum ChatsSubcommand {
         #[arg(short, long)]
         message_id: String,
     },
+
+    /// Invoke an action (button) on an Adaptive Card or hero card
+    CardAction {
+        /// Chat ID
+        chat_id: String,
+
+        /// Message ID containing the card
+        #[arg(short, long)]
+        message_id: String,
+
+        /// Action title (case-insensitive, e.g. "Approve")
+        #[arg(short, long)]
+        action: String,
+
+        /// Input value for the card, as key=value (can be repeated)
+        #[arg(short, long = "input", value_name = "KEY=VALUE")]
+        inputs: Vec<String>,
+    },
 }
 
 #[derive(Debug, Serialize, Tabled)]
@@ -330,6 +366,9 @@ pub async fn execute(cmd: ChatsCommand, config: &Config, format: OutputFormat) -
             file,
             markdown,
             html,
+            card,
+            data,
+            template,
         } => {
             send(
                 config,
@@ -340,6 +379,9 @@ pub async fn execute(cmd: ChatsCommand, config: &Config, format: OutputFormat) -
                 file,
                 markdown,
                 html,
+                card,
+                data,
+                &template,
             )
             .await
         }
@@ -380,6 +422,12 @@ pub async fn execute(cmd: ChatsCommand, config: &Config, format: OutputFormat) -
             chat_id,
             message_id,
         } => reactions(config, &chat_id, &message_id, format).await,
+        ChatsSubcommand::CardAction {
+            chat_id,
+            message_id,
+            action,
+            inputs,
+        } => card_action(config, &chat_id, &message_id, &action, &inputs).await,
     }
 }
 
@@ -536,7 +584,7 @@ async fn show(config: &Config, chat_id: &str, format: OutputFormat) -> Result<()
     Ok(())
 }
 
-async fn messages(
+pub(super) async fn messages(
     config: &Config,
     chat_id: &str,
     limit: usize,
@@ -551,6 +599,7 @@ async fn messages(
         .filter(|m| {
             m.message_type.as_deref() == Some("RichText/Html")
                 || m.message_type.as_deref() == Some("Text")
+                || !message_cards(&m.properties).is_empty()
         })
         .take(limit)
         .collect();
@@ -561,10 +610,27 @@ async fn messages(
             print_single(&filtered_messages, format);
         }
         _ => {
+            let card_sections: Vec<(String, Vec<String>)> = filtered_messages
+                .iter()
+                .map(|m| {
+                    (
+                        m.id.clone().unwrap_or_default(),
+                        render_cards(&m.properties),
+                    )
+                })
+                .filter(|(_, lines)| !lines.is_empty())
+                .collect();
+
             let rows: Vec<MessageRow> = filtered_messages
                 .into_iter()
                 .map(|msg| {
-                    let content = msg.content.map(|c| strip_html(&c)).unwrap_or_default();
+                    let mut content = msg.content.map(|c| strip_html(&c)).unwrap_or_default();
+                    if content.is_empty() {
+                        // Card-only messages (bots, workflows): show the card header instead
+                        if let Some(header) = render_cards(&msg.properties).into_iter().next() {
+                            content = header;
+                        }
+                    }
                     let reactions = crate::api::emoji::format_reactions_summary(&msg.properties);
 
                     let mut status = Vec::new();
@@ -596,11 +662,13 @@ async fn messages(
                 .collect();
 
             print_output(&rows, format);
+            print_card_sections(&card_sections);
         }
     }
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn send(
     config: &Config,
     chat_id_or_message: Option<String>,
@@ -610,6 +678,9 @@ async fn send(
     file: Option<String>,
     markdown: bool,
     html: bool,
+    card: Option<String>,
+    data: Option<String>,
+    template: &TemplateArgs,
 ) -> Result<()> {
     // When --to is used, the first positional arg is the message, not chat_id
     let (chat_id, actual_message) = if to.is_some() {
@@ -618,7 +689,22 @@ async fn send(
         (chat_id_or_message, message)
     };
 
-    let content = if let Some(msg) = actual_message {
+    // Validate the card before doing any network work
+    let card = card
+        .map(|path| load_card(&path, data.as_deref()))
+        .transpose()?;
+    // With --to, clap sees the message as the chat ID and can't flag the conflict
+    if template.template.is_some() && actual_message.is_some() {
+        bail!("--template cannot be combined with a message argument");
+    }
+    let template = template.load()?;
+    let (markdown, html) = template
+        .as_ref()
+        .map_or((markdown, html), Template::format_flags);
+
+    let content = if let Some(t) = template {
+        t.body
+    } else if let Some(msg) = actual_message {
         msg
     } else if stdin {
         let mut buffer = String::new();
@@ -626,6 +712,8 @@ async fn send(
         buffer.trim().to_string()
     } else if let Some(path) = file {
         std::fs::read_to_string(&path)?
+    } else if let Some(card) = &card {
+        card_summary(card)
     } else {
         print_error("No message provided. Use --stdin or --file, or provide message as argument.");
         return Ok(());
@@ -640,8 +728,9 @@ async fn send(
 
     // Resolve the chat ID
     let resolved_chat_id = if let Some(to_query) = to {
-        // Search for user by name or email
-        resolve_user_to_chat(&client, &to_query).await?
+        // Names resolve through people ranking first, then the directory
+        let email = resolve_address(&client, &to_query).await?;
+        resolve_user_to_chat(&client, &email).await?
     } else if let Some(id) = chat_id {
         id
     } else {
@@ -657,6 +746,19 @@ async fn send(
         format!("<p>{}</p>", html_escape(&content))
     };
 
+    if let Some(card) = card {
+        client
+            .send_card_message(
+                &resolved_chat_id,
+                &html_body,
+                &[card_attachment(&card)],
+                None,
+            )
+            .await?;
+        print_success("Card sent successfully");
+        return Ok(());
+    }
+
     client
         .send_message(&resolved_chat_id, &html_body, None)
         .await?;
@@ -1240,3 +1342,84 @@ async fn reactions(
 
     Ok(())
 }
+
+async fn card_action(
+    config: &Config,
+    chat_id: &str,
+    message_id: &str,
+    action_title: &str,
+    inputs: &[String],
+) -> Result<()> {
+    let inputs: Vec<(String, String)> = inputs
+        .iter()
+        .map(|i| parse_key_value(i))
+        .collect::<Result<_>>()?;
+
+    let client = TeamsClient::new(config)?;
+    // Fetched by ID, so cards on messages older than the latest page are reachable too
+    let Some(msg) = client.get_conversation_message(chat_id, message_id).await? else {
+        print_error(&format!("Message not found: {}", message_id));
+        return Ok(());
+    };
+
+    let cards = message_cards(&msg.properties);
+    if cards.is_empty() {
+        print_error("This message has no cards");
+        return Ok(());
+    }
+
+    let Some((card, action)) = cards
+        .iter()
+        .find_map(|c| find_action(c, action_title).map(|a| (c, a)))
+    else {
+        let titles: Vec<String> = cards.iter().flat_map(action_titles).collect();
+        print_error(&format!(
+            "Action not found: {}. Available actions: {}",
+            action_title,
+            titles.join(", ")
+        ));
+        return Ok(());
+    };
+
+    match action.action_type.as_str() {
+        "Action.OpenUrl" | "openUrl" => {
+            let url = action.url.clone().unwrap_or_default();
+            print_info(&format!("Open this URL to continue: {}", url));
+        }
+        "Action.ShowCard" => {
+            print_error("This action only expands a sub-card; invoke one of its actions instead");
+        }
+        "imBack" => {
+            // imBack buttons simply post their value as a chat message
+            let text = match &action.data {
+                Some(serde_json::Value::String(s)) => s.clone(),
+                Some(other) => other.to_string(),
+                None => action.title.clone(),
+            };
+            client
+                .send_message(chat_id, &format!("<p>{}</p>", html_escape(&text)), None)
+                .await?;
+            print_success(&format!("Sent \"{}\"", text));
+        }
+        _ => {
+            let value = build_submit_value(card, &action, &inputs)?;
+            let bot_id = msg
+                .from
+                .clone()
+                .or_else(|| card.app_id.as_ref().map(|id| format!("28:{}", id)))
+                .unwrap_or_default();
+            let verb = if action.action_type == "Action.Execute" {
+                action.verb.as_deref().or(Some(""))
+            } else {
+                None
+            };
+
+            client
+                .submit_card_action(chat_id, message_id, &bot_id, verb, value)
+                .await?;
+            print_success(&format!("Action \"{}\" submitted", action.title));
+        }
+    }
+
+    Ok(())
+}
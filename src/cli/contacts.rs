@@ -0,0 +1,313 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use serde::Serialize;
+use tabled::Tabled;
+
+use crate::api::TeamsClient;
+use crate::config::Config;
+use crate::types::{Contact, EmailAddress};
+
+use super::output::{print_error, print_output, print_single, print_success};
+use super::utils::truncate;
+use super::OutputFormat;
+
+/// Contacts scanned by `contacts search`
+const SEARCH_LIMIT: usize = 1000;
+
+#[derive(Args, Debug)]
+pub struct ContactsCommand {
+    #[command(subcommand)]
+    pub command: ContactsSubcommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ContactsSubcommand {
+    /// List your Outlook contacts
+    List {
+        /// Maximum number of contacts
+        #[arg(short, long, default_value = "50")]
+        limit: usize,
+    },
+
+    /// Show a contact
+    Show {
+        /// Contact ID
+        contact_id: String,
+    },
+
+    /// Find contacts by name, email or company
+    Search {
+        /// Text to look for
+        query: String,
+    },
+
+    /// Create a contact
+    Create {
+        /// Display name
+        name: String,
+
+        #[command(flatten)]
+        fields: ContactArgs,
+    },
+
+    /// Update a contact
+    Update {
+        /// Contact ID
+        contact_id: String,
+
+        /// New display name
+        #[arg(long)]
+        name: Option<String>,
+
+        #[command(flatten)]
+        fields: ContactArgs,
+    },
+
+    /// Delete a contact
+    Delete {
+        /// Contact ID
+        contact_id: String,
+    },
+}
+
+#[derive(Args, Debug)]
+pub struct ContactArgs {
+    /// Email address (repeatable; replaces existing addresses on update)
+    #[arg(short, long)]
+    email: Vec<String>,
+
+    /// First name
+    #[arg(long)]
+    given_name: Option<String>,
+
+    /// Last name
+    #[arg(long)]
+    surname: Option<String>,
+
+    /// Business phone
+    #[arg(long)]
+    phone: Option<String>,
+
+    /// Mobile phone
+    #[arg(long)]
+    mobile: Option<String>,
+
+    /// Company
+    #[arg(long)]
+    company: Option<String>,
+
+    /// Job title
+    #[arg(long)]
+    title: Option<String>,
+
+    /// Notes
+    #[arg(long)]
+    notes: Option<String>,
+}
+
+impl ContactArgs {
+    /// A contact holding only the given fields
+    fn to_contact(&self, name: Option<String>) -> Contact {
+        Contact {
+            display_name: name,
+            given_name: self.given_name.clone(),
+            surname: self.surname.clone(),
+            email_addresses: self
+                .email
+                .iter()
+                .map(|address| EmailAddress {
+                    address: address.clone(),
+                    name: None,
+                })
+                .collect(),
+            business_phones: self.phone.iter().cloned().collect(),
+            mobile_phone: self.mobile.clone(),
+            company_name: self.company.clone(),
+            job_title: self.title.clone(),
+            personal_notes: self.notes.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Tabled)]
+struct ContactRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Email")]
+    email: String,
+    #[tabled(rename = "Phone")]
+    phone: String,
+    #[tabled(rename = "Company")]
+    company: String,
+}
+
+pub async fn execute(cmd: ContactsCommand, config: &Config, format: OutputFormat) -> Result<()> {
+    match cmd.command {
+        ContactsSubcommand::List { limit } => list(config, limit, format).await,
+        ContactsSubcommand::Show { contact_id } => show(config, &contact_id, format).await,
+        ContactsSubcommand::Search { query } => search(config, &query, format).await,
+        ContactsSubcommand::Create { name, fields } => {
+            create(config, fields.to_contact(Some(name)), format).await
+        }
+        ContactsSubcommand::Update {
+            contact_id,
+            name,
+            fields,
+        } => update(config, &contact_id, fields.to_contact(name), format).await,
+        ContactsSubcommand::Delete { contact_id } => delete(config, &contact_id).await,
+    }
+}
+
+fn contact_row(c: &Contact) -> ContactRow {
+    ContactRow {
+        id: truncate(c.id.as_deref().unwrap_or_default(), 12),
+        name: truncate(c.display_name.as_deref().unwrap_or_default(), 30),
+        email: c
+            .email_addresses
+            .first()
+            .map(|e| e.address.clone())
+            .unwrap_or_default(),
+        phone: c
+            .mobile_phone
+            .clone()
+            .or_else(|| c.business_phones.first().cloned())
+            .unwrap_or_default(),
+        company: truncate(c.company_name.as_deref().unwrap_or_default(), 25),
+    }
+}
+
+fn print_contacts(contacts: &[Contact], format: OutputFormat) {
+    if matches!(format, OutputFormat::Json) {
+        print_single(&contacts, format);
+    } else {
+        let rows: Vec<ContactRow> = contacts.iter().map(contact_row).collect();
+        print_output(&rows, format);
+    }
+}
+
+async fn list(config: &Config, limit: usize, format: OutputFormat) -> Result<()> {
+    let client = TeamsClient::new(config)?;
+    let contacts = client.get_contacts(limit).await?;
+    print_contacts(&contacts.value, format);
+    Ok(())
+}
+
+async fn show(config: &Config, contact_id: &str, format: OutputFormat) -> Result<()> {
+    let client = TeamsClient::new(config)?;
+    let contact = client.get_contact(contact_id).await?;
+    print_single(&contact, format);
+    Ok(())
+}
+
+async fn search(config: &Config, query: &str, format: OutputFormat) -> Result<()> {
+    let client = TeamsClient::new(config)?;
+    // Graph can't filter contacts by substring, so they are matched locally
+    let contacts = client.get_contacts(SEARCH_LIMIT).await?;
+    let matches: Vec<Contact> = contacts
+        .value
+        .into_iter()
+        .filter(|c| matches_query(c, query))
+        .collect();
+
+    if matches.is_empty() {
+        print_error(&format!("No contacts found matching '{}'", query));
+    } else {
+        print_contacts(&matches, format);
+    }
+    Ok(())
+}
+
+/// Whether every word of `query` appears in the contact's names, addresses or company
+fn matches_query(contact: &Contact, query: &str) -> bool {
+    let mut haystack = [
+        &contact.display_name,
+        &contact.given_name,
+        &contact.surname,
+        &contact.company_name,
+    ]
+    .iter()
+    .filter_map(|f| f.as_deref())
+    .collect::<Vec<_>>()
+    .join(" ");
+    for email in &contact.email_addresses {
+        haystack.push(' ');
+        haystack.push_str(&email.address);
+    }
+    let haystack = haystack.to_lowercase();
+    query
+        .split_whitespace()
+        .all(|word| haystack.contains(&word.to_lowercase()))
+}
+
+async fn create(config: &Config, contact: Contact, format: OutputFormat) -> Result<()> {
+    let client = TeamsClient::new(config)?;
+    let created = client.create_contact(&contact).await?;
+    if matches!(format, OutputFormat::Json) {
+        print_single(&created, format);
+    } else {
+        print_success(&format!(
+            "Created contact {} ({})",
+            created.display_name.as_deref().unwrap_or_default(),
+            created.id.as_deref().unwrap_or_default()
+        ));
+    }
+    Ok(())
+}
+
+async fn update(
+    config: &Config,
+    contact_id: &str,
+    changes: Contact,
+    format: OutputFormat,
+) -> Result<()> {
+    if serde_json::to_value(&changes)?
+        .as_object()
+        .is_some_and(|o| o.is_empty())
+    {
+        print_error("Nothing to update. Pass at least one field to change.");
+        return Ok(());
+    }
+
+    let client = TeamsClient::new(config)?;
+    let updated = client.update_contact(contact_id, &changes).await?;
+    if matches!(format, OutputFormat::Json) {
+        print_single(&updated, format);
+    } else {
+        print_success(&format!(
+            "Updated contact {}",
+            updated.display_name.as_deref().unwrap_or(contact_id)
+        ));
+    }
+    Ok(())
+}
+
+async fn delete(config: &Config, contact_id: &str) -> Result<()> {
+    let client = TeamsClient::new(config)?;
+    client.delete_contact(contact_id).await?;
+    print_success("Contact deleted");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_query() {
+        let contact = Contact {
+            display_name: Some("Alice Martin".to_string()),
+            company_name: Some("Contoso".to_string()),
+            email_addresses: vec![EmailAddress {
+                address: "alice@contoso.com".to_string(),
+                name: None,
+            }],
+            ..Default::default()
+        };
+        assert!(matches_query(&contact, "alice"));
+        assert!(matches_query(&contact, "contoso MARTIN"));
+        assert!(!matches_query(&contact, "alice bob"));
+    }
+}
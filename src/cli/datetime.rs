@@ -0,0 +1,500 @@
+use anyhow::{anyhow, bail, Result};
+use chrono::{
+    DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday,
+};
+use chrono_tz::Tz;
+
+use crate::config::Config;
+use crate::types::DateTimeZone;
+
+/// Resolve the time zone used to read and display times:
+/// `--tz`, then `[calendar] timezone` from the config, then the system zone
+pub fn resolve_tz(tz: Option<&str>, config: &Config) -> Result<Tz> {
+    if let Some(name) = tz.or(config.calendar.timezone.as_deref()) {
+        return name.parse::<Tz>().map_err(|_| {
+            anyhow!(
+                "Unknown time zone '{}'. Use an IANA name such as Europe/Paris",
+                name
+            )
+        });
+    }
+
+    Ok(iana_time_zone::get_timezone()
+        .ok()
+        .and_then(|name| name.parse::<Tz>().ok())
+        .unwrap_or(Tz::UTC))
+}
+
+/// Parse a zone name as Graph reports it: IANA ("Europe/Paris") or Windows
+/// ("Romance Standard Time", mapped through CLDR's windowsZones table)
+pub fn parse_zone_name(name: &str) -> Option<Tz> {
+    name.parse::<Tz>().ok().or_else(|| {
+        WINDOWS_ZONES
+            .iter()
+            .find(|(windows, _)| windows.eq_ignore_ascii_case(name))
+            .and_then(|(_, iana)| iana.parse().ok())
+    })
+}
+
+/// Windows time zone IDs and their CLDR "001" (primary) IANA zone
+const WINDOWS_ZONES: &[(&str, &str)] = &[
+    ("Dateline Standard Time", "Etc/GMT+12"),
+    ("UTC-11", "Etc/GMT+11"),
+    ("Aleutian Standard Time", "America/Adak"),
+    ("Hawaiian Standard Time", "Pacific/Honolulu"),
+    ("Marquesas Standard Time", "Pacific/Marquesas"),
+    ("Alaskan Standard Time", "America/Anchorage"),
+    ("UTC-09", "Etc/GMT+9"),
+    ("Pacific Standard Time (Mexico)", "America/Tijuana"),
+    ("UTC-08", "Etc/GMT+8"),
+    ("Pacific Standard Time", "America/Los_Angeles"),
+    ("US Mountain Standard Time", "America/Phoenix"),
+    ("Mountain Standard Time (Mexico)", "America/Mazatlan"),
+    ("Mountain Standard Time", "America/Denver"),
+    ("Yukon Standard Time", "America/Whitehorse"),
+    ("Central America Standard Time", "America/Guatemala"),
+    ("Central Standard Time", "America/Chicago"),
+    ("Easter Island Standard Time", "Pacific/Easter"),
+    ("Central Standard Time (Mexico)", "America/Mexico_City"),
+    ("Canada Central Standard Time", "America/Regina"),
+    ("SA Pacific Standard Time", "America/Bogota"),
+    ("Eastern Standard Time (Mexico)", "America/Cancun"),
+    ("Eastern Standard Time", "America/New_York"),
+    ("Haiti Standard Time", "America/Port-au-Prince"),
+    ("Cuba Standard Time", "America/Havana"),
+    ("US Eastern Standard Time", "America/Indianapolis"),
+    ("Turks And Caicos Standard Time", "America/Grand_Turk"),
+    ("Paraguay Standard Time", "America/Asuncion"),
+    ("Atlantic Standard Time", "America/Halifax"),
+    ("Venezuela Standard Time", "America/Caracas"),
+    ("Central Brazilian Standard Time", "America/Cuiaba"),
+    ("SA Western Standard Time", "America/La_Paz"),
+    ("Pacific SA Standard Time", "America/Santiago"),
+    ("Newfoundland Standard Time", "America/St_Johns"),
+    ("Tocantins Standard Time", "America/Araguaina"),
+    ("E. South America Standard Time", "America/Sao_Paulo"),
+    ("SA Eastern Standard Time", "America/Cayenne"),
+    ("Argentina Standard Time", "America/Buenos_Aires"),
+    ("Greenland Standard Time", "America/Godthab"),
+    ("Montevideo Standard Time", "America/Montevideo"),
+    ("Magallanes Standard Time", "America/Punta_Arenas"),
+    ("Saint Pierre Standard Time", "America/Miquelon"),
+    ("Bahia Standard Time", "America/Bahia"),
+    ("UTC-02", "Etc/GMT+2"),
+    ("Mid-Atlantic Standard Time", "Etc/GMT+2"),
+    ("Azores Standard Time", "Atlantic/Azores"),
+    ("Cape Verde Standard Time", "Atlantic/Cape_Verde"),
+    ("UTC", "Etc/UTC"),
+    ("Coordinated Universal Time", "Etc/UTC"),
+    ("GMT Standard Time", "Europe/London"),
+    ("Greenwich Standard Time", "Atlantic/Reykjavik"),
+    ("Sao Tome Standard Time", "Africa/Sao_Tome"),
+    ("Morocco Standard Time", "Africa/Casablanca"),
+    ("W. Europe Standard Time", "Europe/Berlin"),
+    ("Central Europe Standard Time", "Europe/Budapest"),
+    ("Romance Standard Time", "Europe/Paris"),
+    ("Central European Standard Time", "Europe/Warsaw"),
+    ("W. Central Africa Standard Time", "Africa/Lagos"),
+    ("Jordan Standard Time", "Asia/Amman"),
+    ("GTB Standard Time", "Europe/Bucharest"),
+    ("Middle East Standard Time", "Asia/Beirut"),
+    ("Egypt Standard Time", "Africa/Cairo"),
+    ("E. Europe Standard Time", "Europe/Chisinau"),
+    ("Syria Standard Time", "Asia/Damascus"),
+    ("West Bank Standard Time", "Asia/Hebron"),
+    ("South Africa Standard Time", "Africa/Johannesburg"),
+    ("FLE Standard Time", "Europe/Kiev"),
+    ("Israel Standard Time", "Asia/Jerusalem"),
+    ("South Sudan Standard Time", "Africa/Juba"),
+    ("Kaliningrad Standard Time", "Europe/Kaliningrad"),
+    ("Sudan Standard Time", "Africa/Khartoum"),
+    ("Libya Standard Time", "Africa/Tripoli"),
+    ("Namibia Standard Time", "Africa/Windhoek"),
+    ("Arabic Standard Time", "Asia/Baghdad"),
+    ("Turkey Standard Time", "Europe/Istanbul"),
+    ("Arab Standard Time", "Asia/Riyadh"),
+    ("Belarus Standard Time", "Europe/Minsk"),
+    ("Russian Standard Time", "Europe/Moscow"),
+    ("E. Africa Standard Time", "Africa/Nairobi"),
+    ("Volgograd Standard Time", "Europe/Volgograd"),
+    ("Iran Standard Time", "Asia/Tehran"),
+    ("Arabian Standard Time", "Asia/Dubai"),
+    ("Astrakhan Standard Time", "Europe/Astrakhan"),
+    ("Azerbaijan Standard Time", "Asia/Baku"),
+    ("Russia Time Zone 3", "Europe/Samara"),
+    ("Mauritius Standard Time", "Indian/Mauritius"),
+    ("Saratov Standard Time", "Europe/Saratov"),
+    ("Georgian Standard Time", "Asia/Tbilisi"),
+    ("Caucasus Standard Time", "Asia/Yerevan"),
+    ("Afghanistan Standard Time", "Asia/Kabul"),
+    ("West Asia Standard Time", "Asia/Tashkent"),
+    ("Ekaterinburg Standard Time", "Asia/Yekaterinburg"),
+    ("Pakistan Standard Time", "Asia/Karachi"),
+    ("Qyzylorda Standard Time", "Asia/Qyzylorda"),
+    ("India Standard Time", "Asia/Calcutta"),
+    ("Sri Lanka Standard Time", "Asia/Colombo"),
+    ("Nepal Standard Time", "Asia/Katmandu"),
+    ("Central Asia Standard Time", "Asia/Almaty"),
+    ("Bangladesh Standard Time", "Asia/Dhaka"),
+    ("Omsk Standard Time", "Asia/Omsk"),
+    ("Myanmar Standard Time", "Asia/Rangoon"),
+    ("SE Asia Standard Time", "Asia/Bangkok"),
+    ("Altai Standard Time", "Asia/Barnaul"),
+    ("W. Mongolia Standard Time", "Asia/Hovd"),
+    ("North Asia Standard Time", "Asia/Krasnoyarsk"),
+    ("N. Central Asia Standard Time", "Asia/Novosibirsk"),
+    ("Tomsk Standard Time", "Asia/Tomsk"),
+    ("China Standard Time", "Asia/Shanghai"),
+    ("North Asia East Standard Time", "Asia/Irkutsk"),
+    ("Singapore Standard Time", "Asia/Singapore"),
+    ("W. Australia Standard Time", "Australia/Perth"),
+    ("Taipei Standard Time", "Asia/Taipei"),
+    ("Ulaanbaatar Standard Time", "Asia/Ulaanbaatar"),
+    ("Aus Central W. Standard Time", "Australia/Eucla"),
+    ("Transbaikal Standard Time", "Asia/Chita"),
+    ("Tokyo Standard Time", "Asia/Tokyo"),
+    ("North Korea Standard Time", "Asia/Pyongyang"),
+    ("Korea Standard Time", "Asia/Seoul"),
+    ("Yakutsk Standard Time", "Asia/Yakutsk"),
+    ("Cen. Australia Standard Time", "Australia/Adelaide"),
+    ("AUS Central Standard Time", "Australia/Darwin"),
+    ("E. Australia Standard Time", "Australia/Brisbane"),
+    ("AUS Eastern Standard Time", "Australia/Sydney"),
+    ("West Pacific Standard Time", "Pacific/Port_Moresby"),
+    ("Tasmania Standard Time", "Australia/Hobart"),
+    ("Vladivostok Standard Time", "Asia/Vladivostok"),
+    ("Lord Howe Standard Time", "Australia/Lord_Howe"),
+    ("Bougainville Standard Time", "Pacific/Bougainville"),
+    ("Russia Time Zone 10", "Asia/Srednekolymsk"),
+    ("Magadan Standard Time", "Asia/Magadan"),
+    ("Norfolk Standard Time", "Pacific/Norfolk"),
+    ("Sakhalin Standard Time", "Asia/Sakhalin"),
+    ("Central Pacific Standard Time", "Pacific/Guadalcanal"),
+    ("Russia Time Zone 11", "Asia/Kamchatka"),
+    ("New Zealand Standard Time", "Pacific/Auckland"),
+    ("UTC+12", "Etc/GMT-12"),
+    ("Fiji Standard Time", "Pacific/Fiji"),
+    ("Chatham Islands Standard Time", "Pacific/Chatham"),
+    ("UTC+13", "Etc/GMT-13"),
+    ("Tonga Standard Time", "Pacific/Tongatapu"),
+    ("Samoa Standard Time", "Pacific/Apia"),
+    ("Line Islands Standard Time", "Pacific/Kiritimati"),
+];
+
+/// Current time in the given zone
+pub fn now_in(tz: Tz) -> DateTime<Tz> {
+    Utc::now().with_timezone(&tz)
+}
+
+/// A parsed date expression: a whole day, or a precise instant
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum When {
+    Day(NaiveDate),
+    At(DateTime<Tz>),
+}
+
+impl When {
+    /// First instant covered by the expression
+    pub fn start(self, tz: Tz) -> Result<DateTime<Tz>> {
+        match self {
+            When::Day(date) => localize(date.and_time(NaiveTime::MIN), tz),
+            When::At(dt) => Ok(dt),
+        }
+    }
+
+    /// End of the expression: the following midnight for whole days, the instant otherwise
+    pub fn end(self, tz: Tz) -> Result<DateTime<Tz>> {
+        match self {
+            When::Day(date) => {
+                let next = date
+                    .succ_opt()
+                    .ok_or_else(|| anyhow!("Date out of range"))?;
+                localize(next.and_time(NaiveTime::MIN), tz)
+            }
+            When::At(dt) => Ok(dt),
+        }
+    }
+}
+
+/// Parse a date/time expression relative to `now`.
+///
+/// Accepts ISO dates (`2024-05-01`, `2024-05-01T14:00`, RFC 3339), `now`, `today`,
+/// `tomorrow`, `yesterday`, weekdays (`mon`, `this fri`, `next mon`), an optional time
+/// (`14:00`, `9:30am`, `2pm`) and offsets from now (`+2h`, `-1d`, `in 30m`).
+pub fn parse_when(input: &str, now: DateTime<Tz>) -> Result<When> {
+    let tz = now.timezone();
+    let text = input.trim().to_lowercase();
+
+    if text.is_empty() {
+        bail!("Empty date");
+    }
+    if text == "now" {
+        return Ok(When::At(now));
+    }
+
+    // Offsets from now
+    if let Some(rest) = text.strip_prefix('+').or_else(|| text.strip_prefix("in ")) {
+        return Ok(When::At(now + parse_duration(rest)?));
+    }
+    if let Some(rest) = text.strip_prefix('-') {
+        return Ok(When::At(now - parse_duration(rest)?));
+    }
+
+    // ISO forms
+    if let Ok(dt) = DateTime::parse_from_rfc3339(input.trim()) {
+        return Ok(When::At(dt.with_timezone(&tz)));
+    }
+    for fmt in [
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+    ] {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(input.trim(), fmt) {
+            return Ok(When::At(localize(naive, tz)?));
+        }
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(&text, "%Y-%m-%d") {
+        return Ok(When::Day(date));
+    }
+
+    // Day words followed by an optional time
+    let today = now.date_naive();
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let (date, rest) = match words.as_slice() {
+        ["today", rest @ ..] => (Some(today), rest),
+        ["tomorrow", rest @ ..] => (Some(today + Duration::days(1)), rest),
+        ["yesterday", rest @ ..] => (Some(today - Duration::days(1)), rest),
+        ["next", day, rest @ ..] => (Some(next_weekday(today, parse_weekday(day)?, false)), rest),
+        ["this", day, rest @ ..] => (Some(next_weekday(today, parse_weekday(day)?, true)), rest),
+        [first, rest @ ..] if first.parse::<Weekday>().is_ok() => {
+            (Some(next_weekday(today, parse_weekday(first)?, true)), rest)
+        }
+        [first, rest @ ..] => match NaiveDate::parse_from_str(first, "%Y-%m-%d") {
+            Ok(date) => (Some(date), rest),
+            Err(_) => (None, words.as_slice()),
+        },
+        [] => (None, words.as_slice()),
+    };
+
+    let time = match rest {
+        [] => None,
+        [t] => Some(parse_time(t)?),
+        // "9:30 am"
+        [t, suffix @ ("am" | "pm")] => Some(parse_time(&format!("{}{}", t, suffix))?),
+        _ => bail!("Could not understand date '{}'", input),
+    };
+
+    match (date, time) {
+        (Some(date), None) => Ok(When::Day(date)),
+        (Some(date), Some(time)) => Ok(When::At(localize(date.and_time(time), tz)?)),
+        (None, Some(time)) => Ok(When::At(localize(today.and_time(time), tz)?)),
+        (None, None) => bail!("Could not understand date '{}'", input),
+    }
+}
+
+/// Parse a date/time expression to an instant (whole days start at midnight)
+pub fn parse_datetime(input: &str, tz: Tz) -> Result<DateTime<Tz>> {
+    parse_when(input, now_in(tz))?.start(tz)
+}
+
+/// Parse a duration such as `30m`, `1h`, `1h30m`, `2d`, `1w` or a bare number of minutes
+pub fn parse_duration(input: &str) -> Result<Duration> {
+    let text = input.trim().to_lowercase();
+    if let Ok(minutes) = text.parse::<i64>() {
+        return Ok(Duration::minutes(minutes));
+    }
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    let mut chars = text.chars().filter(|c| !c.is_whitespace()).peekable();
+    let mut matched = false;
+
+    while let Some(c) = chars.next() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let mut unit = c.to_string();
+        while let Some(&next) = chars.peek() {
+            if next.is_ascii_alphabetic() {
+                unit.push(next);
+                chars.next();
+            } else {
+                break;
+            }
+        }
+        let value: i64 = number
+            .parse()
+            .map_err(|_| anyhow!("Invalid duration '{}'", input))?;
+        number.clear();
+
+        total += match unit.as_str() {
+            "w" | "wk" | "week" | "weeks" => Duration::weeks(value),
+            "d" | "day" | "days" => Duration::days(value),
+            "h" | "hr" | "hrs" | "hour" | "hours" => Duration::hours(value),
+            "m" | "min" | "mins" | "minute" | "minutes" => Duration::minutes(value),
+            "s" | "sec" | "secs" | "second" | "seconds" => Duration::seconds(value),
+            _ => bail!("Invalid duration unit '{}' in '{}'", unit, input),
+        };
+        matched = true;
+    }
+
+    if !matched || !number.is_empty() {
+        bail!(
+            "Invalid duration '{}'. Use forms like 30m, 1h, 1h30m or 2d",
+            input
+        );
+    }
+    Ok(total)
+}
+
+/// Format an instant for Graph query parameters (UTC, `Z` suffix)
+pub fn to_graph_utc(dt: &DateTime<Tz>) -> String {
+    dt.with_timezone(&Utc)
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string()
+}
+
+/// Convert a UTC ISO string from Graph API to the given zone and format it
+pub fn format_in_tz(utc_str: &str, format_str: &str, tz: Tz) -> String {
+    // Graph API sometimes returns YYYY-MM-DDTHH:MM:SS.NNNNNNN
+    // We append Z to treat it as UTC if not present
+    let iso_str = if utc_str.ends_with('Z') {
+        utc_str.to_string()
+    } else {
+        format!("{}Z", utc_str)
+    };
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(&iso_str) {
+        dt.with_timezone(&tz).format(format_str).to_string()
+    } else {
+        utc_str.to_string()
+    }
+}
+
+/// Parse a Graph date-time returned without a Prefer header (UTC)
+pub fn graph_time_utc(value: &DateTimeZone) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(&value.date_time, "%Y-%m-%dT%H:%M:%S%.f")
+        .ok()
+        .map(|d| d.and_utc())
+}
+
+/// Attach a zone to a wall-clock time, moving forward over DST gaps
+pub fn localize(naive: NaiveDateTime, tz: Tz) -> Result<DateTime<Tz>> {
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .or_else(|| {
+            tz.from_local_datetime(&(naive + Duration::hours(1)))
+                .earliest()
+        })
+        .ok_or_else(|| anyhow!("Invalid local time {} in {}", naive, tz))
+}
+
+fn parse_weekday(s: &str) -> Result<Weekday> {
+    s.parse::<Weekday>()
+        .map_err(|_| anyhow!("Unknown weekday '{}'", s))
+}
+
+/// Next date falling on `weekday`; `include_today` allows returning `today` itself
+fn next_weekday(today: NaiveDate, weekday: Weekday, include_today: bool) -> NaiveDate {
+    let ahead = (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+    let ahead = if ahead == 0 && !include_today {
+        7
+    } else {
+        ahead
+    };
+    today + Duration::days(ahead as i64)
+}
+
+/// Parse a time of day: `14:00`, `9:30`, `9`, `2pm`, `9:30am`, `14h30`
+fn parse_time(s: &str) -> Result<NaiveTime> {
+    let (clock, offset) = if let Some(t) = s.strip_suffix("am") {
+        (t, Some(0))
+    } else if let Some(t) = s.strip_suffix("pm") {
+        (t, Some(12))
+    } else {
+        (s, None)
+    };
+
+    let (hours, minutes) = match clock.split_once([':', 'h']) {
+        Some((h, "")) => (h, "0"),
+        Some((h, m)) => (h, m),
+        None => (clock, "0"),
+    };
+    let mut hours: u32 = hours.parse().map_err(|_| anyhow!("Invalid time '{}'", s))?;
+    let minutes: u32 = minutes
+        .parse()
+        .map_err(|_| anyhow!("Invalid time '{}'", s))?;
+
+    if let Some(offset) = offset {
+        if hours == 0 || hours > 12 {
+            bail!("Invalid time '{}'", s);
+        }
+        hours = hours % 12 + offset;
+    }
+
+    NaiveTime::from_hms_opt(hours, minutes, 0).ok_or_else(|| anyhow!("Invalid time '{}'", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Tz> {
+        // Wednesday 2024-05-01 10:00 in Paris
+        localize(
+            NaiveDate::from_ymd_opt(2024, 5, 1)
+                .unwrap()
+                .and_hms_opt(10, 0, 0)
+                .unwrap(),
+            chrono_tz::Europe::Paris,
+        )
+        .unwrap()
+    }
+
+    fn at(input: &str) -> String {
+        match parse_when(input, now()).unwrap() {
+            When::At(dt) => dt.format("%Y-%m-%d %H:%M %z").to_string(),
+            When::Day(d) => d.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_when() {
+        assert_eq!(at("tomorrow 14:00"), "2024-05-02 14:00 +0200");
+        assert_eq!(at("next mon 9:30"), "2024-05-06 09:30 +0200");
+        assert_eq!(at("next wed"), "2024-05-08");
+        assert_eq!(at("wed 2pm"), "2024-05-01 14:00 +0200");
+        assert_eq!(at("+2h"), "2024-05-01 12:00 +0200");
+        assert_eq!(at("2024-06-10T08:15"), "2024-06-10 08:15 +0200");
+        assert_eq!(at("2024-06-10"), "2024-06-10");
+        assert!(parse_when("someday", now()).is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30m").unwrap(), Duration::minutes(30));
+        assert_eq!(parse_duration("1h30m").unwrap(), Duration::minutes(90));
+        assert_eq!(parse_duration("45").unwrap(), Duration::minutes(45));
+        assert_eq!(parse_duration("2d").unwrap(), Duration::days(2));
+        assert!(parse_duration("3x").is_err());
+    }
+
+    #[test]
+    fn test_parse_zone_name() {
+        assert_eq!(
+            parse_zone_name("Europe/Paris"),
+            Some(chrono_tz::Europe::Paris)
+        );
+        assert_eq!(
+            parse_zone_name("Romance Standard Time"),
+            Some(chrono_tz::Europe::Paris)
+        );
+        assert_eq!(
+            parse_zone_name("pacific standard time"),
+            Some(chrono_tz::America::Los_Angeles)
+        );
+        assert_eq!(parse_zone_name("Customized Time Zone"), None);
+    }
+}
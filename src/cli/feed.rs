@@ -7,7 +7,8 @@ use tabled::Tabled;
 use crate::api::TeamsClient;
 use crate::config::Config;
 
-use super::output::{print_output, print_single};
+use super::mailsync;
+use super::output::{print_info, print_output, print_single, print_warning};
 use super::utils::{strip_html, truncate};
 use super::OutputFormat;
 
@@ -37,7 +38,7 @@ pub enum FeedSource {
     All,
     /// Show only Teams messages
     Chats,
-    /// Show only emails
+    /// Show only emails received since the last feed run
     Mail,
 }
 
@@ -150,51 +151,59 @@ pub async fn execute(cmd: FeedCommand, config: &Config, format: OutputFormat) ->
         }
     }
 
-    // Collect emails
+    // Collect emails received since the last feed run
     if matches!(cmd.source, FeedSource::All | FeedSource::Mail) {
-        if let Ok(emails) = client.get_mail_messages(Some("inbox"), 50).await {
-            for email in emails.value {
-                let is_unread = email.is_read != Some(true);
-
-                if cmd.unread && !is_unread {
-                    continue;
+        match mailsync::sync_folder(&client, "feed", "inbox").await {
+            Ok(changes) if changes.initial => {
+                if !matches!(format, OutputFormat::Json) {
+                    print_info("Tracking inbox changes; new emails show up from the next run");
                 }
+            }
+            Ok(changes) => {
+                for email in changes.new {
+                    let is_unread = email.is_read != Some(true);
 
-                let sender = email
-                    .from
-                    .as_ref()
-                    .map(|f| {
-                        f.email_address
-                            .name
-                            .clone()
-                            .unwrap_or_else(|| f.email_address.address.clone())
-                    })
-                    .unwrap_or_else(|| "Unknown".to_string());
-
-                let subject = email
-                    .subject
-                    .clone()
-                    .unwrap_or_else(|| "(No subject)".to_string());
-
-                let time_str = email.received_date_time.clone().unwrap_or_default();
-                let timestamp = parse_timestamp(&time_str);
-
-                items.push(FeedItemJson {
-                    time: format_time(&time_str),
-                    timestamp,
-                    item_type: "📧 Mail".to_string(),
-                    from: truncate(&sender, 25),
-                    content: truncate(&subject, 50),
-                    unread: is_unread,
-                    source_id: email.id.clone().unwrap_or_default(),
-                    chat_id: None,
-                });
+                    if cmd.unread && !is_unread {
+                        continue;
+                    }
+
+                    let sender = email
+                        .from
+                        .as_ref()
+                        .map(|f| {
+                            f.email_address
+                                .name
+                                .clone()
+                                .unwrap_or_else(|| f.email_address.address.clone())
+                        })
+                        .unwrap_or_else(|| "Unknown".to_string());
+
+                    let subject = email
+                        .subject
+                        .clone()
+                        .unwrap_or_else(|| "(No subject)".to_string());
+
+                    let time_str = email.received_date_time.clone().unwrap_or_default();
+                    let timestamp = parse_timestamp(&time_str);
+
+                    items.push(FeedItemJson {
+                        time: format_time(&time_str),
+                        timestamp,
+                        item_type: "📧 Mail".to_string(),
+                        from: truncate(&sender, 25),
+                        content: truncate(&subject, 50),
+                        unread: is_unread,
+                        source_id: email.id.clone().unwrap_or_default(),
+                        chat_id: None,
+                    });
+                }
             }
+            Err(e) => print_warning(&format!("Mail sync failed: {:#}", e)),
         }
     }
 
     // Sort by timestamp (newest first)
-    items.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    items.sort_by_key(|i| std::cmp::Reverse(i.timestamp));
 
     // Limit results
     items.truncate(cmd.limit);
@@ -0,0 +1,809 @@
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+
+use crate::types::{
+    AttendeeRequest, CalendarEvent, CreateEventRequest, DateTimeZone, EmailAddressSimple,
+    EventBody, Location, PatternedRecurrence, RecurrencePattern, RecurrenceRange,
+    SingleValueExtendedProperty, ICS_UID_PROPERTY,
+};
+
+const PRODID: &str = "PRODID:-//squads-cli//Calendar//EN";
+const WEEKDAYS: [(&str, &str); 7] = [
+    ("MO", "monday"),
+    ("TU", "tuesday"),
+    ("WE", "wednesday"),
+    ("TH", "thursday"),
+    ("FR", "friday"),
+    ("SA", "saturday"),
+    ("SU", "sunday"),
+];
+const INDEXES: [(&str, &str); 5] = [
+    ("1", "first"),
+    ("2", "second"),
+    ("3", "third"),
+    ("4", "fourth"),
+    ("-1", "last"),
+];
+
+/// Whether a mail attachment looks like an iCalendar invite
+pub fn is_ics_attachment(name: &str, content_type: Option<&str>) -> bool {
+    name.to_lowercase().ends_with(".ics")
+        || content_type.is_some_and(|t| t.to_lowercase().starts_with("text/calendar"))
+}
+
+// ============================================================================
+// Export
+// ============================================================================
+
+/// Serialize events to an RFC 5545 VCALENDAR
+pub fn events_to_ics(events: &[CalendarEvent]) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        PRODID.to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+    ];
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        event_lines(event, &stamp, &mut lines);
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut out = String::new();
+    for line in lines {
+        out.push_str(&fold(&line));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+fn event_lines(event: &CalendarEvent, stamp: &str, lines: &mut Vec<String>) {
+    let uid = event
+        .i_cal_u_id
+        .as_deref()
+        .or(event.id.as_deref())
+        .unwrap_or_default();
+    lines.push(format!("UID:{}", escape_text(uid)));
+    lines.push(format!("DTSTAMP:{}", stamp));
+
+    let all_day = event.is_all_day.unwrap_or(false);
+    if let Some(start) = &event.start {
+        lines.push(format!("DTSTART{}", format_time(start, all_day)));
+    }
+    if let Some(end) = &event.end {
+        lines.push(format!("DTEND{}", format_time(end, all_day)));
+    }
+    if let Some(rule) = event.recurrence.as_ref().and_then(recurrence_to_rrule) {
+        lines.push(format!("RRULE:{}", rule));
+    }
+
+    if let Some(subject) = &event.subject {
+        lines.push(format!("SUMMARY:{}", escape_text(subject)));
+    }
+    if let Some(location) = event
+        .location
+        .as_ref()
+        .and_then(|l| l.display_name.as_deref())
+        .filter(|l| !l.is_empty())
+    {
+        lines.push(format!("LOCATION:{}", escape_text(location)));
+    }
+    if let Some(body) = event.body_preview.as_deref().filter(|b| !b.is_empty()) {
+        lines.push(format!("DESCRIPTION:{}", escape_text(body)));
+    }
+    let join_url = event
+        .online_meeting
+        .as_ref()
+        .and_then(|m| m.join_url.as_deref())
+        .or(event.online_meeting_url.as_deref());
+    if let Some(url) = join_url {
+        lines.push(format!("URL:{}", url));
+    }
+
+    if let Some(email) = event
+        .organizer
+        .as_ref()
+        .and_then(|o| o.email_address.as_ref())
+    {
+        if let Some(address) = &email.address {
+            lines.push(format!(
+                "ORGANIZER{}:mailto:{}",
+                cn_param(email.name.as_deref()),
+                address
+            ));
+        }
+    }
+    for attendee in event.attendees.iter().flatten() {
+        let Some(email) = &attendee.email_address else {
+            continue;
+        };
+        let Some(address) = &email.address else {
+            continue;
+        };
+        let role = match attendee.attendee_type.as_deref() {
+            Some("optional") => "OPT-PARTICIPANT",
+            Some("resource") => "NON-PARTICIPANT",
+            _ => "REQ-PARTICIPANT",
+        };
+        let partstat = match attendee.status.as_ref().and_then(|s| s.response.as_deref()) {
+            Some("accepted") | Some("organizer") => "ACCEPTED",
+            Some("tentativelyAccepted") => "TENTATIVE",
+            Some("declined") => "DECLINED",
+            _ => "NEEDS-ACTION",
+        };
+        lines.push(format!(
+            "ATTENDEE{};ROLE={};PARTSTAT={}:mailto:{}",
+            cn_param(email.name.as_deref()),
+            role,
+            partstat,
+            address
+        ));
+    }
+
+    let status = if event.is_cancelled.unwrap_or(false) {
+        "CANCELLED"
+    } else {
+        "CONFIRMED"
+    };
+    lines.push(format!("STATUS:{}", status));
+}
+
+/// Property value (with leading parameters and colon) for a Graph date-time
+fn format_time(time: &DateTimeZone, all_day: bool) -> String {
+    let naive = parse_graph_naive(&time.date_time);
+    match naive {
+        Some(naive) if all_day => format!(";VALUE=DATE:{}", naive.format("%Y%m%d")),
+        Some(naive) if time.time_zone == "UTC" => {
+            format!(":{}", naive.format("%Y%m%dT%H%M%SZ"))
+        }
+        Some(naive) => format!(";TZID={}:{}", time.time_zone, naive.format("%Y%m%dT%H%M%S")),
+        None => format!(":{}", time.date_time),
+    }
+}
+
+fn parse_graph_naive(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim_end_matches('Z');
+    let value = value.split('.').next().unwrap_or(value);
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").ok()
+}
+
+fn cn_param(name: Option<&str>) -> String {
+    match name.filter(|n| !n.is_empty()) {
+        Some(name) => format!(";CN=\"{}\"", name.replace('"', "'")),
+        None => String::new(),
+    }
+}
+
+fn recurrence_to_rrule(recurrence: &PatternedRecurrence) -> Option<String> {
+    let pattern = &recurrence.pattern;
+    let days = || {
+        pattern
+            .days_of_week
+            .iter()
+            .flatten()
+            .filter_map(|d| {
+                WEEKDAYS
+                    .iter()
+                    .find(|(_, name)| name.eq_ignore_ascii_case(d))
+                    .map(|(code, _)| *code)
+            })
+            .collect::<Vec<_>>()
+    };
+    let index = || {
+        let index = pattern.index.as_deref().unwrap_or("first");
+        INDEXES
+            .iter()
+            .find(|(_, name)| *name == index)
+            .map(|(n, _)| *n)
+            .unwrap_or("1")
+    };
+    let relative_days = || {
+        let index = index();
+        days()
+            .iter()
+            .map(|d| format!("{}{}", index, d))
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+
+    let mut parts = match pattern.pattern_type.as_str() {
+        "daily" => vec!["FREQ=DAILY".to_string()],
+        "weekly" => vec![
+            "FREQ=WEEKLY".to_string(),
+            format!("BYDAY={}", days().join(",")),
+        ],
+        "absoluteMonthly" => vec![
+            "FREQ=MONTHLY".to_string(),
+            format!("BYMONTHDAY={}", pattern.day_of_month?),
+        ],
+        "relativeMonthly" => vec![
+            "FREQ=MONTHLY".to_string(),
+            format!("BYDAY={}", relative_days()),
+        ],
+        "absoluteYearly" => vec![
+            "FREQ=YEARLY".to_string(),
+            format!("BYMONTH={}", pattern.month?),
+            format!("BYMONTHDAY={}", pattern.day_of_month?),
+        ],
+        "relativeYearly" => vec![
+            "FREQ=YEARLY".to_string(),
+            format!("BYMONTH={}", pattern.month?),
+            format!("BYDAY={}", relative_days()),
+        ],
+        _ => return None,
+    };
+    if pattern.interval > 1 {
+        parts.push(format!("INTERVAL={}", pattern.interval));
+    }
+
+    let range = &recurrence.range;
+    match range.range_type.as_str() {
+        "endDate" => {
+            let end = NaiveDate::parse_from_str(range.end_date.as_deref()?, "%Y-%m-%d").ok()?;
+            parts.push(format!("UNTIL={}T235959Z", end.format("%Y%m%d")));
+        }
+        "numbered" => parts.push(format!("COUNT={}", range.number_of_occurrences?)),
+        _ => {}
+    }
+    Some(parts.join(";"))
+}
+
+/// Escape a TEXT value (RFC 5545 §3.3.11)
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Fold a content line at 75 octets without splitting characters
+fn fold(line: &str) -> String {
+    let mut out = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        let len = c.len_utf8();
+        if width + len > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += len;
+    }
+    out
+}
+
+// ============================================================================
+// Import
+// ============================================================================
+
+/// A date or date-time read from an .ics file
+#[derive(Debug, Clone, PartialEq)]
+pub enum IcsTime {
+    Date(NaiveDate),
+    Utc(DateTime<Utc>),
+    /// Wall-clock time with its TZID, or floating when there is none
+    Local(NaiveDateTime, Option<String>),
+}
+
+/// An attendee read from an .ics file
+#[derive(Debug, Clone)]
+pub struct IcsAttendee {
+    pub email: String,
+    pub name: Option<String>,
+    pub optional: bool,
+}
+
+/// A VEVENT read from an .ics file
+#[derive(Debug, Clone, Default)]
+pub struct IcsEvent {
+    pub uid: Option<String>,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub url: Option<String>,
+    pub start: Option<IcsTime>,
+    pub end: Option<IcsTime>,
+    pub duration: Option<Duration>,
+    pub rrule: Option<String>,
+    pub status: Option<String>,
+    pub attendees: Vec<IcsAttendee>,
+}
+
+/// Parse the VEVENTs of an iCalendar document
+pub fn parse_ics(text: &str) -> Result<Vec<IcsEvent>> {
+    // Unfold continuation lines
+    let mut lines: Vec<String> = Vec::new();
+    for raw in text.lines() {
+        let raw = raw.trim_end_matches('\r');
+        if let Some(rest) = raw.strip_prefix([' ', '\t']) {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(rest);
+                continue;
+            }
+        }
+        if !raw.is_empty() {
+            lines.push(raw.to_string());
+        }
+    }
+
+    if !lines
+        .first()
+        .is_some_and(|l| l.eq_ignore_ascii_case("BEGIN:VCALENDAR"))
+    {
+        bail!("Not an iCalendar file (missing BEGIN:VCALENDAR)");
+    }
+
+    let mut events = Vec::new();
+    let mut current: Option<IcsEvent> = None;
+    // Depth of components nested in the VEVENT (VALARM...), whose properties are ignored
+    let mut nested = 0;
+
+    for line in &lines {
+        let (name, params, value) = split_property(line)?;
+        match (name.as_str(), value) {
+            ("BEGIN", v) if v.eq_ignore_ascii_case("VEVENT") => {
+                current = Some(IcsEvent::default());
+                nested = 0;
+            }
+            ("END", v) if v.eq_ignore_ascii_case("VEVENT") => {
+                if let Some(event) = current.take() {
+                    events.push(event);
+                }
+            }
+            ("BEGIN", _) if current.is_some() => nested += 1,
+            ("END", _) if current.is_some() => nested -= 1,
+            _ => {
+                if nested > 0 {
+                    continue;
+                }
+                if let Some(event) = current.as_mut() {
+                    apply_property(event, &name, &params, value)?;
+                }
+            }
+        }
+    }
+
+    Ok(events)
+}
+
+fn apply_property(
+    event: &mut IcsEvent,
+    name: &str,
+    params: &[(String, String)],
+    value: &str,
+) -> Result<()> {
+    let param = |key: &str| {
+        params
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    };
+    match name {
+        "UID" => event.uid = Some(unescape_text(value)),
+        "SUMMARY" => event.summary = Some(unescape_text(value)),
+        "DESCRIPTION" => event.description = Some(unescape_text(value)),
+        "LOCATION" => event.location = Some(unescape_text(value)),
+        "URL" => event.url = Some(value.to_string()),
+        "RRULE" => event.rrule = Some(value.to_string()),
+        "STATUS" => event.status = Some(value.to_uppercase()),
+        "DTSTART" => event.start = Some(parse_time(value, param("VALUE"), param("TZID"))?),
+        "DTEND" => event.end = Some(parse_time(value, param("VALUE"), param("TZID"))?),
+        "DURATION" => event.duration = Some(parse_ics_duration(value)?),
+        "ATTENDEE" => {
+            let email = strip_mailto(value);
+            if !email.is_empty() {
+                event.attendees.push(IcsAttendee {
+                    email,
+                    name: param("CN").map(str::to_string),
+                    optional: param("ROLE")
+                        .is_some_and(|r| r.eq_ignore_ascii_case("OPT-PARTICIPANT")),
+                });
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Property parameters as uppercase name and raw value
+type Params = Vec<(String, String)>;
+
+/// Split `NAME;PARAM=VALUE:value` into its name, parameters and value
+fn split_property(line: &str) -> Result<(String, Params, &str)> {
+    let mut in_quotes = false;
+    let colon = line
+        .char_indices()
+        .find(|&(_, c)| {
+            if c == '"' {
+                in_quotes = !in_quotes;
+            }
+            c == ':' && !in_quotes
+        })
+        .map(|(i, _)| i)
+        .ok_or_else(|| anyhow!("Invalid iCalendar line: {}", line))?;
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+
+    let mut parts = Vec::new();
+    let mut part = String::new();
+    let mut in_quotes = false;
+    for c in head.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ';' if !in_quotes => parts.push(std::mem::take(&mut part)),
+            _ => part.push(c),
+        }
+    }
+    parts.push(part);
+
+    let name = parts[0].to_uppercase();
+    let params = parts[1..]
+        .iter()
+        .filter_map(|p| p.split_once('='))
+        .map(|(k, v)| (k.to_uppercase(), v.to_string()))
+        .collect();
+    Ok((name, params, value))
+}
+
+fn parse_time(value: &str, value_type: Option<&str>, tzid: Option<&str>) -> Result<IcsTime> {
+    let invalid = || anyhow!("Invalid iCalendar date '{}'", value);
+    if value_type.is_some_and(|t| t.eq_ignore_ascii_case("DATE")) || value.len() == 8 {
+        return NaiveDate::parse_from_str(value, "%Y%m%d")
+            .map(IcsTime::Date)
+            .map_err(|_| invalid());
+    }
+    if let Some(utc) = value.strip_suffix('Z') {
+        return NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S")
+            .map(|n| IcsTime::Utc(n.and_utc()))
+            .map_err(|_| invalid());
+    }
+    NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+        .map(|n| IcsTime::Local(n, tzid.map(str::to_string)))
+        .map_err(|_| invalid())
+}
+
+/// Parse an RFC 5545 duration such as `PT1H30M`, `P1D` or `-PT15M`
+fn parse_ics_duration(value: &str) -> Result<Duration> {
+    let invalid = || anyhow!("Invalid iCalendar duration '{}'", value);
+    let (negative, rest) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let rest = rest.strip_prefix('P').ok_or_else(invalid)?;
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for c in rest.chars() {
+        match c {
+            'T' => continue,
+            '0'..='9' => number.push(c),
+            unit => {
+                let n: i64 = number.parse().map_err(|_| invalid())?;
+                number.clear();
+                total += match unit {
+                    'W' => Duration::weeks(n),
+                    'D' => Duration::days(n),
+                    'H' => Duration::hours(n),
+                    'M' => Duration::minutes(n),
+                    'S' => Duration::seconds(n),
+                    _ => return Err(invalid()),
+                };
+            }
+        }
+    }
+    if !number.is_empty() {
+        return Err(invalid());
+    }
+    Ok(if negative { -total } else { total })
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut out = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') | Some('N') => out.push('\n'),
+                Some(other) => out.push(other),
+                None => {}
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn strip_mailto(value: &str) -> String {
+    let value = value.trim();
+    match value.get(..7) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("mailto:") => value[7..].to_string(),
+        _ => value.to_string(),
+    }
+}
+
+impl IcsEvent {
+    /// Build the Graph request creating this event; floating times use `tz`.
+    /// Attendees are only copied when `invite_attendees` is set, since Graph
+    /// sends each of them an invitation from the importing user.
+    pub fn to_create_request(&self, tz: Tz, invite_attendees: bool) -> Result<CreateEventRequest> {
+        let start = self
+            .start
+            .clone()
+            .ok_or_else(|| anyhow!("Event has no DTSTART"))?;
+        let all_day = matches!(start, IcsTime::Date(_));
+        let end = match (&self.end, self.duration) {
+            (Some(end), _) => end.clone(),
+            (None, Some(duration)) => shift(&start, duration),
+            (None, None) if all_day => shift(&start, Duration::days(1)),
+            (None, None) => start.clone(),
+        };
+
+        let recurrence = match &self.rrule {
+            Some(rule) => Some(rrule_to_recurrence(rule, start_date(&start), tz)?),
+            None => None,
+        };
+
+        let description = match (&self.description, &self.url) {
+            (Some(d), Some(url)) if !d.contains(url.as_str()) => Some(format!("{}\n\n{}", d, url)),
+            (Some(d), _) => Some(d.clone()),
+            (None, Some(url)) => Some(url.clone()),
+            (None, None) => None,
+        };
+
+        let attendees: Vec<AttendeeRequest> = self
+            .attendees
+            .iter()
+            .filter(|_| invite_attendees)
+            .map(|a| AttendeeRequest {
+                email_address: EmailAddressSimple {
+                    name: a.name.clone(),
+                    address: Some(a.email.clone()),
+                },
+                attendee_type: if a.optional { "optional" } else { "required" }.to_string(),
+            })
+            .collect();
+
+        Ok(CreateEventRequest {
+            subject: self
+                .summary
+                .clone()
+                .unwrap_or_else(|| "(no subject)".into()),
+            start: graph_time(&start, tz),
+            end: graph_time(&end, tz),
+            body: description.map(|content| EventBody {
+                content_type: "text".to_string(),
+                content,
+            }),
+            location: self.location.clone().map(|l| Location {
+                display_name: Some(l),
+                location_uri: None,
+            }),
+            attendees: if attendees.is_empty() {
+                None
+            } else {
+                Some(attendees)
+            },
+            is_online_meeting: None,
+            online_meeting_provider: None,
+            recurrence,
+            is_all_day: all_day.then_some(true),
+            single_value_extended_properties: self.uid.clone().map(|value| {
+                vec![SingleValueExtendedProperty {
+                    id: ICS_UID_PROPERTY.to_string(),
+                    value,
+                }]
+            }),
+        })
+    }
+}
+
+fn shift(time: &IcsTime, by: Duration) -> IcsTime {
+    match time {
+        IcsTime::Date(d) => IcsTime::Date(*d + by),
+        IcsTime::Utc(dt) => IcsTime::Utc(*dt + by),
+        IcsTime::Local(n, tzid) => IcsTime::Local(*n + by, tzid.clone()),
+    }
+}
+
+fn start_date(time: &IcsTime) -> NaiveDate {
+    match time {
+        IcsTime::Date(d) => *d,
+        IcsTime::Utc(dt) => dt.date_naive(),
+        IcsTime::Local(n, _) => n.date(),
+    }
+}
+
+/// Graph date-time for an .ics time. Graph accepts IANA and Windows zone names;
+/// other TZIDs (custom VTIMEZONE labels) fall back to `tz`.
+fn graph_time(time: &IcsTime, tz: Tz) -> DateTimeZone {
+    let (naive, zone) = match time {
+        IcsTime::Date(d) => (d.and_time(chrono::NaiveTime::MIN), tz.name().to_string()),
+        IcsTime::Utc(dt) => (dt.naive_utc(), "UTC".to_string()),
+        IcsTime::Local(n, Some(tzid))
+            if tzid.parse::<Tz>().is_ok()
+                || tzid.ends_with("Standard Time")
+                || tzid.ends_with("Daylight Time") =>
+        {
+            (*n, tzid.clone())
+        }
+        IcsTime::Local(n, _) => (*n, tz.name().to_string()),
+    };
+    DateTimeZone {
+        date_time: naive.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        time_zone: zone,
+    }
+}
+
+/// Convert an RRULE to a Graph recurrence starting on `start`
+fn rrule_to_recurrence(rule: &str, start: NaiveDate, tz: Tz) -> Result<PatternedRecurrence> {
+    let parts: Vec<(String, String)> = rule
+        .split(';')
+        .filter_map(|p| p.split_once('='))
+        .map(|(k, v)| (k.to_uppercase(), v.to_uppercase()))
+        .collect();
+    let get = |key: &str| {
+        parts
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    };
+    let number = |key: &str| -> Result<Option<u32>> {
+        get(key)
+            .map(|v| {
+                v.parse::<u32>()
+                    .map_err(|_| anyhow!("Invalid {} '{}' in RRULE", key, v))
+            })
+            .transpose()
+    };
+
+    // BYDAY entries split into an optional ordinal and a day code
+    let mut ordinal = None;
+    let mut days = Vec::new();
+    for entry in get("BYDAY").into_iter().flat_map(|v| v.split(',')) {
+        let split = entry.len().saturating_sub(2);
+        let (index, code) = entry.split_at(split);
+        let day = WEEKDAYS
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map(|(_, name)| name.to_string())
+            .ok_or_else(|| anyhow!("Invalid BYDAY '{}' in RRULE", entry))?;
+        if !index.is_empty() {
+            let index = index.trim_start_matches('+');
+            ordinal = Some(
+                INDEXES
+                    .iter()
+                    .find(|(n, _)| *n == index)
+                    .map(|(_, name)| name.to_string())
+                    .ok_or_else(|| anyhow!("Unsupported BYDAY ordinal '{}'", entry))?,
+            );
+        }
+        days.push(day);
+    }
+    let start_day = WEEKDAYS[start.weekday().num_days_from_monday() as usize]
+        .1
+        .to_string();
+
+    let mut pattern = RecurrencePattern {
+        pattern_type: String::new(),
+        interval: number("INTERVAL")?.unwrap_or(1),
+        days_of_week: None,
+        day_of_month: None,
+        month: None,
+        first_day_of_week: None,
+        index: None,
+    };
+    match get("FREQ") {
+        Some("DAILY") => pattern.pattern_type = "daily".into(),
+        Some("WEEKLY") => {
+            pattern.pattern_type = "weekly".into();
+            pattern.days_of_week = Some(if days.is_empty() {
+                vec![start_day]
+            } else {
+                days
+            });
+            pattern.first_day_of_week = Some("monday".into());
+        }
+        Some(freq @ ("MONTHLY" | "YEARLY")) => {
+            let yearly = freq == "YEARLY";
+            if yearly {
+                pattern.month = Some(number("BYMONTH")?.unwrap_or(start.month()));
+            }
+            if let Some(index) = ordinal {
+                pattern.pattern_type = if yearly {
+                    "relativeYearly"
+                } else {
+                    "relativeMonthly"
+                }
+                .into();
+                pattern.days_of_week = Some(days);
+                pattern.index = Some(index);
+            } else {
+                pattern.pattern_type = if yearly {
+                    "absoluteYearly"
+                } else {
+                    "absoluteMonthly"
+                }
+                .into();
+                pattern.day_of_month = Some(number("BYMONTHDAY")?.unwrap_or(start.day()));
+            }
+        }
+        other => bail!("Unsupported RRULE frequency: {}", other.unwrap_or("none")),
+    }
+
+    let mut range = RecurrenceRange {
+        range_type: "noEnd".into(),
+        start_date: start.format("%Y-%m-%d").to_string(),
+        end_date: None,
+        number_of_occurrences: None,
+        recurrence_time_zone: Some(tz.name().to_string()),
+    };
+    if let Some(until) = get("UNTIL") {
+        let date = until
+            .get(..8)
+            .and_then(|d| NaiveDate::parse_from_str(d, "%Y%m%d").ok())
+            .ok_or_else(|| anyhow!("Invalid UNTIL '{}' in RRULE", until))?;
+        range.range_type = "endDate".into();
+        range.end_date = Some(date.format("%Y-%m-%d").to_string());
+    } else if let Some(count) = number("COUNT")? {
+        range.range_type = "numbered".into();
+        range.number_of_occurrences = Some(count);
+    }
+
+    Ok(PatternedRecurrence { pattern, range })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ics() {
+        let text = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VEVENT\r\nUID:abc@example.com\r\n\
+            SUMMARY:Plan\\, review\r\nDESCRIPTION:Line one\\nLine \r\n two\r\n\
+            DTSTART;TZID=Europe/Paris:20240506T093000\r\nDURATION:PT1H30M\r\n\
+            RRULE:FREQ=MONTHLY;BYDAY=-1FR;COUNT=3\r\n\
+            ATTENDEE;CN=\"Doe, Jane\";ROLE=OPT-PARTICIPANT:MAILTO:jane@example.com\r\n\
+            BEGIN:VALARM\r\nDESCRIPTION:Reminder\r\nEND:VALARM\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+        let events = parse_ics(text).unwrap();
+        assert_eq!(events.len(), 1);
+
+        let event = &events[0];
+        assert_eq!(event.summary.as_deref(), Some("Plan, review"));
+        assert_eq!(event.description.as_deref(), Some("Line one\nLine two"));
+        assert_eq!(event.attendees[0].email, "jane@example.com");
+        assert_eq!(event.attendees[0].name.as_deref(), Some("Doe, Jane"));
+        assert!(event.attendees[0].optional);
+
+        let request = event.to_create_request(chrono_tz::UTC, false).unwrap();
+        assert!(request.attendees.is_none());
+        let invited = event.to_create_request(chrono_tz::UTC, true).unwrap();
+        let attendees = invited.attendees.unwrap();
+        assert_eq!(attendees.len(), 1);
+        assert_eq!(attendees[0].attendee_type, "optional");
+        assert_eq!(request.start.date_time, "2024-05-06T09:30:00");
+        assert_eq!(request.start.time_zone, "Europe/Paris");
+        assert_eq!(request.end.date_time, "2024-05-06T11:00:00");
+        let recurrence = request.recurrence.unwrap();
+        assert_eq!(recurrence.pattern.pattern_type, "relativeMonthly");
+        assert_eq!(recurrence.pattern.index.as_deref(), Some("last"));
+        assert_eq!(recurrence.range.number_of_occurrences, Some(3));
+        assert_eq!(
+            recurrence_to_rrule(&recurrence).unwrap(),
+            "FREQ=MONTHLY;BYDAY=-1FR;COUNT=3"
+        );
+    }
+
+    #[test]
+    fn test_fold_and_escape() {
+        let line = format!("SUMMARY:{}", escape_text(&"a;b, é".repeat(20)));
+        let folded = fold(&line);
+        assert!(folded.split("\r\n").all(|l| l.len() <= 75));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+}
@@ -1,77 +1,187 @@
-use std::io::{self, Read};
-
-use anyhow::Result;
-use clap::{Args, Subcommand};
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use clap::{Args, Subcommand, ValueEnum};
+use colored::Colorize;
 use serde::Serialize;
+use serde_json::json;
 use tabled::Tabled;
 
 use crate::api::TeamsClient;
 use crate::config::Config;
-
-use super::output::{print_error, print_output, print_single, print_success};
-use super::utils::{markdown_to_html, strip_html, truncate};
+use crate::types::{
+    AutomaticRepliesSetting, BatchRequest, DateTimeZone, ItemBody, MailFilter, MailFolder,
+    MailMessage, MailboxSettings, Recipient,
+};
+
+use super::datetime::{format_in_tz, now_in, parse_when, resolve_tz, to_graph_utc};
+use super::ics::is_ics_attachment;
+use super::mailstore::{store_message, ExportState, StoreFormat, StoredMessage};
+use super::mailsync;
+use super::output::{
+    print_error, print_info, print_output, print_single, print_success, print_warning,
+};
+use super::people::resolve_addresses;
+use super::rules::{self, RulesSubcommand};
+use super::templates::{Template, TemplateArgs};
+use super::utils::{html_escape, html_to_text, markdown_to_html, strip_html, truncate};
 use super::OutputFormat;
 
 #[derive(Args, Debug)]
 pub struct MailCommand {
+    /// Shared or delegated mailbox to use instead of your own (e.g. support@contoso.com)
+    #[arg(long, global = true)]
+    pub mailbox: Option<String>,
+
     #[command(subcommand)]
     pub command: MailSubcommand,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum MailSubcommand {
-    /// List mail folders
-    Folders,
+    /// List mail folders, or create, rename and delete them
+    Folders {
+        /// Include nested folders, shown as a tree
+        #[arg(long)]
+        tree: bool,
+
+        #[command(subcommand)]
+        command: Option<FoldersSubcommand>,
+    },
 
     /// List mail messages
     List {
-        /// Folder to list (inbox, sentitems, drafts, etc.)
+        /// Folder to list (inbox, sentitems, a path like Projects/Alpha, or an ID)
         #[arg(long)]
         folder: Option<String>,
 
         /// Maximum number of messages
         #[arg(short, long, default_value = "20")]
         limit: usize,
+
+        /// Group messages by conversation
+        #[arg(long)]
+        threads: bool,
+
+        #[command(flatten)]
+        filter: FilterArgs,
     },
 
     /// Read a specific email
     Read {
         /// Message ID
         message_id: String,
+
+        /// Print the RFC 822 source instead
+        #[arg(long)]
+        raw: bool,
+    },
+
+    /// Export a folder to Maildir, mbox or .eml files; reruns download only messages not exported yet
+    Export {
+        /// Folder to export (name, path or ID)
+        #[arg(long, default_value = "inbox")]
+        folder: String,
+
+        /// Local format
+        #[arg(long = "as", value_enum, default_value = "maildir")]
+        store: StoreFormat,
+
+        /// Output directory
+        #[arg(short, long)]
+        output: String,
+    },
+
+    /// Report new, read/unread, changed and removed messages since the last sync
+    Sync {
+        /// Folder to sync (name, path or ID)
+        #[arg(long, default_value = "inbox")]
+        folder: String,
+
+        /// Consumer name; each name keeps its own sync state
+        #[arg(long, default_value = "cli")]
+        name: String,
+
+        /// Forget the saved state and start over
+        #[arg(long)]
+        reset: bool,
+    },
+
+    /// Upload .eml files into a folder (Graph stores them as drafts)
+    Import {
+        /// .eml files (- for stdin)
+        #[arg(required = true)]
+        files: Vec<String>,
+
+        /// Destination folder (name, path or ID)
+        #[arg(long, default_value = "inbox")]
+        folder: String,
+    },
+
+    /// Show the whole conversation a message belongs to
+    Thread {
+        /// ID of any message in the conversation
+        message_id: String,
+
+        /// Show full bodies instead of collapsing quoted history
+        #[arg(long)]
+        full: bool,
     },
 
     /// Send an email
     Send {
-        /// Recipient email address(es), comma-separated
+        /// Recipients (comma-separated names or emails)
         #[arg(short, long)]
         to: String,
 
-        /// Email subject
+        /// Email subject (defaults to the template's Subject: line)
         #[arg(short, long)]
-        subject: String,
+        subject: Option<String>,
 
         /// Email body (omit to read from stdin)
+        #[arg(conflicts_with = "template")]
         body: Option<String>,
 
-        /// CC recipients, comma-separated
+        /// CC recipients (comma-separated names or emails)
         #[arg(short, long)]
         cc: Option<String>,
 
         /// Read body from stdin
-        #[arg(long)]
+        #[arg(long, conflicts_with = "template")]
         stdin: bool,
 
         /// Read body from file
-        #[arg(long)]
+        #[arg(long, conflicts_with = "template")]
         file: Option<String>,
 
         /// Treat body as Markdown and convert to HTML
-        #[arg(short, long)]
+        #[arg(short, long, conflicts_with = "template")]
         markdown: bool,
 
         /// Send raw HTML without escaping
-        #[arg(long)]
+        #[arg(long, conflicts_with = "template")]
         html: bool,
+
+        /// File to attach (repeatable)
+        #[arg(short = 'a', long = "attach", value_name = "PATH")]
+        attach: Vec<String>,
+
+        /// Image to embed, referenced in the body as cid:<file name> (repeatable; needs --markdown or --html)
+        #[arg(long, value_name = "PATH")]
+        inline: Vec<String>,
+
+        /// With --mailbox, send on behalf of the mailbox instead of as the mailbox
+        #[arg(long)]
+        on_behalf: bool,
+
+        #[command(flatten)]
+        template: TemplateArgs,
+
+        #[command(flatten)]
+        signature: SignatureArgs,
     },
 
     /// Search emails
@@ -86,36 +196,55 @@ pub enum MailSubcommand {
 
     /// Create a draft email
     Draft {
-        /// Recipient email address(es), comma-separated
+        /// Recipients (comma-separated names or emails)
         #[arg(short, long)]
         to: String,
 
-        /// Email subject
+        /// Email subject (defaults to the template's Subject: line)
         #[arg(short, long)]
-        subject: String,
+        subject: Option<String>,
 
         /// Email body (omit to read from stdin)
+        #[arg(conflicts_with = "template")]
         body: Option<String>,
 
-        /// CC recipients, comma-separated
+        /// CC recipients (comma-separated names or emails)
         #[arg(short, long)]
         cc: Option<String>,
 
         /// Read body from stdin
-        #[arg(long)]
+        #[arg(long, conflicts_with = "template")]
         stdin: bool,
 
         /// Read body from file
-        #[arg(long)]
+        #[arg(long, conflicts_with = "template")]
         file: Option<String>,
 
         /// Treat body as Markdown and convert to HTML
-        #[arg(short, long)]
+        #[arg(short, long, conflicts_with = "template")]
         markdown: bool,
 
         /// Send raw HTML without escaping
-        #[arg(long)]
+        #[arg(long, conflicts_with = "template")]
         html: bool,
+
+        /// File to attach (repeatable)
+        #[arg(short = 'a', long = "attach", value_name = "PATH")]
+        attach: Vec<String>,
+
+        /// Image to embed, referenced in the body as cid:<file name> (repeatable; needs --markdown or --html)
+        #[arg(long, value_name = "PATH")]
+        inline: Vec<String>,
+
+        /// With --mailbox, send on behalf of the mailbox instead of as the mailbox
+        #[arg(long)]
+        on_behalf: bool,
+
+        #[command(flatten)]
+        template: TemplateArgs,
+
+        #[command(flatten)]
+        signature: SignatureArgs,
     },
 
     /// Reply to an email
@@ -124,19 +253,34 @@ pub enum MailSubcommand {
         message_id: String,
 
         /// Reply body
-        body: String,
+        #[arg(required_unless_present = "template", conflicts_with = "template")]
+        body: Option<String>,
 
         /// Reply to all recipients
         #[arg(long)]
         all: bool,
 
-        /// CC recipients, comma-separated
+        /// CC recipients (comma-separated names or emails)
         #[arg(short, long)]
         cc: Option<String>,
 
-        /// BCC recipients, comma-separated
+        /// BCC recipients (comma-separated names or emails)
         #[arg(short, long)]
         bcc: Option<String>,
+
+        /// File to attach (repeatable)
+        #[arg(short = 'a', long = "attach", value_name = "PATH")]
+        attach: Vec<String>,
+
+        /// With --mailbox, send on behalf of the mailbox instead of as the mailbox
+        #[arg(long)]
+        on_behalf: bool,
+
+        #[command(flatten)]
+        template: TemplateArgs,
+
+        #[command(flatten)]
+        signature: SignatureArgs,
     },
 
     /// Forward an email
@@ -144,35 +288,46 @@ pub enum MailSubcommand {
         /// Message ID to forward
         message_id: String,
 
-        /// Recipient email address(es), comma-separated
+        /// Recipients (comma-separated names or emails)
         #[arg(short, long)]
         to: String,
 
         /// Optional comment to include
         #[arg(short, long)]
         comment: Option<String>,
+
+        /// File to attach (repeatable)
+        #[arg(short = 'a', long = "attach", value_name = "PATH")]
+        attach: Vec<String>,
+
+        /// With --mailbox, send on behalf of the mailbox instead of as the mailbox
+        #[arg(long)]
+        on_behalf: bool,
+
+        #[command(flatten)]
+        signature: SignatureArgs,
     },
 
     /// Delete an email
     Delete {
-        /// Message ID to delete
-        message_id: String,
+        #[command(flatten)]
+        selection: Selection,
     },
 
     /// Move an email to a folder
     Move {
-        /// Message ID to move
-        message_id: String,
+        #[command(flatten)]
+        selection: Selection,
 
-        /// Destination folder (ID or well-known name: archive, deleteditems, drafts, inbox, junkemail, sentitems)
+        /// Destination folder (well-known name: archive, deleteditems, drafts, inbox, junkemail, sentitems; a path like Projects/Alpha; or an ID)
         #[arg(short, long)]
         to: String,
     },
 
     /// Mark email as read or unread
     Mark {
-        /// Message ID
-        message_id: String,
+        #[command(flatten)]
+        selection: Selection,
 
         /// Mark as read
         #[arg(long, conflicts_with = "unread")]
@@ -183,6 +338,38 @@ pub enum MailSubcommand {
         unread: bool,
     },
 
+    /// Flag an email for follow-up
+    Flag {
+        #[command(flatten)]
+        selection: Selection,
+
+        /// Due date (e.g. friday, 2026-10-30, +3d)
+        #[arg(long, conflicts_with_all = ["complete", "clear"])]
+        due: Option<String>,
+
+        /// Mark the flag as complete
+        #[arg(long, conflicts_with = "clear")]
+        complete: bool,
+
+        /// Remove the flag
+        #[arg(long)]
+        clear: bool,
+    },
+
+    /// Add or remove categories on an email
+    Categorize {
+        #[command(flatten)]
+        selection: Selection,
+
+        /// Category to add (repeatable)
+        #[arg(long, required_unless_present = "remove")]
+        add: Vec<String>,
+
+        /// Category to remove (repeatable)
+        #[arg(long)]
+        remove: Vec<String>,
+    },
+
     /// List attachments of an email
     Attachments {
         /// Message ID
@@ -201,6 +388,227 @@ pub enum MailSubcommand {
         #[arg(short, long)]
         output: Option<String>,
     },
+
+    /// Inbox rules
+    Rules {
+        #[command(subcommand)]
+        command: RulesSubcommand,
+    },
+
+    /// Mailbox settings
+    Settings {
+        #[command(subcommand)]
+        command: SettingsSubcommand,
+    },
+
+    /// Automatic replies (out of office)
+    Oof {
+        #[command(subcommand)]
+        command: OofSubcommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum FoldersSubcommand {
+    /// Create a folder; parents in the path must exist
+    Create {
+        /// Folder path, e.g. Projects/Alpha or Inbox/Receipts
+        path: String,
+    },
+
+    /// Rename a folder
+    Rename {
+        /// Folder path or ID
+        folder: String,
+
+        /// New display name
+        name: String,
+    },
+
+    /// Delete a folder and move its contents to Deleted Items
+    Delete {
+        /// Folder path or ID
+        folder: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SettingsSubcommand {
+    /// Show time zone, working hours and automatic replies
+    Show,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum OofSubcommand {
+    /// Turn on automatic replies, always or between --start and --end
+    Set {
+        /// Start of the absence (YYYY-MM-DD, next mon, ...)
+        #[arg(short, long, requires = "end")]
+        start: Option<String>,
+
+        /// End of the absence, inclusive (YYYY-MM-DD, fri, ...)
+        #[arg(short, long, requires = "start")]
+        end: Option<String>,
+
+        /// Reply sent to people in your organization
+        #[arg(short, long)]
+        internal: String,
+
+        /// Reply sent to external senders (defaults to the internal reply)
+        #[arg(short = 'x', long)]
+        external: Option<String>,
+
+        /// External senders who get a reply
+        #[arg(short, long, value_enum, default_value = "all")]
+        audience: OofAudience,
+    },
+
+    /// Turn off automatic replies
+    Off,
+}
+
+/// Messages a command acts on: one ID, IDs from stdin, or a filter query
+#[derive(Args, Debug)]
+pub struct Selection {
+    /// Message ID (or use --stdin / --filter for several)
+    #[arg(required_unless_present_any = ["stdin", "filter"])]
+    message_id: Option<String>,
+
+    /// Read message IDs from stdin, one per line
+    #[arg(long, conflicts_with_all = ["message_id", "filter"])]
+    stdin: bool,
+
+    /// Select messages by query, e.g. 'from:noreply@ci.example.com is:unread until:-30d'
+    #[arg(long, conflicts_with = "message_id")]
+    filter: Option<String>,
+
+    /// Folder searched by --filter, by name, path or ID (default: all folders)
+    #[arg(long, requires = "filter")]
+    folder: Option<String>,
+
+    /// Maximum number of messages --filter selects
+    #[arg(long, default_value = "100")]
+    max: usize,
+
+    /// Show the messages that would change, without changing them
+    #[arg(long)]
+    dry_run: bool,
+}
+
+/// Filters for `mail list`
+#[derive(Args, Debug)]
+pub struct FilterArgs {
+    /// Only unread messages
+    #[arg(long)]
+    unread: bool,
+
+    /// Only messages from this sender address
+    #[arg(long, value_name = "ADDRESS")]
+    from: Option<String>,
+
+    /// Received on or after (e.g. 2026-10-01, yesterday, -3d)
+    #[arg(long)]
+    since: Option<String>,
+
+    /// Received up to and including (e.g. 2026-10-07, today)
+    #[arg(long)]
+    until: Option<String>,
+
+    /// Only flagged messages
+    #[arg(long)]
+    flagged: bool,
+
+    /// Only messages with attachments
+    #[arg(long)]
+    has_attachments: bool,
+
+    /// Only messages of this importance
+    #[arg(long, value_enum)]
+    importance: Option<Importance>,
+
+    /// Only messages in this category
+    #[arg(long)]
+    category: Option<String>,
+}
+
+impl FilterArgs {
+    fn to_filter(&self, config: &Config) -> Result<MailFilter> {
+        let tz = resolve_tz(None, config)?;
+        let now = now_in(tz);
+        let since = match &self.since {
+            Some(since) => Some(to_graph_utc(&parse_when(since, now)?.start(tz)?)),
+            None => None,
+        };
+        let until = match &self.until {
+            Some(until) => Some(to_graph_utc(&parse_when(until, now)?.end(tz)?)),
+            None => None,
+        };
+
+        Ok(MailFilter {
+            unread: self.unread,
+            from: self.from.clone(),
+            since,
+            until,
+            flagged: self.flagged,
+            has_attachments: self.has_attachments,
+            importance: self.importance.map(|i| i.as_graph().to_string()),
+            category: self.category.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Importance {
+    Low,
+    Normal,
+    High,
+}
+
+impl Importance {
+    pub fn as_graph(self) -> &'static str {
+        match self {
+            Importance::Low => "low",
+            Importance::Normal => "normal",
+            Importance::High => "high",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum OofAudience {
+    /// No external replies
+    None,
+    /// Only senders in your contacts
+    Contacts,
+    /// All external senders
+    All,
+}
+
+/// Signature choice for outgoing mail
+#[derive(Args, Debug)]
+pub struct SignatureArgs {
+    /// Don't append the configured signature
+    #[arg(long)]
+    no_signature: bool,
+
+    /// Signature profile to append instead of the mailbox's default
+    #[arg(long, value_name = "PROFILE", conflicts_with = "no_signature")]
+    signature: Option<String>,
+}
+
+impl SignatureArgs {
+    /// HTML of the signature for mail from `mailbox`, if one applies
+    fn html(&self, config: &Config, mailbox: Option<&str>) -> Result<Option<String>> {
+        if self.no_signature {
+            return Ok(None);
+        }
+        let signature = config.signatures.find(mailbox, self.signature.as_deref())?;
+        Ok(signature.and_then(|s| {
+            s.html
+                .clone()
+                .or_else(|| s.markdown.as_deref().map(markdown_to_html))
+        }))
+    }
 }
 
 #[derive(Debug, Serialize, Tabled)]
@@ -215,6 +623,61 @@ struct FolderRow {
     total: i32,
 }
 
+#[derive(Debug, Serialize, Tabled)]
+struct FolderTreeRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "Folder")]
+    name: String,
+    #[tabled(rename = "Unread")]
+    unread: i32,
+    #[tabled(rename = "Total")]
+    total: i32,
+    #[tabled(skip)]
+    path: String,
+}
+
+#[derive(Debug, Serialize, Tabled)]
+struct ThreadRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "From")]
+    from: String,
+    #[tabled(rename = "Subject")]
+    subject: String,
+    #[tabled(rename = "Msgs")]
+    messages: usize,
+    #[tabled(rename = "Unread")]
+    unread: String,
+    #[tabled(rename = "Latest")]
+    date: String,
+}
+
+/// A conversation in the listed folder, for JSON output
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ThreadSummary {
+    conversation_id: String,
+    subject: String,
+    message_count: usize,
+    unread_count: usize,
+    latest_message_id: String,
+    latest_received_date_time: Option<String>,
+    message_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Tabled)]
+struct SyncRow {
+    #[tabled(rename = "Change")]
+    change: String,
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "From")]
+    from: String,
+    #[tabled(rename = "Subject")]
+    subject: String,
+}
+
 #[derive(Debug, Serialize, Tabled)]
 struct MailRow {
     #[tabled(rename = "ID")]
@@ -230,10 +693,55 @@ struct MailRow {
 }
 
 pub async fn execute(cmd: MailCommand, config: &Config, format: OutputFormat) -> Result<()> {
+    let mailbox = cmd.mailbox.as_deref();
     match cmd.command {
-        MailSubcommand::Folders => folders(config, format).await,
-        MailSubcommand::List { folder, limit } => list(config, folder, limit, format).await,
-        MailSubcommand::Read { message_id } => read(config, &message_id, format).await,
+        MailSubcommand::Folders { tree, command } => match command {
+            None if tree => folder_tree(config, mailbox, format).await,
+            None => folders(config, mailbox, format).await,
+            Some(FoldersSubcommand::Create { path }) => {
+                folder_create(config, mailbox, &path, format).await
+            }
+            Some(FoldersSubcommand::Rename { folder, name }) => {
+                folder_rename(config, mailbox, &folder, &name).await
+            }
+            Some(FoldersSubcommand::Delete { folder }) => {
+                folder_delete(config, mailbox, &folder).await
+            }
+        },
+        MailSubcommand::List {
+            folder,
+            limit,
+            threads,
+            filter,
+        } => {
+            let filter = filter.to_filter(config)?;
+            if threads {
+                list_threads(config, mailbox, folder, &filter, limit, format).await
+            } else {
+                list(config, mailbox, folder, &filter, limit, format).await
+            }
+        }
+        MailSubcommand::Read { message_id, raw } => {
+            if raw {
+                read_raw(config, mailbox, &message_id).await
+            } else {
+                read(config, mailbox, &message_id, format).await
+            }
+        }
+        MailSubcommand::Export {
+            folder,
+            store,
+            output,
+        } => export(config, mailbox, &folder, store, &output).await,
+        MailSubcommand::Sync {
+            folder,
+            name,
+            reset,
+        } => sync(config, mailbox, &folder, &name, reset, format).await,
+        MailSubcommand::Import { files, folder } => import(config, mailbox, &files, &folder).await,
+        MailSubcommand::Thread { message_id, full } => {
+            thread(config, mailbox, &message_id, full, format).await
+        }
         MailSubcommand::Send {
             to,
             subject,
@@ -243,8 +751,21 @@ pub async fn execute(cmd: MailCommand, config: &Config, format: OutputFormat) ->
             file,
             markdown,
             html,
-        } => send(config, &to, &subject, body, cc, stdin, file, markdown, html).await,
-        MailSubcommand::Search { query, limit } => search(config, &query, limit, format).await,
+            attach,
+            inline,
+            on_behalf,
+            template,
+            signature,
+        } => {
+            send(
+                config, mailbox, &to, subject, body, cc, stdin, file, markdown, html, &attach,
+                &inline, on_behalf, &template, &signature,
+            )
+            .await
+        }
+        MailSubcommand::Search { query, limit } => {
+            search(config, mailbox, &query, limit, format).await
+        }
         MailSubcommand::Draft {
             to,
             subject,
@@ -254,9 +775,15 @@ pub async fn execute(cmd: MailCommand, config: &Config, format: OutputFormat) ->
             file,
             markdown,
             html,
+            attach,
+            inline,
+            on_behalf,
+            template,
+            signature,
         } => {
             draft(
-                config, &to, &subject, body, cc, stdin, file, markdown, html, format,
+                config, mailbox, &to, subject, body, cc, stdin, file, markdown, html, &attach,
+                &inline, on_behalf, &template, &signature, format,
             )
             .await
         }
@@ -266,32 +793,132 @@ pub async fn execute(cmd: MailCommand, config: &Config, format: OutputFormat) ->
             all,
             cc,
             bcc,
-        } => reply(config, &message_id, &body, all, cc, bcc).await,
+            attach,
+            on_behalf,
+            template,
+            signature,
+        } => {
+            reply(
+                config,
+                mailbox,
+                &message_id,
+                body,
+                all,
+                cc,
+                bcc,
+                &attach,
+                on_behalf,
+                &template,
+                &signature,
+            )
+            .await
+        }
         MailSubcommand::Forward {
             message_id,
             to,
             comment,
-        } => forward(config, &message_id, &to, comment).await,
-        MailSubcommand::Delete { message_id } => delete(config, &message_id).await,
-        MailSubcommand::Move { message_id, to } => move_mail(config, &message_id, &to).await,
+            attach,
+            on_behalf,
+            signature,
+        } => {
+            forward(
+                config,
+                mailbox,
+                &message_id,
+                &to,
+                comment,
+                &attach,
+                on_behalf,
+                &signature,
+            )
+            .await
+        }
+        MailSubcommand::Delete { selection } => match selection.single() {
+            Some(id) => delete(config, mailbox, id).await,
+            None => bulk(config, mailbox, &selection, BulkAction::Delete, format).await,
+        },
+        MailSubcommand::Move { selection, to } => match selection.single() {
+            Some(id) => move_mail(config, mailbox, id, &to).await,
+            None => {
+                let action = BulkAction::Move {
+                    id: to.clone(),
+                    folder: to,
+                };
+                bulk(config, mailbox, &selection, action, format).await
+            }
+        },
         MailSubcommand::Mark {
-            message_id,
+            selection,
             read,
             unread,
-        } => mark(config, &message_id, read, unread).await,
+        } => match selection.single() {
+            Some(id) => mark(config, mailbox, id, read, unread).await,
+            None if !read && !unread => {
+                print_error("Please specify --read or --unread");
+                Ok(())
+            }
+            None => bulk(config, mailbox, &selection, BulkAction::Mark(read), format).await,
+        },
+        MailSubcommand::Flag {
+            selection,
+            due,
+            complete,
+            clear,
+        } => {
+            let flag = if clear {
+                FlagChange::Clear
+            } else if complete {
+                FlagChange::Complete
+            } else {
+                let tz = resolve_tz(None, config)?;
+                let due = due
+                    .map(|due| parse_when(&due, now_in(tz))?.start(tz))
+                    .transpose()?;
+                FlagChange::Flag(due.map(|d| d.with_timezone(&Utc)))
+            };
+            bulk(config, mailbox, &selection, BulkAction::Flag(flag), format).await
+        }
+        MailSubcommand::Categorize {
+            selection,
+            add,
+            remove,
+        } => {
+            bulk(
+                config,
+                mailbox,
+                &selection,
+                BulkAction::Categorize { add, remove },
+                format,
+            )
+            .await
+        }
         MailSubcommand::Attachments { message_id } => {
-            attachments(config, &message_id, format).await
+            attachments(config, mailbox, &message_id, format).await
         }
         MailSubcommand::Download {
             message_id,
             attachment_id,
             output,
-        } => download(config, &message_id, &attachment_id, output).await,
+        } => download(config, mailbox, &message_id, &attachment_id, output).await,
+        MailSubcommand::Rules { command } => rules::execute(command, config, mailbox, format).await,
+        MailSubcommand::Settings { command } => match command {
+            SettingsSubcommand::Show => settings_show(config, mailbox, format).await,
+        },
+        MailSubcommand::Oof { command } => match command {
+            OofSubcommand::Set {
+                start,
+                end,
+                internal,
+                external,
+                audience,
+            } => oof_set(config, mailbox, start, end, &internal, external, audience).await,
+            OofSubcommand::Off => oof_off(config, mailbox).await,
+        },
     }
 }
 
-async fn folders(config: &Config, format: OutputFormat) -> Result<()> {
-    let client = TeamsClient::new(config)?;
+async fn folders(config: &Config, mailbox: Option<&str>, format: OutputFormat) -> Result<()> {
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
     let folders = client.get_mail_folders().await?;
 
     let rows: Vec<FolderRow> = folders
@@ -309,14 +936,162 @@ async fn folders(config: &Config, format: OutputFormat) -> Result<()> {
     Ok(())
 }
 
+async fn folder_tree(config: &Config, mailbox: Option<&str>, format: OutputFormat) -> Result<()> {
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
+    let rows: Vec<FolderTreeRow> = walk_folders(&client)
+        .await?
+        .into_iter()
+        .map(|(f, depth, path)| FolderTreeRow {
+            id: f.id,
+            name: format!("{}{}", "  ".repeat(depth), f.display_name),
+            unread: f.unread_item_count.unwrap_or(0),
+            total: f.total_item_count.unwrap_or(0),
+            path,
+        })
+        .collect();
+
+    print_output(&rows, format);
+    Ok(())
+}
+
+/// Every folder in depth-first order, with its depth and slash-separated path
+pub(crate) async fn walk_folders(client: &TeamsClient) -> Result<Vec<(MailFolder, usize, String)>> {
+    let top = client.get_mail_folders().await?;
+    let mut stack: Vec<(MailFolder, usize, String)> = top
+        .value
+        .into_iter()
+        .rev()
+        .map(|f| {
+            let path = f.display_name.clone();
+            (f, 0, path)
+        })
+        .collect();
+
+    let mut folders = Vec::new();
+    while let Some((folder, depth, path)) = stack.pop() {
+        if folder.child_folder_count.unwrap_or(0) > 0 {
+            let children = client.get_child_mail_folders(&folder.id).await?;
+            for child in children.value.into_iter().rev() {
+                let child_path = format!("{}/{}", path, child.display_name);
+                stack.push((child, depth + 1, child_path));
+            }
+        }
+        folders.push((folder, depth, path));
+    }
+    Ok(folders)
+}
+
+/// Resolve a folder argument to something Graph accepts in a URL: a well-known
+/// name, a slash-separated path of display names, or (failing both) an ID
+pub(super) async fn resolve_folder(client: &TeamsClient, folder: &str) -> Result<String> {
+    let segments = folder_segments(folder);
+    let Some((first, rest)) = segments.split_first() else {
+        bail!("Empty folder path");
+    };
+
+    let mut current = match well_known_folder(first) {
+        Some(id) => id.to_string(),
+        None => {
+            let top = client.get_mail_folders().await?;
+            match find_folder(&top.value, first) {
+                Some(id) => id,
+                None if rest.is_empty() => return Ok(folder.to_string()),
+                None => bail!("No top-level mail folder named '{}'", first),
+            }
+        }
+    };
+    for (i, segment) in rest.iter().enumerate() {
+        let children = client.get_child_mail_folders(&current).await?;
+        current = find_folder(&children.value, segment)
+            .ok_or_else(|| anyhow!("No folder '{}' in '{}'", segment, segments[..=i].join("/")))?;
+    }
+    Ok(current)
+}
+
+/// Resolve a folder argument like [`resolve_folder`], then look the folder up so
+/// that well-known names become real IDs and unknown names are an error
+pub(super) async fn resolve_folder_id(client: &TeamsClient, folder: &str) -> Result<String> {
+    let id = resolve_folder(client, folder).await?;
+    let found = client
+        .get_mail_folder(&id)
+        .await
+        .with_context(|| format!("No mail folder '{}'", folder))?;
+    Ok(found.id)
+}
+
+/// Display names along a folder path, ignoring blanks around and between slashes
+fn folder_segments(folder: &str) -> Vec<&str> {
+    folder
+        .split('/')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn find_folder(folders: &[MailFolder], name: &str) -> Option<String> {
+    folders
+        .iter()
+        .find(|f| f.display_name.eq_ignore_ascii_case(name))
+        .map(|f| f.id.clone())
+}
+
+async fn folder_create(
+    config: &Config,
+    mailbox: Option<&str>,
+    path: &str,
+    format: OutputFormat,
+) -> Result<()> {
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
+    let (parent, name) = match path.trim_matches('/').rsplit_once('/') {
+        Some((parent, name)) => (Some(resolve_folder(&client, parent).await?), name),
+        None => (None, path.trim_matches('/')),
+    };
+    let folder = client.create_mail_folder(parent.as_deref(), name).await?;
+
+    match format {
+        OutputFormat::Json => print_single(&folder, format),
+        _ => print_success(&format!("Folder '{}' created with ID: {}", path, folder.id)),
+    }
+    Ok(())
+}
+
+async fn folder_rename(
+    config: &Config,
+    mailbox: Option<&str>,
+    folder: &str,
+    name: &str,
+) -> Result<()> {
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
+    let id = resolve_folder(&client, folder).await?;
+    client.rename_mail_folder(&id, name).await?;
+    print_success(&format!("Folder '{}' renamed to '{}'", folder, name));
+    Ok(())
+}
+
+async fn folder_delete(config: &Config, mailbox: Option<&str>, folder: &str) -> Result<()> {
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
+    let id = resolve_folder(&client, folder).await?;
+    client.delete_mail_folder(&id).await?;
+    print_success(&format!("Folder '{}' deleted", folder));
+    Ok(())
+}
+
 async fn list(
     config: &Config,
+    mailbox: Option<&str>,
     folder: Option<String>,
+    filter: &MailFilter,
     limit: usize,
     format: OutputFormat,
 ) -> Result<()> {
-    let client = TeamsClient::new(config)?;
-    let messages = client.get_mail_messages(folder.as_deref(), limit).await?;
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
+    let folder = match folder {
+        Some(folder) => Some(resolve_folder(&client, &folder).await?),
+        None => None,
+    };
+    let messages = client
+        .get_mail_messages(folder.as_deref(), filter, limit)
+        .await?;
 
     match format {
         OutputFormat::Json => {
@@ -325,27 +1100,7 @@ async fn list(
         }
         _ => {
             // For table/plain, show truncated data
-            let rows: Vec<MailRow> = messages
-                .value
-                .into_iter()
-                .map(|m| {
-                    let from = m
-                        .from
-                        .map(|r| r.email_address.name.unwrap_or(r.email_address.address))
-                        .unwrap_or_else(|| "Unknown".to_string());
-
-                    MailRow {
-                        id: truncate(&m.id.unwrap_or_default(), 12),
-                        from: truncate(&from, 25),
-                        subject: truncate(&m.subject.unwrap_or_default(), 40),
-                        date: m
-                            .received_date_time
-                            .map(|d| truncate(&d, 19))
-                            .unwrap_or_default(),
-                        is_read: if m.is_read == Some(true) { "Yes" } else { "No" }.to_string(),
-                    }
-                })
-                .collect();
+            let rows: Vec<MailRow> = messages.value.iter().map(mail_row).collect();
 
             print_output(&rows, format);
         }
@@ -353,8 +1108,376 @@ async fn list(
     Ok(())
 }
 
-async fn read(config: &Config, message_id: &str, format: OutputFormat) -> Result<()> {
-    let client = TeamsClient::new(config)?;
+async fn list_threads(
+    config: &Config,
+    mailbox: Option<&str>,
+    folder: Option<String>,
+    filter: &MailFilter,
+    limit: usize,
+    format: OutputFormat,
+) -> Result<()> {
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
+    let folder = match folder {
+        Some(folder) => Some(resolve_folder(&client, &folder).await?),
+        None => None,
+    };
+    let messages = client
+        .get_mail_messages(folder.as_deref(), filter, limit)
+        .await?;
+    let threads = group_by_conversation(messages.value);
+
+    if matches!(format, OutputFormat::Json) {
+        let summaries: Vec<ThreadSummary> = threads
+            .into_iter()
+            .map(|(conversation_id, messages)| {
+                let latest = &messages[0];
+                ThreadSummary {
+                    conversation_id,
+                    subject: latest.subject.clone().unwrap_or_default(),
+                    message_count: messages.len(),
+                    unread_count: messages.iter().filter(|m| m.is_read == Some(false)).count(),
+                    latest_message_id: latest.id.clone().unwrap_or_default(),
+                    latest_received_date_time: latest.received_date_time.clone(),
+                    message_ids: messages.iter().filter_map(|m| m.id.clone()).collect(),
+                }
+            })
+            .collect();
+        print_single(&summaries, format);
+        return Ok(());
+    }
+
+    let rows: Vec<ThreadRow> = threads
+        .into_iter()
+        .map(|(_, messages)| {
+            let latest = &messages[0];
+            let from = latest
+                .from
+                .as_ref()
+                .map(|r| {
+                    r.email_address
+                        .name
+                        .clone()
+                        .unwrap_or(r.email_address.address.clone())
+                })
+                .unwrap_or_else(|| "Unknown".to_string());
+            let unread = messages.iter().any(|m| m.is_read == Some(false));
+
+            ThreadRow {
+                id: truncate(latest.id.as_deref().unwrap_or_default(), 12),
+                from: truncate(&from, 25),
+                subject: truncate(latest.subject.as_deref().unwrap_or_default(), 40),
+                messages: messages.len(),
+                unread: if unread { "●" } else { "" }.to_string(),
+                date: latest
+                    .received_date_time
+                    .as_deref()
+                    .map(|d| truncate(d, 19))
+                    .unwrap_or_default(),
+            }
+        })
+        .collect();
+
+    print_output(&rows, format);
+    Ok(())
+}
+
+/// Group messages (newest first) by conversation, keeping the order in which
+/// each conversation first appears
+fn group_by_conversation(messages: Vec<MailMessage>) -> Vec<(String, Vec<MailMessage>)> {
+    let mut threads: Vec<(String, Vec<MailMessage>)> = Vec::new();
+    for message in messages {
+        let key = message
+            .conversation_id
+            .clone()
+            .or_else(|| message.id.clone())
+            .unwrap_or_default();
+        match threads.iter_mut().find(|(id, _)| *id == key) {
+            Some((_, group)) => group.push(message),
+            None => threads.push((key, vec![message])),
+        }
+    }
+    threads
+}
+
+async fn read_raw(config: &Config, mailbox: Option<&str>, message_id: &str) -> Result<()> {
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
+    let mime = client.get_mail_mime(message_id).await?;
+    io::stdout().write_all(&mime)?;
+    Ok(())
+}
+
+async fn export(
+    config: &Config,
+    mailbox: Option<&str>,
+    folder: &str,
+    store: StoreFormat,
+    output: &str,
+) -> Result<()> {
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
+    let folder_id = resolve_folder(&client, folder).await?;
+    let dir = Path::new(output);
+    std::fs::create_dir_all(dir)?;
+    let mut state = ExportState::load(dir, folder, store)?;
+
+    // The whole folder is listed, since messages can be filed into it with any receive time
+    let messages = client.get_mail_message_ids(&folder_id).await?;
+    let pending: Vec<(String, String, bool)> = messages
+        .into_iter()
+        .filter_map(|m| {
+            let received = m.received_date_time?;
+            let id = m.id?;
+            (!state.is_exported(&id)).then_some((id, received, m.is_read == Some(true)))
+        })
+        .collect();
+    if pending.is_empty() {
+        print_info(&format!(
+            "Nothing new to export ({} message(s) already in {})",
+            state.count, output
+        ));
+        return Ok(());
+    }
+
+    let mbox_name: String = folder
+        .rsplit('/')
+        .next()
+        .unwrap_or(folder)
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    for (i, (id, received, is_read)) in pending.iter().enumerate() {
+        let mime = client.get_mail_mime(id).await?;
+        let message = StoredMessage {
+            id,
+            received: DateTime::parse_from_rfc3339(received)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+            is_read: *is_read,
+            mime: &mime,
+        };
+        store_message(dir, store, &mbox_name, &message)?;
+        // Saved after every message so an interrupted run resumes where it stopped
+        state.record(id);
+        state.save(dir)?;
+        if (i + 1) % 50 == 0 {
+            print_info(&format!("Exported {}/{}", i + 1, pending.len()));
+        }
+    }
+
+    print_success(&format!(
+        "Exported {} new message(s) to {} ({} in total)",
+        pending.len(),
+        output,
+        state.count
+    ));
+    Ok(())
+}
+
+async fn sync(
+    config: &Config,
+    mailbox: Option<&str>,
+    folder: &str,
+    name: &str,
+    reset: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
+    let folder_id = resolve_folder(&client, folder).await?;
+    if reset {
+        mailsync::reset(name, mailbox, &folder_id)?;
+    }
+    let changes = mailsync::sync_folder(&client, name, &folder_id).await?;
+
+    if matches!(format, OutputFormat::Json) {
+        print_single(&changes, format);
+        return Ok(());
+    }
+    if changes.initial {
+        print_info(&format!(
+            "Tracking changes in {}; they are reported from the next sync",
+            folder
+        ));
+        return Ok(());
+    }
+
+    let change_row = |change: &str, m: &MailMessage| {
+        let row = mail_row(m);
+        SyncRow {
+            change: change.to_string(),
+            id: row.id,
+            from: row.from,
+            subject: row.subject,
+        }
+    };
+    let mut rows: Vec<SyncRow> = changes.new.iter().map(|m| change_row("new", m)).collect();
+    rows.extend(changes.read_changed.iter().map(|m| {
+        change_row(
+            if m.is_read == Some(true) {
+                "read"
+            } else {
+                "unread"
+            },
+            m,
+        )
+    }));
+    rows.extend(changes.updated.iter().map(|m| change_row("updated", m)));
+    rows.extend(changes.removed.iter().map(|id| SyncRow {
+        change: "removed".to_string(),
+        id: truncate(id, 12),
+        from: String::new(),
+        subject: String::new(),
+    }));
+    if rows.is_empty() {
+        print_info("No changes since the last sync");
+        return Ok(());
+    }
+    print_output(&rows, format);
+    Ok(())
+}
+
+async fn import(
+    config: &Config,
+    mailbox: Option<&str>,
+    files: &[String],
+    folder: &str,
+) -> Result<()> {
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
+    let folder_id = resolve_folder(&client, folder).await?;
+
+    for file in files {
+        let mime = if file == "-" {
+            let mut buffer = Vec::new();
+            io::stdin().read_to_end(&mut buffer)?;
+            buffer
+        } else {
+            std::fs::read(file).with_context(|| format!("Failed to read {}", file))?
+        };
+        let message = client.import_mail_mime(&folder_id, &mime).await?;
+        print_success(&format!(
+            "Imported {} ({})",
+            file,
+            message.subject.unwrap_or_default()
+        ));
+    }
+    Ok(())
+}
+
+async fn thread(
+    config: &Config,
+    mailbox: Option<&str>,
+    message_id: &str,
+    full: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
+    let message = client.get_mail_message(message_id).await?;
+    let messages = match message.conversation_id.as_deref() {
+        Some(conversation_id) => client.get_conversation_messages(conversation_id).await?,
+        None => vec![message],
+    };
+
+    if matches!(format, OutputFormat::Json) {
+        print_single(&messages, format);
+        return Ok(());
+    }
+
+    let tz = resolve_tz(None, config)?;
+    let unread = messages.iter().filter(|m| m.is_read == Some(false)).count();
+    let subject = messages
+        .first()
+        .and_then(|m| m.subject.clone())
+        .unwrap_or_default();
+    println!(
+        "{} ({} messages, {} unread)",
+        subject.bold(),
+        messages.len(),
+        unread
+    );
+
+    let total = messages.len();
+    for (i, message) in messages.iter().enumerate() {
+        let from = message
+            .from
+            .as_ref()
+            .map(|r| match &r.email_address.name {
+                Some(name) if !name.is_empty() => {
+                    format!("{} <{}>", name, r.email_address.address)
+                }
+                _ => r.email_address.address.clone(),
+            })
+            .unwrap_or_else(|| "Unknown".to_string());
+        let date = message
+            .sent_date_time
+            .as_deref()
+            .or(message.received_date_time.as_deref())
+            .map(|d| format_in_tz(d, "%a %d %b %Y %H:%M", tz))
+            .unwrap_or_default();
+        let marker = if message.is_read == Some(false) {
+            "● ".blue().to_string()
+        } else {
+            String::new()
+        };
+
+        println!();
+        println!(
+            "{}",
+            format!("── [{}/{}] ──────────────────────────────", i + 1, total).dimmed()
+        );
+        println!("{}{}  {}", marker, from.bold(), date.dimmed());
+        let to = recipient_list(&message.to_recipients);
+        if !to.is_empty() {
+            println!("To: {}", to);
+        }
+        let cc = recipient_list(&message.cc_recipients);
+        if !cc.is_empty() {
+            println!("Cc: {}", cc);
+        }
+        println!();
+
+        let full_text = message.body.as_ref().map(body_text).unwrap_or_default();
+        let unique_text = message.unique_body.as_ref().map(body_text);
+        match unique_text {
+            Some(unique) if !full && unique.len() < full_text.len() => {
+                println!("{}", unique);
+                println!("{}", "[quoted history hidden, use --full]".dimmed());
+            }
+            _ if !full_text.is_empty() => println!("{}", full_text),
+            _ => println!("{}", message.body_preview.clone().unwrap_or_default()),
+        }
+    }
+
+    Ok(())
+}
+
+fn recipient_list(recipients: &Option<Vec<Recipient>>) -> String {
+    recipients
+        .iter()
+        .flatten()
+        .map(|r| r.email_address.address.clone())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn body_text(body: &ItemBody) -> String {
+    if body.content_type.eq_ignore_ascii_case("text") {
+        body.content.trim().to_string()
+    } else {
+        strip_html(&body.content)
+    }
+}
+
+async fn read(
+    config: &Config,
+    mailbox: Option<&str>,
+    message_id: &str,
+    format: OutputFormat,
+) -> Result<()> {
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
     let message = client.get_mail_message(message_id).await?;
 
     match format {
@@ -397,8 +1520,8 @@ async fn read(config: &Config, message_id: &str, format: OutputFormat) -> Result
                 if body.content_type == "text" {
                     println!("{}", body.content);
                 } else {
-                    // Strip HTML for display
-                    println!("{}", strip_html(&body.content));
+                    // Convert HTML to text, keeping paragraphs
+                    println!("{}", html_to_text(&body.content));
                 }
             } else if let Some(preview) = message.body_preview {
                 println!("{}", preview);
@@ -412,17 +1535,35 @@ async fn read(config: &Config, message_id: &str, format: OutputFormat) -> Result
 #[allow(clippy::too_many_arguments)]
 async fn send(
     config: &Config,
+    mailbox: Option<&str>,
     to: &str,
-    subject: &str,
+    subject: Option<String>,
     body: Option<String>,
     cc: Option<String>,
     stdin: bool,
     file: Option<String>,
     markdown: bool,
     html: bool,
+    attach: &[String],
+    inline: &[String],
+    on_behalf: bool,
+    template: &TemplateArgs,
+    signature: &SignatureArgs,
 ) -> Result<()> {
+    let template = template.load()?;
+    let Some(subject) = subject.or_else(|| template.as_ref().and_then(|t| t.subject.clone()))
+    else {
+        print_error("No subject provided. Use --subject or a template with a Subject: line.");
+        return Ok(());
+    };
+    let (markdown, html) = template
+        .as_ref()
+        .map_or((markdown, html), Template::format_flags);
+
     // Get the body content
-    let content = if let Some(b) = body {
+    let content = if let Some(t) = template {
+        t.body
+    } else if let Some(b) = body {
         b
     } else if stdin {
         let mut buffer = String::new();
@@ -450,50 +1591,69 @@ async fn send(
         (content, "Text")
     };
 
-    let client = TeamsClient::new(config)?;
-
-    // Parse recipients
-    let to_list: Vec<&str> = to.split(',').map(|s| s.trim()).collect();
-    let cc_list: Option<Vec<String>> = cc
-        .as_ref()
-        .map(|c| c.split(',').map(|s| s.trim().to_string()).collect());
+    if !inline.is_empty() && content_type != "HTML" {
+        print_error("Inline images need an HTML body. Use --markdown or --html.");
+        return Ok(());
+    }
+    let (final_body, content_type) =
+        with_signature(final_body, content_type, signature.html(config, mailbox)?);
+    let files = read_attachments(attach, inline)?;
+
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
+    let sender = delegate_sender(&client, on_behalf).await?;
+
+    // Names resolve through people ranking, then the directory
+    let to_addresses = resolve_addresses(&client, to).await?;
+    let to_list: Vec<&str> = to_addresses.iter().map(|s| s.as_str()).collect();
+    let cc_list: Option<Vec<String>> = match &cc {
+        Some(c) => Some(resolve_addresses(&client, c).await?),
+        None => None,
+    };
     let cc_refs: Option<Vec<&str>> = cc_list
         .as_ref()
         .map(|v| v.iter().map(|s| s.as_str()).collect());
 
-    client
-        .send_mail(to_list, subject, &final_body, cc_refs, content_type)
-        .await?;
+    if files.is_empty() {
+        client
+            .send_mail(
+                to_list,
+                &subject,
+                &final_body,
+                cc_refs,
+                content_type,
+                sender.as_deref(),
+            )
+            .await?;
+    } else {
+        // Attachments can only be added to an existing message
+        let draft = client
+            .create_draft(
+                to_list,
+                &subject,
+                &final_body,
+                cc_refs,
+                content_type,
+                sender.as_deref(),
+            )
+            .await?;
+        send_with_attachments(&client, draft, &files).await?;
+    }
     print_success("Email sent successfully");
 
     Ok(())
 }
 
-async fn search(config: &Config, query: &str, limit: usize, format: OutputFormat) -> Result<()> {
-    let client = TeamsClient::new(config)?;
+async fn search(
+    config: &Config,
+    mailbox: Option<&str>,
+    query: &str,
+    limit: usize,
+    format: OutputFormat,
+) -> Result<()> {
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
     let messages = client.search_mail(query, limit).await?;
 
-    let rows: Vec<MailRow> = messages
-        .value
-        .into_iter()
-        .map(|m| {
-            let from = m
-                .from
-                .map(|r| r.email_address.name.unwrap_or(r.email_address.address))
-                .unwrap_or_else(|| "Unknown".to_string());
-
-            MailRow {
-                id: truncate(&m.id.unwrap_or_default(), 12),
-                from: truncate(&from, 25),
-                subject: truncate(&m.subject.unwrap_or_default(), 40),
-                date: m
-                    .received_date_time
-                    .map(|d| truncate(&d, 19))
-                    .unwrap_or_default(),
-                is_read: if m.is_read == Some(true) { "Yes" } else { "No" }.to_string(),
-            }
-        })
-        .collect();
+    let rows: Vec<MailRow> = messages.value.iter().map(mail_row).collect();
 
     print_output(&rows, format);
     Ok(())
@@ -502,18 +1662,36 @@ async fn search(config: &Config, query: &str, limit: usize, format: OutputFormat
 #[allow(clippy::too_many_arguments)]
 async fn draft(
     config: &Config,
+    mailbox: Option<&str>,
     to: &str,
-    subject: &str,
+    subject: Option<String>,
     body: Option<String>,
     cc: Option<String>,
     stdin: bool,
     file: Option<String>,
     markdown: bool,
     html: bool,
+    attach: &[String],
+    inline: &[String],
+    on_behalf: bool,
+    template: &TemplateArgs,
+    signature: &SignatureArgs,
     format: OutputFormat,
 ) -> Result<()> {
+    let template = template.load()?;
+    let Some(subject) = subject.or_else(|| template.as_ref().and_then(|t| t.subject.clone()))
+    else {
+        print_error("No subject provided. Use --subject or a template with a Subject: line.");
+        return Ok(());
+    };
+    let (markdown, html) = template
+        .as_ref()
+        .map_or((markdown, html), Template::format_flags);
+
     // Get the body content
-    let content = if let Some(b) = body {
+    let content = if let Some(t) = template {
+        t.body
+    } else if let Some(b) = body {
         b
     } else if stdin {
         let mut buffer = String::new();
@@ -541,31 +1719,50 @@ async fn draft(
         (content, "Text")
     };
 
-    let client = TeamsClient::new(config)?;
-
-    // Parse recipients
-    let to_list: Vec<&str> = to.split(',').map(|s| s.trim()).collect();
-    let cc_list: Option<Vec<String>> = cc
-        .as_ref()
-        .map(|c| c.split(',').map(|s| s.trim().to_string()).collect());
+    if !inline.is_empty() && content_type != "HTML" {
+        print_error("Inline images need an HTML body. Use --markdown or --html.");
+        return Ok(());
+    }
+    let (final_body, content_type) =
+        with_signature(final_body, content_type, signature.html(config, mailbox)?);
+    let files = read_attachments(attach, inline)?;
+
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
+    let sender = delegate_sender(&client, on_behalf).await?;
+
+    // Names resolve through people ranking, then the directory
+    let to_addresses = resolve_addresses(&client, to).await?;
+    let to_list: Vec<&str> = to_addresses.iter().map(|s| s.as_str()).collect();
+    let cc_list: Option<Vec<String>> = match &cc {
+        Some(c) => Some(resolve_addresses(&client, c).await?),
+        None => None,
+    };
     let cc_refs: Option<Vec<&str>> = cc_list
         .as_ref()
         .map(|v| v.iter().map(|s| s.as_str()).collect());
 
     let draft = client
-        .create_draft(to_list, subject, &final_body, cc_refs, content_type)
+        .create_draft(
+            to_list,
+            &subject,
+            &final_body,
+            cc_refs,
+            content_type,
+            sender.as_deref(),
+        )
         .await?;
+    let Some(id) = draft.id.clone() else {
+        bail!("Graph returned a draft without an id");
+    };
+    attach_files(&client, &id, &files).await?;
 
     match format {
         OutputFormat::Json => {
             print_single(&draft, format);
         }
         _ => {
-            print_success(&format!(
-                "Draft created with ID: {}",
-                draft.id.unwrap_or_default()
-            ));
-            println!("To: {}", to);
+            print_success(&format!("Draft created with ID: {}", id));
+            println!("To: {}", to_addresses.join(", "));
             println!("Subject: {}", subject);
             if let Some(link) = draft.web_link {
                 println!("Open in Outlook: {}", link);
@@ -576,35 +1773,82 @@ async fn draft(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn reply(
     config: &Config,
+    mailbox: Option<&str>,
     message_id: &str,
-    body: &str,
+    body: Option<String>,
     reply_all: bool,
     cc: Option<String>,
     bcc: Option<String>,
+    attach: &[String],
+    on_behalf: bool,
+    template: &TemplateArgs,
+    signature: &SignatureArgs,
 ) -> Result<()> {
-    let client = TeamsClient::new(config)?;
-
-    // Parse CC recipients
-    let cc_list: Option<Vec<String>> = cc
+    let files = read_attachments(attach, &[])?;
+    // A reply keeps its "RE:" subject, so only the template's body is used
+    let template = template.load()?;
+    let (markdown, html) = template
         .as_ref()
-        .map(|c| c.split(',').map(|s| s.trim().to_string()).collect());
+        .map_or((false, false), Template::format_flags);
+    let content = template.map(|t| t.body).or(body).unwrap_or_default();
+    let (content, content_type) = if html {
+        (content, "HTML")
+    } else if markdown {
+        (markdown_to_html(&content), "HTML")
+    } else {
+        (content, "Text")
+    };
+    let (body, content_type) =
+        with_signature(content, content_type, signature.html(config, mailbox)?);
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
+    let sender = delegate_sender(&client, on_behalf).await?;
+
+    // Names resolve through people ranking, then the directory
+    let cc_list: Option<Vec<String>> = match &cc {
+        Some(c) => Some(resolve_addresses(&client, c).await?),
+        None => None,
+    };
     let cc_refs: Option<Vec<&str>> = cc_list
         .as_ref()
         .map(|v| v.iter().map(|s| s.as_str()).collect());
 
-    // Parse BCC recipients
-    let bcc_list: Option<Vec<String>> = bcc
-        .as_ref()
-        .map(|b| b.split(',').map(|s| s.trim().to_string()).collect());
+    let bcc_list: Option<Vec<String>> = match &bcc {
+        Some(b) => Some(resolve_addresses(&client, b).await?),
+        None => None,
+    };
     let bcc_refs: Option<Vec<&str>> = bcc_list
         .as_ref()
         .map(|v| v.iter().map(|s| s.as_str()).collect());
 
-    client
-        .reply_mail(message_id, body, reply_all, cc_refs, bcc_refs)
-        .await?;
+    if files.is_empty() {
+        client
+            .reply_mail(
+                message_id,
+                &body,
+                content_type,
+                reply_all,
+                cc_refs,
+                bcc_refs,
+                sender.as_deref(),
+            )
+            .await?;
+    } else {
+        let draft = client
+            .create_reply_draft(
+                message_id,
+                &body,
+                content_type,
+                reply_all,
+                cc_refs,
+                bcc_refs,
+                sender.as_deref(),
+            )
+            .await?;
+        send_with_attachments(&client, draft, &files).await?;
+    }
 
     if reply_all {
         print_success("Reply sent to all recipients");
@@ -614,54 +1858,216 @@ async fn reply(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn forward(
     config: &Config,
+    mailbox: Option<&str>,
     message_id: &str,
     to: &str,
     comment: Option<String>,
+    attach: &[String],
+    on_behalf: bool,
+    signature: &SignatureArgs,
 ) -> Result<()> {
-    let client = TeamsClient::new(config)?;
-    let to_list: Vec<&str> = to.split(',').map(|s| s.trim()).collect();
-    client
-        .forward_mail(message_id, to_list, comment.as_deref())
-        .await?;
-    print_success(&format!("Email forwarded to {}", to));
+    let files = read_attachments(attach, &[])?;
+    // The comment is rendered as HTML above the forwarded message
+    let comment = match signature.html(config, mailbox)? {
+        Some(sig) => Some(with_signature(comment.unwrap_or_default(), "Text", Some(sig)).0),
+        None => comment,
+    };
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
+    let sender = delegate_sender(&client, on_behalf).await?;
+    let to_addresses = resolve_addresses(&client, to).await?;
+    let to_list: Vec<&str> = to_addresses.iter().map(|s| s.as_str()).collect();
+    if files.is_empty() {
+        client
+            .forward_mail(message_id, to_list, comment.as_deref(), sender.as_deref())
+            .await?;
+    } else {
+        let draft = client
+            .create_forward_draft(message_id, to_list, comment.as_deref(), sender.as_deref())
+            .await?;
+        send_with_attachments(&client, draft, &files).await?;
+    }
+    print_success(&format!("Email forwarded to {}", to_addresses.join(", ")));
+    Ok(())
+}
+
+/// The signed-in user's address, as the sender of mail sent on behalf of `--mailbox`
+async fn delegate_sender(client: &TeamsClient, on_behalf: bool) -> Result<Option<String>> {
+    if !on_behalf {
+        return Ok(None);
+    }
+    if client.mailbox().is_none() {
+        bail!("--on-behalf needs --mailbox");
+    }
+    let me = client.get_me().await?;
+    me.mail
+        .or(me.user_principal_name)
+        .map(Some)
+        .ok_or_else(|| anyhow!("Could not determine your email address"))
+}
+
+/// Append a signature to a body; a text body is turned into HTML to carry it
+fn with_signature(
+    body: String,
+    content_type: &'static str,
+    signature: Option<String>,
+) -> (String, &'static str) {
+    match signature {
+        None => (body, content_type),
+        Some(sig) if content_type == "HTML" => (format!("{}<br>{}", body, sig), "HTML"),
+        Some(sig) => (
+            format!(
+                "<div style=\"white-space: pre-wrap\">{}</div><br>{}",
+                html_escape(&body),
+                sig
+            ),
+            "HTML",
+        ),
+    }
+}
+
+/// A local file to attach to an outgoing message
+struct OutgoingFile {
+    name: String,
+    content_type: &'static str,
+    content: Vec<u8>,
+    inline: bool,
+}
+
+/// Read every file up front, so a bad path fails before anything is created
+fn read_attachments(attach: &[String], inline: &[String]) -> Result<Vec<OutgoingFile>> {
+    let paths = attach
+        .iter()
+        .map(|p| (p, false))
+        .chain(inline.iter().map(|p| (p, true)));
+    paths
+        .map(|(path, inline)| {
+            let content =
+                std::fs::read(path).with_context(|| format!("Failed to read {}", path))?;
+            let name = Path::new(path)
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.clone());
+            Ok(OutgoingFile {
+                content_type: content_type_for(&name),
+                name,
+                content,
+                inline,
+            })
+        })
+        .collect()
+}
+
+/// Guess a MIME type from the file extension
+fn content_type_for(name: &str) -> &'static str {
+    let ext = Path::new(name)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "webp" => "image/webp",
+        "pdf" => "application/pdf",
+        "txt" | "log" => "text/plain",
+        "md" => "text/markdown",
+        "csv" => "text/csv",
+        "html" | "htm" => "text/html",
+        "ics" => "text/calendar",
+        "json" => "application/json",
+        "zip" => "application/zip",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        _ => "application/octet-stream",
+    }
+}
+
+async fn attach_files(
+    client: &TeamsClient,
+    message_id: &str,
+    files: &[OutgoingFile],
+) -> Result<()> {
+    for file in files {
+        client
+            .add_mail_attachment(
+                message_id,
+                &file.name,
+                file.content_type,
+                &file.content,
+                file.inline,
+            )
+            .await?;
+    }
     Ok(())
 }
 
-async fn delete(config: &Config, message_id: &str) -> Result<()> {
-    let client = TeamsClient::new(config)?;
+/// Attach files to a freshly created draft and send it
+async fn send_with_attachments(
+    client: &TeamsClient,
+    draft: MailMessage,
+    files: &[OutgoingFile],
+) -> Result<()> {
+    let Some(id) = draft.id else {
+        bail!("Graph returned a draft without an id");
+    };
+    attach_files(client, &id, files)
+        .await
+        .with_context(|| format!("Message kept as draft {}", id))?;
+    client.send_draft(&id).await
+}
+
+async fn delete(config: &Config, mailbox: Option<&str>, message_id: &str) -> Result<()> {
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
     client.delete_mail(message_id).await?;
     print_success("Email deleted");
     Ok(())
 }
 
-async fn move_mail(config: &Config, message_id: &str, folder: &str) -> Result<()> {
-    let client = TeamsClient::new(config)?;
+/// Map well-known folder names and their aliases to Graph's well-known IDs
+fn well_known_folder(folder: &str) -> Option<&'static str> {
+    match folder.to_lowercase().as_str() {
+        "archive" => Some("archive"),
+        "deleteditems" | "deleted" | "trash" => Some("deleteditems"),
+        "drafts" => Some("drafts"),
+        "inbox" => Some("inbox"),
+        "junkemail" | "junk" | "spam" => Some("junkemail"),
+        "sentitems" | "sent" => Some("sentitems"),
+        _ => None,
+    }
+}
 
-    // Map well-known folder names to their IDs
-    let folder_id = match folder.to_lowercase().as_str() {
-        "archive" => "archive",
-        "deleteditems" | "deleted" | "trash" => "deleteditems",
-        "drafts" => "drafts",
-        "inbox" => "inbox",
-        "junkemail" | "junk" | "spam" => "junkemail",
-        "sentitems" | "sent" => "sentitems",
-        _ => folder, // Assume it's a folder ID
-    };
+async fn move_mail(
+    config: &Config,
+    mailbox: Option<&str>,
+    message_id: &str,
+    folder: &str,
+) -> Result<()> {
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
 
-    client.move_mail(message_id, folder_id).await?;
+    let folder_id = resolve_folder(&client, folder).await?;
+    client.move_mail(message_id, &folder_id).await?;
     print_success(&format!("Email moved to {}", folder));
     Ok(())
 }
 
-async fn mark(config: &Config, message_id: &str, read: bool, unread: bool) -> Result<()> {
+async fn mark(
+    config: &Config,
+    mailbox: Option<&str>,
+    message_id: &str,
+    read: bool,
+    unread: bool,
+) -> Result<()> {
     if !read && !unread {
         print_error("Please specify --read or --unread");
         return Ok(());
     }
 
-    let client = TeamsClient::new(config)?;
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
     let is_read = read; // If --read is set, mark as read; if --unread is set, read=false
     client.mark_mail(message_id, is_read).await?;
 
@@ -673,6 +2079,365 @@ async fn mark(config: &Config, message_id: &str, read: bool, unread: bool) -> Re
     Ok(())
 }
 
+impl Selection {
+    /// The message ID when exactly one message was named and nothing is previewed
+    fn single(&self) -> Option<&str> {
+        match &self.message_id {
+            Some(id) if !self.dry_run => Some(id),
+            _ => None,
+        }
+    }
+}
+
+/// New state of a follow-up flag
+enum FlagChange {
+    Flag(Option<DateTime<Utc>>),
+    Complete,
+    Clear,
+}
+
+/// An operation applied to every selected message
+enum BulkAction {
+    Mark(bool),
+    /// `folder` as given by the user, `id` as resolved for Graph
+    Move {
+        folder: String,
+        id: String,
+    },
+    Delete,
+    Flag(FlagChange),
+    Categorize {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+}
+
+impl BulkAction {
+    fn describe(&self) -> String {
+        match self {
+            BulkAction::Mark(true) => "mark as read".to_string(),
+            BulkAction::Mark(false) => "mark as unread".to_string(),
+            BulkAction::Move { folder, .. } => format!("move to {}", folder),
+            BulkAction::Delete => "delete".to_string(),
+            BulkAction::Flag(FlagChange::Flag(None)) => "flag".to_string(),
+            BulkAction::Flag(FlagChange::Flag(Some(due))) => {
+                format!("flag (due {})", due.format("%Y-%m-%d %H:%M UTC"))
+            }
+            BulkAction::Flag(FlagChange::Complete) => "complete the flag of".to_string(),
+            BulkAction::Flag(FlagChange::Clear) => "clear the flag of".to_string(),
+            BulkAction::Categorize { add, remove } => {
+                let mut parts = Vec::new();
+                if !add.is_empty() {
+                    parts.push(format!("add {}", add.join(", ")));
+                }
+                if !remove.is_empty() {
+                    parts.push(format!("remove {}", remove.join(", ")));
+                }
+                format!("categorize ({})", parts.join("; "))
+            }
+        }
+    }
+
+    /// The Graph request for one message, or `None` when it is already in the wanted state
+    fn request(
+        &self,
+        mailbox_path: &str,
+        batch_id: String,
+        message: &MailMessage,
+    ) -> Option<BatchRequest> {
+        let id = message.id.as_deref()?;
+        let path = format!("{}/messages/{}", mailbox_path, id);
+        let request = match self {
+            BulkAction::Mark(is_read) => {
+                BatchRequest::new(batch_id, "PATCH", path).json(json!({ "isRead": is_read }))
+            }
+            BulkAction::Move { id: folder, .. } => {
+                BatchRequest::new(batch_id, "POST", format!("{}/move", path))
+                    .json(json!({ "destinationId": folder }))
+            }
+            BulkAction::Delete => BatchRequest::new(batch_id, "DELETE", path),
+            BulkAction::Flag(change) => BatchRequest::new(batch_id, "PATCH", path)
+                .json(json!({ "flag": flag_body(change) })),
+            BulkAction::Categorize { add, remove } => {
+                let current = message.categories.clone().unwrap_or_default();
+                let updated = apply_categories(&current, add, remove);
+                if updated == current {
+                    return None;
+                }
+                BatchRequest::new(batch_id, "PATCH", path).json(json!({ "categories": updated }))
+            }
+        };
+        Some(request)
+    }
+}
+
+fn flag_body(change: &FlagChange) -> serde_json::Value {
+    let utc = |dt: DateTime<Utc>| DateTimeZone {
+        date_time: dt.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        time_zone: "UTC".to_string(),
+    };
+    match change {
+        FlagChange::Flag(None) => json!({ "flagStatus": "flagged" }),
+        // Graph wants a start date whenever a due date is set
+        FlagChange::Flag(Some(due)) => json!({
+            "flagStatus": "flagged",
+            "startDateTime": utc(Utc::now().min(*due)),
+            "dueDateTime": utc(*due),
+        }),
+        FlagChange::Complete => json!({ "flagStatus": "complete" }),
+        FlagChange::Clear => json!({ "flagStatus": "notFlagged" }),
+    }
+}
+
+/// Add and remove categories, keeping the existing order (case-insensitive names)
+fn apply_categories(current: &[String], add: &[String], remove: &[String]) -> Vec<String> {
+    let mut updated: Vec<String> = current
+        .iter()
+        .filter(|c| !remove.iter().any(|r| r.eq_ignore_ascii_case(c)))
+        .cloned()
+        .collect();
+    for category in add {
+        if !updated.iter().any(|c| c.eq_ignore_ascii_case(category)) {
+            updated.push(category.clone());
+        }
+    }
+    updated
+}
+
+/// Parse a `--filter` query such as `from:a@example.com is:unread since:-7d category:"Project X"`
+fn parse_filter_query(query: &str, tz: Tz, now: DateTime<Tz>) -> Result<MailFilter> {
+    let mut filter = MailFilter::default();
+    for token in split_query(query) {
+        let (key, value) = token
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Expected key:value in filter, got '{}'", token))?;
+        match (key.to_lowercase().as_str(), value.to_lowercase().as_str()) {
+            ("from", _) => filter.from = Some(value.to_string()),
+            ("since" | "after", _) => {
+                filter.since = Some(to_graph_utc(&parse_when(value, now)?.start(tz)?))
+            }
+            ("until" | "before", _) => {
+                filter.until = Some(to_graph_utc(&parse_when(value, now)?.end(tz)?))
+            }
+            ("is", "unread") => filter.unread = true,
+            ("is", "flagged") => filter.flagged = true,
+            ("has", "attachment" | "attachments") => filter.has_attachments = true,
+            ("importance", level @ ("low" | "normal" | "high")) => {
+                filter.importance = Some(level.to_string())
+            }
+            ("category", _) => filter.category = Some(value.to_string()),
+            _ => bail!(
+                "Unknown filter term '{}'. Use from:, since:, until:, is:unread, is:flagged, \
+                 has:attachments, importance: or category:",
+                token
+            ),
+        }
+    }
+    Ok(filter)
+}
+
+/// Split on whitespace, keeping double-quoted runs together (quotes removed)
+fn split_query(query: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in query.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+/// Resolve the selected messages, with the details previews and category edits need
+async fn select_messages(
+    client: &TeamsClient,
+    selection: &Selection,
+    config: &Config,
+) -> Result<Vec<MailMessage>> {
+    if let Some(query) = &selection.filter {
+        let tz = resolve_tz(None, config)?;
+        let filter = parse_filter_query(query, tz, now_in(tz))?;
+        if filter.to_odata().is_none() {
+            bail!("Filter '{}' would select every message", query);
+        }
+        let folder = match &selection.folder {
+            Some(folder) => Some(resolve_folder(client, folder).await?),
+            None => None,
+        };
+        let messages = client
+            .get_mail_messages(folder.as_deref(), &filter, selection.max)
+            .await?;
+        return Ok(messages.value);
+    }
+
+    let ids: Vec<String> = if selection.stdin {
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer)?;
+        buffer
+            .lines()
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty())
+            .collect()
+    } else {
+        selection.message_id.iter().cloned().collect()
+    };
+
+    let requests = ids
+        .iter()
+        .enumerate()
+        .map(|(i, id)| {
+            BatchRequest::new(
+                i.to_string(),
+                "GET",
+                format!(
+                    "{}/messages/{}?$select=id,subject,from,receivedDateTime,isRead,flag,categories",
+                    client.mailbox_path(),
+                    id
+                ),
+            )
+        })
+        .collect();
+    let responses = client.batch(requests).await?;
+
+    let mut messages = Vec::new();
+    for (id, response) in ids.iter().zip(responses) {
+        let message = response
+            .body
+            .clone()
+            .filter(|_| response.is_success())
+            .and_then(|body| serde_json::from_value::<MailMessage>(body).ok());
+        match message {
+            Some(message) => messages.push(message),
+            None => print_warning(&format!(
+                "Skipping {}: {}",
+                id,
+                response
+                    .error_message()
+                    .unwrap_or_else(|| format!("status {}", response.status))
+            )),
+        }
+    }
+    Ok(messages)
+}
+
+/// Apply an action to the selected messages through Graph `$batch`
+async fn bulk(
+    config: &Config,
+    mailbox: Option<&str>,
+    selection: &Selection,
+    action: BulkAction,
+    format: OutputFormat,
+) -> Result<()> {
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
+    let action = match action {
+        BulkAction::Move { folder, .. } => BulkAction::Move {
+            id: resolve_folder(&client, &folder).await?,
+            folder,
+        },
+        action => action,
+    };
+    let messages = select_messages(&client, selection, config).await?;
+    // A JSON dry run prints the selection alone (possibly `[]`), so it stays parseable
+    let json_preview = selection.dry_run && matches!(format, OutputFormat::Json);
+    if messages.is_empty() && !json_preview {
+        print_info("No messages selected");
+        return Ok(());
+    }
+
+    if selection.dry_run {
+        if !json_preview {
+            print_info(&format!(
+                "Would {} {} message(s):",
+                action.describe(),
+                messages.len()
+            ));
+        }
+        let rows: Vec<MailRow> = messages.iter().map(mail_row).collect();
+        print_output(&rows, format);
+        return Ok(());
+    }
+
+    let mailbox_path = client.mailbox_path();
+    let requests: Vec<BatchRequest> = messages
+        .iter()
+        .enumerate()
+        .filter_map(|(i, m)| action.request(&mailbox_path, i.to_string(), m))
+        .collect();
+    let unchanged = messages.len() - requests.len();
+    let responses = client.batch(requests).await?;
+
+    let mut failed = 0;
+    for response in &responses {
+        if response.is_success() {
+            continue;
+        }
+        failed += 1;
+        let subject = response
+            .id
+            .parse::<usize>()
+            .ok()
+            .and_then(|i| messages.get(i))
+            .and_then(|m| m.subject.clone())
+            .unwrap_or_default();
+        print_error(&format!(
+            "{}: {}",
+            truncate(&subject, 50),
+            response
+                .error_message()
+                .unwrap_or_else(|| format!("status {}", response.status))
+        ));
+    }
+
+    let done = responses.len() - failed;
+    print_success(&format!(
+        "Applied '{}' to {} message(s)",
+        action.describe(),
+        done
+    ));
+    if unchanged > 0 {
+        print_info(&format!("{} message(s) already up to date", unchanged));
+    }
+    if failed > 0 {
+        bail!("{} of {} message(s) failed", failed, responses.len());
+    }
+    Ok(())
+}
+
+fn mail_row(m: &MailMessage) -> MailRow {
+    let from = m
+        .from
+        .as_ref()
+        .map(|r| {
+            r.email_address
+                .name
+                .clone()
+                .unwrap_or(r.email_address.address.clone())
+        })
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    MailRow {
+        id: truncate(m.id.as_deref().unwrap_or_default(), 12),
+        from: truncate(&from, 25),
+        subject: truncate(m.subject.as_deref().unwrap_or_default(), 40),
+        date: m
+            .received_date_time
+            .as_deref()
+            .map(|d| truncate(d, 19))
+            .unwrap_or_default(),
+        is_read: if m.is_read == Some(true) { "Yes" } else { "No" }.to_string(),
+    }
+}
+
 #[derive(Debug, Serialize, Tabled)]
 struct AttachmentRow {
     #[tabled(rename = "ID")]
@@ -685,8 +2450,13 @@ struct AttachmentRow {
     size: String,
 }
 
-async fn attachments(config: &Config, message_id: &str, format: OutputFormat) -> Result<()> {
-    let client = TeamsClient::new(config)?;
+async fn attachments(
+    config: &Config,
+    mailbox: Option<&str>,
+    message_id: &str,
+    format: OutputFormat,
+) -> Result<()> {
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
     let attachments = client.get_mail_attachments(message_id).await?;
 
     match format {
@@ -699,18 +2469,34 @@ async fn attachments(config: &Config, message_id: &str, format: OutputFormat) ->
                 return Ok(());
             }
 
+            let mut has_invite = false;
             let rows: Vec<AttachmentRow> = attachments
                 .value
                 .into_iter()
-                .map(|a| AttachmentRow {
-                    id: truncate(&a.id.unwrap_or_default(), 20),
-                    name: a.name,
-                    content_type: a.content_type.unwrap_or_default(),
-                    size: format_size(a.size.unwrap_or(0)),
+                .map(|a| {
+                    let invite = is_ics_attachment(&a.name, a.content_type.as_deref());
+                    has_invite |= invite;
+                    let content_type = a.content_type.unwrap_or_default();
+                    AttachmentRow {
+                        id: truncate(&a.id.unwrap_or_default(), 20),
+                        name: a.name,
+                        content_type: if invite {
+                            format!("{} (calendar invite)", content_type)
+                        } else {
+                            content_type
+                        },
+                        size: format_size(a.size.unwrap_or(0)),
+                    }
                 })
                 .collect();
 
             print_output(&rows, format);
+            if has_invite {
+                print_info(&format!(
+                    "Add the invite to your calendar with: squads-cli calendar import --from-mail {}",
+                    message_id
+                ));
+            }
         }
     }
     Ok(())
@@ -731,11 +2517,12 @@ fn format_size(bytes: i64) -> String {
 
 async fn download(
     config: &Config,
+    mailbox: Option<&str>,
     message_id: &str,
     attachment_id: &str,
     output: Option<String>,
 ) -> Result<()> {
-    let client = TeamsClient::new(config)?;
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
     let (filename, content) = client
         .download_attachment(message_id, attachment_id)
         .await?;
@@ -750,3 +2537,293 @@ async fn download(
     ));
     Ok(())
 }
+
+async fn settings_show(config: &Config, mailbox: Option<&str>, format: OutputFormat) -> Result<()> {
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
+    let settings = client.get_mailbox_settings().await?;
+
+    if matches!(format, OutputFormat::Json) {
+        print_single(&settings, format);
+        return Ok(());
+    }
+
+    let tz = resolve_tz(None, config)?;
+    println!(
+        "Time zone: {}",
+        settings.time_zone.as_deref().unwrap_or("unknown")
+    );
+    if let Some(language) = &settings.language {
+        println!(
+            "Language: {}",
+            language
+                .display_name
+                .as_deref()
+                .or(language.locale.as_deref())
+                .unwrap_or_default()
+        );
+    }
+    if let (Some(date), Some(time)) = (&settings.date_format, &settings.time_format) {
+        println!("Date/time format: {} {}", date, time);
+    }
+    if let Some(hours) = &settings.working_hours {
+        let days: Vec<String> = hours
+            .days_of_week
+            .iter()
+            .map(|d| d.chars().take(3).collect())
+            .collect();
+        let trim = |t: &Option<String>| {
+            t.as_deref()
+                .map(|t| t.get(..5).unwrap_or(t).to_string())
+                .unwrap_or_default()
+        };
+        println!(
+            "Working hours: {} {}-{} ({})",
+            days.join(","),
+            trim(&hours.start_time),
+            trim(&hours.end_time),
+            hours
+                .time_zone
+                .as_ref()
+                .and_then(|z| z.name.as_deref())
+                .unwrap_or("unknown")
+        );
+    }
+
+    let Some(replies) = &settings.automatic_replies_setting else {
+        return Ok(());
+    };
+    println!("Automatic replies: {}", replies.status);
+    if replies.status == "scheduled" {
+        let when = |value: &Option<DateTimeZone>| match value {
+            Some(v) if v.time_zone == "UTC" => format_in_tz(&v.date_time, "%Y-%m-%d %H:%M", tz),
+            Some(v) => format!("{} ({})", v.date_time, v.time_zone),
+            None => String::new(),
+        };
+        println!("  From: {}", when(&replies.scheduled_start_date_time));
+        println!("  Until: {}", when(&replies.scheduled_end_date_time));
+    }
+    if replies.status != "disabled" {
+        if let Some(audience) = &replies.external_audience {
+            println!("  External audience: {}", audience);
+        }
+        if let Some(message) = &replies.internal_reply_message {
+            println!("  Internal reply: {}", strip_html(message).trim());
+        }
+        if let Some(message) = &replies.external_reply_message {
+            println!("  External reply: {}", strip_html(message).trim());
+        }
+    }
+    Ok(())
+}
+
+async fn oof_set(
+    config: &Config,
+    mailbox: Option<&str>,
+    start: Option<String>,
+    end: Option<String>,
+    internal: &str,
+    external: Option<String>,
+    audience: OofAudience,
+) -> Result<()> {
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
+    let tz = resolve_tz(None, config)?;
+
+    // Whole days run to the end of the given day
+    let schedule = match (start, end) {
+        (Some(start), Some(end)) => {
+            let now = now_in(tz);
+            let start = parse_when(&start, now)?.start(tz)?;
+            let end = parse_when(&end, now)?.end(tz)?;
+            if end <= start {
+                bail!("End must be after start");
+            }
+            Some((start, end))
+        }
+        _ => None,
+    };
+    let utc = |dt: &chrono::DateTime<chrono_tz::Tz>| DateTimeZone {
+        date_time: dt
+            .with_timezone(&chrono::Utc)
+            .format("%Y-%m-%dT%H:%M:%S")
+            .to_string(),
+        time_zone: "UTC".to_string(),
+    };
+    let to_html = |text: &str| html_escape(text).replace('\n', "<br>");
+
+    let replies = AutomaticRepliesSetting {
+        status: if schedule.is_some() {
+            "scheduled"
+        } else {
+            "alwaysEnabled"
+        }
+        .to_string(),
+        external_audience: Some(
+            match audience {
+                OofAudience::None => "none",
+                OofAudience::Contacts => "contactsOnly",
+                OofAudience::All => "all",
+            }
+            .to_string(),
+        ),
+        scheduled_start_date_time: schedule.as_ref().map(|(s, _)| utc(s)),
+        scheduled_end_date_time: schedule.as_ref().map(|(_, e)| utc(e)),
+        internal_reply_message: Some(to_html(internal)),
+        external_reply_message: Some(to_html(external.as_deref().unwrap_or(internal))),
+    };
+    client
+        .update_mailbox_settings(&MailboxSettings {
+            automatic_replies_setting: Some(replies),
+            ..Default::default()
+        })
+        .await?;
+
+    match schedule {
+        Some((start, end)) => print_success(&format!(
+            "Automatic replies scheduled from {} to {}",
+            start.format("%Y-%m-%d %H:%M"),
+            end.format("%Y-%m-%d %H:%M")
+        )),
+        None => print_success("Automatic replies turned on"),
+    }
+    Ok(())
+}
+
+async fn oof_off(config: &Config, mailbox: Option<&str>) -> Result<()> {
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
+    client
+        .update_mailbox_settings(&MailboxSettings {
+            automatic_replies_setting: Some(AutomaticRepliesSetting {
+                status: "disabled".to_string(),
+                external_audience: None,
+                scheduled_start_date_time: None,
+                scheduled_end_date_time: None,
+                internal_reply_message: None,
+                external_reply_message: None,
+            }),
+            ..Default::default()
+        })
+        .await?;
+    print_success("Automatic replies turned off");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_filter_query() {
+        let tz: Tz = "Europe/Paris".parse().unwrap();
+        let now = tz.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap();
+        let filter = parse_filter_query(
+            r#"from:noreply@ci.example.com is:unread until:2026-10-01 category:"Project X""#,
+            tz,
+            now,
+        )
+        .unwrap();
+        assert_eq!(filter.from.as_deref(), Some("noreply@ci.example.com"));
+        assert!(filter.unread);
+        assert_eq!(filter.until.as_deref(), Some("2026-10-01T22:00:00Z"));
+        assert_eq!(filter.category.as_deref(), Some("Project X"));
+        assert!(parse_filter_query("is:starred", tz, now).is_err());
+
+        let current = vec!["Red".to_string(), "Blue".to_string()];
+        assert_eq!(
+            apply_categories(&current, &["green".into(), "blue".into()], &["red".into()]),
+            vec!["Blue".to_string(), "green".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_folder_path_parts() {
+        assert_eq!(
+            folder_segments(" Projects / Alpha/ "),
+            vec!["Projects", "Alpha"]
+        );
+        assert!(folder_segments("/ /").is_empty());
+        assert_eq!(well_known_folder("Sent"), Some("sentitems"));
+        assert_eq!(well_known_folder("Projects"), None);
+
+        let folders: Vec<MailFolder> = serde_json::from_value(serde_json::json!([
+            {"id": "f1", "displayName": "Projects"},
+            {"id": "f2", "displayName": "Alpha"},
+        ]))
+        .unwrap();
+        assert_eq!(find_folder(&folders, "alpha").as_deref(), Some("f2"));
+        assert_eq!(find_folder(&folders, "Beta"), None);
+    }
+
+    #[test]
+    fn test_group_by_conversation() {
+        let mail = |id: &str, conversation: Option<&str>| -> MailMessage {
+            serde_json::from_value(serde_json::json!({
+                "id": id,
+                "conversationId": conversation,
+            }))
+            .unwrap()
+        };
+        let threads = group_by_conversation(vec![
+            mail("3", Some("b")),
+            mail("2", Some("a")),
+            mail("1", Some("b")),
+            mail("0", None),
+        ]);
+        let summary: Vec<(String, Vec<String>)> = threads
+            .into_iter()
+            .map(|(key, group)| (key, group.into_iter().filter_map(|m| m.id).collect()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("b".to_string(), vec!["3".to_string(), "1".to_string()]),
+                ("a".to_string(), vec!["2".to_string()]),
+                ("0".to_string(), vec!["0".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_attachments() {
+        let dir = std::env::temp_dir().join(format!("squads-attach-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let report = dir.join("report.PDF");
+        let logo = dir.join("logo.png");
+        std::fs::write(&report, b"%PDF").unwrap();
+        std::fs::write(&logo, b"png").unwrap();
+        let path = |p: &Path| p.to_string_lossy().into_owned();
+
+        let files = read_attachments(&[path(&report)], &[path(&logo)]).unwrap();
+        let summary: Vec<_> = files
+            .iter()
+            .map(|f| (f.name.as_str(), f.content_type, f.inline, f.content.len()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("report.PDF", "application/pdf", false, 4),
+                ("logo.png", "image/png", true, 3),
+            ]
+        );
+        let missing = path(&dir.join("missing.txt"));
+        assert!(read_attachments(&[missing], &[]).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_with_signature() {
+        let sig = Some("<b>Bob</b>".to_string());
+        assert_eq!(
+            with_signature("<p>Hi</p>".into(), "HTML", sig.clone()),
+            ("<p>Hi</p><br><b>Bob</b>".to_string(), "HTML")
+        );
+        let (body, content_type) = with_signature("a < b".into(), "Text", sig);
+        assert_eq!(content_type, "HTML");
+        assert!(body.starts_with("<div style=\"white-space: pre-wrap\">a &lt; b</div>"));
+        assert_eq!(
+            with_signature("Hi".into(), "Text", None),
+            ("Hi".to_string(), "Text")
+        );
+    }
+}
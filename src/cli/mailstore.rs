@@ -0,0 +1,235 @@
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// File recording what an export directory already holds
+const STATE_FILE: &str = ".squads-export.json";
+
+/// Local layout for exported mail
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum StoreFormat {
+    /// Maildir (cur/new/tmp), readable by mutt, notmuch, dovecot...
+    Maildir,
+    /// A single mboxrd file
+    Mbox,
+    /// One .eml file per message
+    Eml,
+}
+
+/// Incremental export state, kept in the export directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportState {
+    pub folder: String,
+    pub format: StoreFormat,
+    /// IDs of the exported messages. Keyed by ID rather than receive time, so a
+    /// message filed into the folder after an export is still picked up.
+    #[serde(default)]
+    pub exported: HashSet<String>,
+    /// Messages exported so far
+    #[serde(default)]
+    pub count: usize,
+}
+
+impl ExportState {
+    /// Load the state of `dir`, or start a new one. Refuses to mix formats or folders.
+    pub fn load(dir: &Path, folder: &str, format: StoreFormat) -> Result<Self> {
+        let path = dir.join(STATE_FILE);
+        if !path.exists() {
+            return Ok(Self {
+                folder: folder.to_string(),
+                format,
+                exported: HashSet::new(),
+                count: 0,
+            });
+        }
+
+        let content = fs::read_to_string(&path)?;
+        let state: ExportState = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        if state.format != format {
+            bail!(
+                "{} already holds a {:?} export; use another directory",
+                dir.display(),
+                state.format
+            );
+        }
+        if !state.folder.eq_ignore_ascii_case(folder) {
+            bail!(
+                "{} already holds an export of '{}'; use another directory",
+                dir.display(),
+                state.folder
+            );
+        }
+        Ok(state)
+    }
+
+    /// Whether a listed message was exported by an earlier run
+    pub fn is_exported(&self, id: &str) -> bool {
+        self.exported.contains(id)
+    }
+
+    /// Record an exported message
+    pub fn record(&mut self, id: &str) {
+        if self.exported.insert(id.to_string()) {
+            self.count += 1;
+        }
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        // Write beside the state file and rename over it, so an interrupted
+        // export never leaves it truncated
+        let path = dir.join(STATE_FILE);
+        let tmp = path.with_extension(format!("tmp.{}", std::process::id()));
+        fs::write(&tmp, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, &path).with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(())
+    }
+}
+
+/// A message to store, with the metadata file names are derived from
+pub struct StoredMessage<'a> {
+    pub id: &'a str,
+    pub received: DateTime<Utc>,
+    pub is_read: bool,
+    pub mime: &'a [u8],
+}
+
+/// Write one message into `dir` in the given layout, returning the file written
+pub fn store_message(
+    dir: &Path,
+    format: StoreFormat,
+    mbox_name: &str,
+    message: &StoredMessage,
+) -> Result<PathBuf> {
+    match format {
+        StoreFormat::Eml => {
+            let path = dir.join(format!(
+                "{}-{:016x}.eml",
+                message.received.format("%Y%m%dT%H%M%SZ"),
+                fnv1a(message.id)
+            ));
+            fs::write(&path, message.mime)?;
+            Ok(path)
+        }
+        StoreFormat::Maildir => {
+            for sub in ["cur", "new", "tmp"] {
+                fs::create_dir_all(dir.join(sub))?;
+            }
+            let name = maildir_name(message);
+            // Deliver through tmp/ so readers never see a partial file
+            let tmp = dir.join("tmp").join(&name);
+            fs::write(&tmp, message.mime)?;
+            let path = dir.join("cur").join(&name);
+            fs::rename(&tmp, &path)?;
+            Ok(path)
+        }
+        StoreFormat::Mbox => {
+            let path = dir.join(format!("{}.mbox", mbox_name));
+            let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+            file.write_all(&mbox_entry(message))?;
+            Ok(path)
+        }
+    }
+}
+
+/// `<time>.<id hash>.squads-cli:2,<flags>`, marked seen when read
+fn maildir_name(message: &StoredMessage) -> String {
+    format!(
+        "{}.{:016x}.squads-cli:2,{}",
+        message.received.timestamp(),
+        fnv1a(message.id),
+        if message.is_read { "S" } else { "" }
+    )
+}
+
+/// An mboxrd entry: `From ` separator line, LF line endings, and `From ` lines quoted.
+/// Works on raw bytes so 8bit parts in any charset are kept as they are.
+fn mbox_entry(message: &StoredMessage) -> Vec<u8> {
+    let mut out = format!(
+        "From MAILER-DAEMON {}\n",
+        message.received.format("%a %b %e %H:%M:%S %Y")
+    )
+    .into_bytes();
+    let mime = message.mime.strip_suffix(b"\n").unwrap_or(message.mime);
+    for line in mime.split(|&b| b == b'\n') {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        let unquoted = &line[line.iter().take_while(|&&b| b == b'>').count()..];
+        if unquoted.starts_with(b"From ") {
+            out.push(b'>');
+        }
+        out.extend_from_slice(line);
+        out.push(b'\n');
+    }
+    out.push(b'\n');
+    out
+}
+
+/// Stable 64-bit FNV-1a hash, used to keep Graph's long IDs out of file names
+fn fnv1a(s: &str) -> u64 {
+    s.bytes().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_export_state_picks_up_late_filed_message() {
+        let dir = std::env::temp_dir().join(format!("squads-export-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let mut state = ExportState::load(&dir, "Archive", StoreFormat::Eml).unwrap();
+        state.record("new");
+        state.save(&dir).unwrap();
+
+        // A message received long ago but filed into the folder after the export
+        let state = ExportState::load(&dir, "archive", StoreFormat::Eml).unwrap();
+        assert!(state.is_exported("new"));
+        assert!(!state.is_exported("older-filed-later"));
+        assert_eq!(state.count, 1);
+        assert!(ExportState::load(&dir, "Archive", StoreFormat::Mbox).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_mbox_entry() {
+        let message = StoredMessage {
+            id: "AAMk",
+            received: Utc.with_ymd_and_hms(2026, 10, 5, 9, 3, 0).unwrap(),
+            is_read: true,
+            mime: b"Subject: Hi\r\n\r\nFrom here on\r\n>From quoted\r\nbye\r\n",
+        };
+        assert_eq!(
+            String::from_utf8(mbox_entry(&message)).unwrap(),
+            "From MAILER-DAEMON Mon Oct  5 09:03:00 2026\n\
+             Subject: Hi\n\n>From here on\n>>From quoted\nbye\n\n"
+        );
+
+        let latin1 = StoredMessage {
+            mime: b"Subject: Caf\xe9\r\n\r\nD\xe9j\xe0 vu\r\n",
+            ..message
+        };
+        assert_eq!(
+            mbox_entry(&latin1),
+            b"From MAILER-DAEMON Mon Oct  5 09:03:00 2026\n\
+              Subject: Caf\xe9\n\nD\xe9j\xe0 vu\n\n"
+        );
+        assert_eq!(
+            maildir_name(&message),
+            format!("1791190980.{:016x}.squads-cli:2,S", fnv1a("AAMk"))
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::api::TeamsClient;
+use crate::cache::{mail_sync_file, Cache};
+use crate::types::MailMessage;
+
+/// Delta sync state of one consumer's folders, persisted in the cache dir.
+/// Each consumer has its own file, so `mail sync` never swallows the changes
+/// `watch` has yet to report and concurrent consumers never overwrite each other.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SyncStore {
+    #[serde(default)]
+    folders: HashMap<String, FolderSync>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FolderSync {
+    delta_link: String,
+    /// When tracking started; older messages showing up in a delta were changed, not received
+    since: String,
+    /// Read state of the messages seen in a delta since tracking started
+    #[serde(default)]
+    known: HashMap<String, bool>,
+}
+
+/// Changes in a folder since a consumer's previous sync
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncChanges {
+    /// No usable earlier state: tracking starts now and nothing is reported
+    pub initial: bool,
+    pub new: Vec<MailMessage>,
+    /// Messages whose read state flipped
+    pub read_changed: Vec<MailMessage>,
+    /// Messages changed otherwise (flags, categories...)
+    pub updated: Vec<MailMessage>,
+    /// IDs of messages deleted or moved out of the folder
+    pub removed: Vec<String>,
+}
+
+impl SyncChanges {
+    pub fn is_empty(&self) -> bool {
+        self.new.is_empty()
+            && self.read_changed.is_empty()
+            && self.updated.is_empty()
+            && self.removed.is_empty()
+    }
+}
+
+/// Key of a folder's state; folders of other mailboxes are prefixed with the mailbox
+fn store_key(mailbox: Option<&str>, folder_id: &str) -> String {
+    match mailbox {
+        Some(mailbox) => format!("{}:{}", mailbox.to_lowercase(), folder_id),
+        None => folder_id.to_string(),
+    }
+}
+
+/// Forget a consumer's state for a folder, so the next sync starts over
+pub fn reset(consumer: &str, mailbox: Option<&str>, folder_id: &str) -> Result<()> {
+    let cache = Cache::new()?;
+    let file = mail_sync_file(consumer);
+    let mut store: SyncStore = cache.load(&file)?.unwrap_or_default();
+    store.folders.remove(&store_key(mailbox, folder_id));
+    cache.save(&file, &store)
+}
+
+/// Sync a folder of the client's mailbox for `consumer`, returning what changed
+/// since its last sync.
+/// The first sync starts from the folder's current state without listing it.
+/// The new delta link is saved only once all pages were read.
+pub async fn sync_folder(
+    client: &TeamsClient,
+    consumer: &str,
+    folder_id: &str,
+) -> Result<SyncChanges> {
+    let cache = Cache::new()?;
+    let file = mail_sync_file(consumer);
+    let mut store: SyncStore = cache.load(&file)?.unwrap_or_default();
+    let key = store_key(client.mailbox(), folder_id);
+    let previous = store.folders.get(&key).cloned();
+
+    let mut fetched = match &previous {
+        Some(state) => {
+            client
+                .get_mail_delta(folder_id, Some(&state.delta_link))
+                .await?
+        }
+        None => None,
+    };
+    // No state yet, or an expired delta link: start over from now
+    let initial = fetched.is_none();
+    let mut state = match previous {
+        Some(state) if !initial => state,
+        _ => {
+            fetched = client.get_mail_delta(folder_id, None).await?;
+            FolderSync {
+                delta_link: String::new(),
+                since: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+                known: HashMap::new(),
+            }
+        }
+    };
+    let Some((messages, delta_link)) = fetched else {
+        bail!("Mail sync state expired twice in a row; try again later");
+    };
+
+    let mut changes = apply_delta(&mut state.known, &state.since, messages);
+    changes.initial = initial;
+    if initial || !changes.is_empty() || state.delta_link != delta_link {
+        state.delta_link = delta_link;
+        store.folders.insert(key, state);
+        cache.save(&file, &store)?;
+    }
+    Ok(changes)
+}
+
+/// Fold delta entries into the known read states, sorting them into changes
+fn apply_delta(
+    known: &mut HashMap<String, bool>,
+    since: &str,
+    messages: Vec<MailMessage>,
+) -> SyncChanges {
+    let mut changes = SyncChanges::default();
+    for message in messages {
+        let Some(id) = message.id.clone() else {
+            continue;
+        };
+        if message.removed.is_some() {
+            known.remove(&id);
+            changes.removed.push(id);
+            continue;
+        }
+
+        let is_read = message.is_read.unwrap_or(false);
+        let received_since = message
+            .received_date_time
+            .as_deref()
+            .is_some_and(|received| received >= since);
+        match known.insert(id, is_read) {
+            None if received_since => changes.new.push(message),
+            Some(before) if before != is_read => changes.read_changed.push(message),
+            _ => changes.updated.push(message),
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::RemovedMarker;
+
+    fn message(id: &str, is_read: bool, received: &str) -> MailMessage {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "subject": format!("subject {}", id),
+            "isRead": is_read,
+            "receivedDateTime": received,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_apply_delta() {
+        let since = "2024-05-06T09:00:00Z";
+        let mut known = HashMap::new();
+        let changes = apply_delta(
+            &mut known,
+            since,
+            vec![
+                message("a", false, "2024-05-06T09:05:00Z"),
+                message("old", true, "2024-01-01T00:00:00Z"),
+            ],
+        );
+        assert_eq!(changes.new.len(), 1);
+        assert_eq!(changes.updated.len(), 1);
+
+        let mut gone = message("old", true, "2024-01-01T00:00:00Z");
+        gone.removed = Some(RemovedMarker {
+            reason: Some("deleted".to_string()),
+        });
+        let changes = apply_delta(
+            &mut known,
+            since,
+            vec![
+                message("a", true, "2024-05-06T09:05:00Z"),
+                message("c", false, "2024-05-06T09:10:00Z"),
+                gone,
+                message("a", true, "2024-05-06T09:05:00Z"),
+            ],
+        );
+        assert_eq!(changes.new.len(), 1);
+        assert_eq!(changes.read_changed.len(), 1);
+        assert_eq!(changes.updated.len(), 1);
+        assert_eq!(changes.removed, vec!["old".to_string()]);
+        assert_eq!(known.len(), 2);
+    }
+}
@@ -1,15 +1,25 @@
 pub mod activity;
+pub mod agenda;
 pub mod auth;
 pub mod calendar;
+pub mod cards;
 pub mod chats;
 pub mod completions;
+pub mod contacts;
+pub mod datetime;
 pub mod feed;
+pub mod ics;
 pub mod install;
 pub mod mail;
+pub mod mailstore;
+pub mod mailsync;
 pub mod notes;
 pub mod output;
+pub mod people;
+pub mod rules;
 pub mod search;
 pub mod teams;
+pub mod templates;
 pub mod update;
 pub mod users;
 pub mod utils;
@@ -44,6 +54,12 @@ pub enum Commands {
     /// User operations
     Users(users::UsersCommand),
 
+    /// Outlook contacts
+    Contacts(contacts::ContactsCommand),
+
+    /// People you work with, ranked by relevance
+    People(people::PeopleCommand),
+
     /// Activity feed
     Activity(activity::ActivityCommand),
 
@@ -66,8 +66,7 @@ pub fn print_info(message: &str) {
     println!("{} {}", "ℹ".blue(), message);
 }
 
-/// Print warning message
-#[allow(dead_code)]
+/// Print warning message (to stderr, so it never mixes into `-f json` output)
 pub fn print_warning(message: &str) {
-    println!("{} {}", "⚠".yellow(), message);
+    eprintln!("{} {}", "⚠".yellow(), message);
 }
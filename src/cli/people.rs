@@ -0,0 +1,268 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use anyhow::{bail, Result};
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+use tabled::Tabled;
+
+use crate::api::TeamsClient;
+use crate::config::Config;
+
+use super::output::{print_output, print_single};
+use super::utils::truncate;
+use super::OutputFormat;
+
+/// Candidates shown when a name is ambiguous
+const MAX_CANDIDATES: usize = 10;
+
+#[derive(Args, Debug)]
+pub struct PeopleCommand {
+    /// Name or address to look for (omit for the people you work with most)
+    pub query: Option<String>,
+
+    /// Maximum number of people
+    #[arg(short, long, default_value = "20")]
+    pub limit: usize,
+}
+
+#[derive(Debug, Serialize, Tabled)]
+struct PersonRow {
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Email")]
+    email: String,
+    #[tabled(rename = "Job Title")]
+    job_title: String,
+    #[tabled(rename = "Kind")]
+    kind: String,
+}
+
+pub async fn execute(cmd: PeopleCommand, config: &Config, format: OutputFormat) -> Result<()> {
+    let client = TeamsClient::new(config)?;
+    let people = client.get_people(cmd.query.as_deref(), cmd.limit).await?;
+
+    if matches!(format, OutputFormat::Json) {
+        print_single(&people.value, format);
+        return Ok(());
+    }
+    let rows: Vec<PersonRow> = people
+        .value
+        .iter()
+        .map(|p| PersonRow {
+            name: truncate(p.display_name.as_deref().unwrap_or_default(), 30),
+            email: p.email().unwrap_or_default().to_string(),
+            job_title: truncate(p.job_title.as_deref().unwrap_or_default(), 30),
+            kind: p
+                .person_type
+                .as_ref()
+                .and_then(|t| t.subclass.clone())
+                .unwrap_or_default(),
+        })
+        .collect();
+    print_output(&rows, format);
+    Ok(())
+}
+
+/// Someone a name on the command line may refer to
+#[derive(Debug, Clone)]
+struct Candidate {
+    name: String,
+    email: String,
+}
+
+/// Resolve a list of names or addresses to addresses. See [`resolve_address`]
+/// and [`split_recipients`].
+pub async fn resolve_addresses(client: &TeamsClient, list: &str) -> Result<Vec<String>> {
+    let mut addresses = Vec::new();
+    for query in split_recipients(list) {
+        addresses.push(resolve_address(client, &query).await?);
+    }
+    Ok(addresses)
+}
+
+/// Split a recipient list on commas or `;` outside double quotes, so that
+/// `"Martin, Alice"` stays one name. Quotes are dropped, and a
+/// `Name <address>` entry is reduced to its address.
+fn split_recipients(list: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in list.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' | ';' if !quoted => items.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    items.push(current);
+    items
+        .iter()
+        .map(|item| {
+            let item = item.trim();
+            let address = item
+                .rsplit_once('<')
+                .and_then(|(_, rest)| rest.strip_suffix('>'))
+                .filter(|address| address.contains('@'));
+            address.unwrap_or(item).trim().to_string()
+        })
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Resolve a name to an email address: addresses pass through, names are looked
+/// up in the relevance-ranked people list, then in the directory. When several
+/// people match, the user picks one on a terminal; otherwise it is an error.
+pub async fn resolve_address(client: &TeamsClient, query: &str) -> Result<String> {
+    let query = query.trim();
+    if query.contains('@') {
+        return Ok(query.to_string());
+    }
+
+    let mut candidates: Vec<Candidate> = client
+        .get_people(Some(query), MAX_CANDIDATES)
+        .await?
+        .value
+        .iter()
+        .filter_map(|p| {
+            Some(Candidate {
+                name: p.display_name.clone().unwrap_or_default(),
+                email: p.email()?.to_string(),
+            })
+        })
+        .collect();
+    if candidates.is_empty() {
+        candidates = client
+            .search_users(query, MAX_CANDIDATES)
+            .await?
+            .value
+            .into_iter()
+            .filter_map(|u| {
+                Some(Candidate {
+                    name: u.display_name.unwrap_or_default(),
+                    email: u.mail?,
+                })
+            })
+            .collect();
+    }
+
+    let picked = pick(query, dedup(candidates))?;
+    eprintln!(
+        "{}",
+        format!("{} → {} <{}>", query, picked.name, picked.email).dimmed()
+    );
+    Ok(picked.email)
+}
+
+/// Drop repeated addresses, keeping the best-ranked entry
+fn dedup(candidates: Vec<Candidate>) -> Vec<Candidate> {
+    let mut unique: Vec<Candidate> = Vec::new();
+    for candidate in candidates {
+        if !unique
+            .iter()
+            .any(|c| c.email.eq_ignore_ascii_case(&candidate.email))
+        {
+            unique.push(candidate);
+        }
+    }
+    unique
+}
+
+/// One candidate, or the only exact name match; otherwise ask
+fn pick(query: &str, candidates: Vec<Candidate>) -> Result<Candidate> {
+    if candidates.is_empty() {
+        bail!("No one found matching \"{}\"", query);
+    }
+    let exact: Vec<&Candidate> = candidates
+        .iter()
+        .filter(|c| c.name.eq_ignore_ascii_case(query))
+        .collect();
+    if exact.len() == 1 {
+        return Ok(exact[0].clone());
+    }
+    if candidates.len() == 1 {
+        return Ok(candidates[0].clone());
+    }
+
+    eprintln!("Several people match \"{}\":", query);
+    for (i, c) in candidates.iter().enumerate() {
+        eprintln!("  {}. {} <{}>", i + 1, c.name, c.email);
+    }
+    if !io::stdin().is_terminal() || !io::stderr().is_terminal() {
+        bail!("\"{}\" is ambiguous; use an email address instead", query);
+    }
+
+    loop {
+        eprint!("Choose 1-{} (Enter for 1, q to cancel): ", candidates.len());
+        io::stderr().flush()?;
+        let mut line = String::new();
+        if io::stdin().lock().read_line(&mut line)? == 0 {
+            bail!("Cancelled");
+        }
+        let answer = line.trim();
+        if answer.eq_ignore_ascii_case("q") {
+            bail!("Cancelled");
+        }
+        if answer.is_empty() {
+            return Ok(candidates[0].clone());
+        }
+        if let Some(c) = answer
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| n.checked_sub(1))
+            .and_then(|i| candidates.get(i))
+        {
+            return Ok(c.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(name: &str, email: &str) -> Candidate {
+        Candidate {
+            name: name.to_string(),
+            email: email.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_pick() {
+        let candidates = dedup(vec![
+            candidate("Alice Martin", "alice@contoso.com"),
+            candidate("Alice", "ALICE@contoso.com"),
+        ]);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(pick("alice", candidates).unwrap().name, "Alice Martin");
+
+        let exact = pick(
+            "alice martin",
+            vec![
+                candidate("Alice Martinez", "amartinez@contoso.com"),
+                candidate("Alice Martin", "alice@contoso.com"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(exact.email, "alice@contoso.com");
+        assert!(pick("bob", Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_split_recipients() {
+        assert_eq!(
+            split_recipients(r#""Martin, Alice", bob@contoso.com; Carol Diaz;"#),
+            vec!["Martin, Alice", "bob@contoso.com", "Carol Diaz"]
+        );
+        assert_eq!(
+            split_recipients(r#""Doe; Jane" <jane@x.com>; Bob <bob@x.com>"#),
+            vec!["jane@x.com", "bob@x.com"]
+        );
+        assert_eq!(
+            split_recipients("a@x.com,b@x.com"),
+            vec!["a@x.com", "b@x.com"]
+        );
+        assert!(split_recipients(" , ; ").is_empty());
+    }
+}
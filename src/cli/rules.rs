@@ -0,0 +1,808 @@
+use std::collections::HashMap;
+use std::io::{self, Read};
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use serde::Serialize;
+use serde_json::Value;
+use tabled::Tabled;
+
+use crate::api::TeamsClient;
+use crate::config::Config;
+use crate::types::{
+    EmailAddress, MessageRule, MessageRuleActions, MessageRulePredicates, Recipient,
+};
+
+use super::mail::{resolve_folder_id, walk_folders, Importance};
+use super::output::{print_info, print_output, print_single, print_success};
+use super::OutputFormat;
+
+#[derive(Subcommand, Debug)]
+pub enum RulesSubcommand {
+    /// List inbox rules in the order they run
+    List,
+
+    /// Show a rule's conditions and actions
+    Show {
+        /// Rule ID or name
+        rule: String,
+    },
+
+    /// Create a rule
+    Create {
+        /// Rule name
+        name: String,
+
+        #[command(flatten)]
+        spec: RuleArgs,
+
+        /// Create the rule disabled
+        #[arg(long)]
+        disabled: bool,
+    },
+
+    /// Change a rule; options not given keep their current value
+    Update {
+        /// Rule ID or name
+        rule: String,
+
+        /// New rule name
+        #[arg(long)]
+        rename: Option<String>,
+
+        #[command(flatten)]
+        spec: RuleArgs,
+    },
+
+    /// Delete a rule
+    Delete {
+        /// Rule ID or name
+        rule: String,
+    },
+
+    /// Enable a rule
+    Enable {
+        /// Rule ID or name
+        rule: String,
+    },
+
+    /// Disable a rule
+    Disable {
+        /// Rule ID or name
+        rule: String,
+    },
+
+    /// Print all rules as JSON, with folder IDs replaced by folder paths
+    Export,
+
+    /// Make the server match a rules file: rules are matched by name, then
+    /// created or updated as needed
+    Apply {
+        /// Rules file as written by `export` (- for stdin)
+        file: String,
+
+        /// Show the changes without making them
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Also delete server rules that are missing from the file
+        #[arg(long)]
+        prune: bool,
+    },
+}
+
+/// Conditions and actions settable from the command line
+#[derive(Args, Debug)]
+pub struct RuleArgs {
+    /// Run order, lowest first
+    #[arg(long)]
+    sequence: Option<i32>,
+
+    /// Condition: sender address (repeatable)
+    #[arg(long, value_name = "ADDRESS")]
+    from: Vec<String>,
+
+    /// Condition: subject contains text (repeatable)
+    #[arg(long, value_name = "TEXT")]
+    subject_contains: Vec<String>,
+
+    /// Condition: body or subject contains text (repeatable)
+    #[arg(long, value_name = "TEXT")]
+    body_or_subject_contains: Vec<String>,
+
+    /// Condition: sender name or address contains text (repeatable)
+    #[arg(long, value_name = "TEXT")]
+    sender_contains: Vec<String>,
+
+    /// Condition: message has attachments
+    #[arg(long)]
+    has_attachments: bool,
+
+    /// Condition: message importance
+    #[arg(long, value_enum)]
+    importance: Option<Importance>,
+
+    /// Action: move to folder (name, path or ID)
+    #[arg(long, value_name = "FOLDER")]
+    move_to: Option<String>,
+
+    /// Action: copy to folder (name, path or ID)
+    #[arg(long, value_name = "FOLDER")]
+    copy_to: Option<String>,
+
+    /// Action: mark as read
+    #[arg(long)]
+    mark_read: bool,
+
+    /// Action: assign category (repeatable)
+    #[arg(long, value_name = "CATEGORY")]
+    categorize: Vec<String>,
+
+    /// Action: forward to address (repeatable)
+    #[arg(long, value_name = "ADDRESS")]
+    forward_to: Vec<String>,
+
+    /// Action: move to Deleted Items
+    #[arg(long)]
+    delete: bool,
+
+    /// Action: do not run later rules
+    #[arg(long)]
+    stop: bool,
+}
+
+impl RuleArgs {
+    /// Set the given options on `rule`, resolving folder names to IDs
+    async fn merge_into(&self, client: &TeamsClient, rule: &mut MessageRule) -> Result<()> {
+        if self.sequence.is_some() {
+            rule.sequence = self.sequence;
+        }
+
+        let conditions = rule.conditions.get_or_insert_with(Default::default);
+        if !self.from.is_empty() {
+            conditions.from_addresses = Some(recipients(&self.from));
+        }
+        if !self.subject_contains.is_empty() {
+            conditions.subject_contains = Some(self.subject_contains.clone());
+        }
+        if !self.body_or_subject_contains.is_empty() {
+            conditions.body_or_subject_contains = Some(self.body_or_subject_contains.clone());
+        }
+        if !self.sender_contains.is_empty() {
+            conditions.sender_contains = Some(self.sender_contains.clone());
+        }
+        if self.has_attachments {
+            conditions.has_attachments = Some(true);
+        }
+        if let Some(importance) = self.importance {
+            conditions.importance = Some(importance.as_graph().to_string());
+        }
+        if *conditions == MessageRulePredicates::default() {
+            rule.conditions = None;
+        }
+
+        let actions = rule.actions.get_or_insert_with(Default::default);
+        if let Some(folder) = &self.move_to {
+            actions.move_to_folder = Some(resolve_folder_id(client, folder).await?);
+        }
+        if let Some(folder) = &self.copy_to {
+            actions.copy_to_folder = Some(resolve_folder_id(client, folder).await?);
+        }
+        if self.mark_read {
+            actions.mark_as_read = Some(true);
+        }
+        if !self.categorize.is_empty() {
+            actions.assign_categories = Some(self.categorize.clone());
+        }
+        if !self.forward_to.is_empty() {
+            actions.forward_to = Some(recipients(&self.forward_to));
+        }
+        if self.delete {
+            actions.delete = Some(true);
+        }
+        if self.stop {
+            actions.stop_processing_rules = Some(true);
+        }
+        if *actions == MessageRuleActions::default() {
+            rule.actions = None;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Tabled)]
+struct RuleRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "Seq")]
+    sequence: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Enabled")]
+    enabled: String,
+    #[tabled(rename = "Status")]
+    status: String,
+}
+
+/// A change `apply` makes to bring the server in line with a rules file
+#[derive(Debug, PartialEq)]
+enum RuleChange {
+    Create(MessageRule),
+    Update { id: String, rule: MessageRule },
+    Delete { id: String, name: String },
+}
+
+pub async fn execute(
+    cmd: RulesSubcommand,
+    config: &Config,
+    mailbox: Option<&str>,
+    format: OutputFormat,
+) -> Result<()> {
+    let client = TeamsClient::new(config)?.with_mailbox(mailbox);
+    match cmd {
+        RulesSubcommand::List => list(&client, format).await,
+        RulesSubcommand::Show { rule } => show(&client, &rule, format).await,
+        RulesSubcommand::Create {
+            name,
+            spec,
+            disabled,
+        } => create(&client, name, &spec, disabled, format).await,
+        RulesSubcommand::Update { rule, rename, spec } => {
+            update(&client, &rule, rename, &spec).await
+        }
+        RulesSubcommand::Delete { rule } => {
+            let rule = find_rule(&client, &rule).await?;
+            client
+                .delete_message_rule(rule.id.as_deref().unwrap_or_default())
+                .await?;
+            print_success(&format!("Rule '{}' deleted", rule_name(&rule)));
+            Ok(())
+        }
+        RulesSubcommand::Enable { rule } => set_enabled(&client, &rule, true).await,
+        RulesSubcommand::Disable { rule } => set_enabled(&client, &rule, false).await,
+        RulesSubcommand::Export => export(&client).await,
+        RulesSubcommand::Apply {
+            file,
+            dry_run,
+            prune,
+        } => apply(&client, &file, dry_run, prune).await,
+    }
+}
+
+async fn list(client: &TeamsClient, format: OutputFormat) -> Result<()> {
+    let rules = sorted_rules(client).await?;
+
+    if matches!(format, OutputFormat::Json) {
+        print_single(&rules, format);
+        return Ok(());
+    }
+
+    let rows: Vec<RuleRow> = rules
+        .iter()
+        .map(|r| {
+            let mut status = Vec::new();
+            if r.has_error == Some(true) {
+                status.push("error");
+            }
+            if r.is_read_only == Some(true) {
+                status.push("read-only");
+            }
+            RuleRow {
+                id: r.id.clone().unwrap_or_default(),
+                sequence: r.sequence.map(|s| s.to_string()).unwrap_or_default(),
+                name: rule_name(r).to_string(),
+                enabled: if r.is_enabled == Some(true) {
+                    "Yes"
+                } else {
+                    "No"
+                }
+                .to_string(),
+                status: status.join(", "),
+            }
+        })
+        .collect();
+
+    print_output(&rows, format);
+    Ok(())
+}
+
+async fn show(client: &TeamsClient, key: &str, format: OutputFormat) -> Result<()> {
+    let rule = find_rule(client, key).await?;
+
+    if matches!(format, OutputFormat::Json) {
+        print_single(&rule, format);
+        return Ok(());
+    }
+
+    let mut rule = rule;
+    if has_folder_actions(&rule) {
+        let paths = folder_paths(client).await?;
+        map_rule_folders(&mut rule, |id| paths.get(id).cloned());
+    }
+
+    println!("{}", rule_name(&rule).bold());
+    println!("ID: {}", rule.id.as_deref().unwrap_or_default());
+    if let Some(sequence) = rule.sequence {
+        println!("Sequence: {}", sequence);
+    }
+    println!(
+        "Enabled: {}",
+        if rule.is_enabled == Some(true) {
+            "Yes"
+        } else {
+            "No"
+        }
+    );
+    if rule.has_error == Some(true) {
+        println!("{}", "This rule has an error and does not run".red());
+    }
+
+    for (title, value) in [
+        ("Conditions", serde_json::to_value(&rule.conditions)?),
+        ("Exceptions", serde_json::to_value(&rule.exceptions)?),
+        ("Actions", serde_json::to_value(&rule.actions)?),
+    ] {
+        let Value::Object(fields) = normalize(value) else {
+            continue;
+        };
+        println!("{}:", title);
+        for (key, value) in fields {
+            println!("  {}: {}", key, describe_value(&value));
+        }
+    }
+    Ok(())
+}
+
+async fn create(
+    client: &TeamsClient,
+    name: String,
+    spec: &RuleArgs,
+    disabled: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    let mut rule = MessageRule {
+        display_name: Some(name),
+        is_enabled: Some(!disabled),
+        ..Default::default()
+    };
+    spec.merge_into(client, &mut rule).await?;
+    if rule.actions.is_none() {
+        bail!("A rule needs at least one action, e.g. --move-to, --mark-read or --delete");
+    }
+    if rule.sequence.is_none() {
+        // Run after the existing rules
+        let last = client
+            .get_message_rules()
+            .await?
+            .value
+            .iter()
+            .filter_map(|r| r.sequence)
+            .max();
+        rule.sequence = Some(last.unwrap_or(0) + 1);
+    }
+
+    let created = client.create_message_rule(&rule).await?;
+    match format {
+        OutputFormat::Json => print_single(&created, format),
+        _ => print_success(&format!(
+            "Rule '{}' created with ID: {}",
+            rule_name(&created),
+            created.id.as_deref().unwrap_or_default()
+        )),
+    }
+    Ok(())
+}
+
+async fn update(
+    client: &TeamsClient,
+    key: &str,
+    rename: Option<String>,
+    spec: &RuleArgs,
+) -> Result<()> {
+    let current = find_rule(client, key).await?;
+    let mut rule = current.desired();
+    if rename.is_some() {
+        rule.display_name = rename;
+    }
+    spec.merge_into(client, &mut rule).await?;
+
+    if rule == current.desired() {
+        print_info("Nothing to change");
+        return Ok(());
+    }
+    let updated = client
+        .update_message_rule(current.id.as_deref().unwrap_or_default(), &rule)
+        .await?;
+    print_success(&format!("Rule '{}' updated", rule_name(&updated)));
+    Ok(())
+}
+
+async fn set_enabled(client: &TeamsClient, key: &str, enabled: bool) -> Result<()> {
+    let rule = find_rule(client, key).await?;
+    let change = MessageRule {
+        is_enabled: Some(enabled),
+        ..Default::default()
+    };
+    client
+        .update_message_rule(rule.id.as_deref().unwrap_or_default(), &change)
+        .await?;
+    print_success(&format!(
+        "Rule '{}' {}",
+        rule_name(&rule),
+        if enabled { "enabled" } else { "disabled" }
+    ));
+    Ok(())
+}
+
+async fn export(client: &TeamsClient) -> Result<()> {
+    let rules = sorted_rules(client).await?;
+    let paths = if rules.iter().any(has_folder_actions) {
+        folder_paths(client).await?
+    } else {
+        HashMap::new()
+    };
+
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&exported_rules(&rules, &paths))?
+    );
+    Ok(())
+}
+
+/// Rules as written to a rules file, with folder IDs replaced by folder paths
+fn exported_rules(rules: &[MessageRule], paths: &HashMap<String, String>) -> Vec<MessageRule> {
+    rules
+        .iter()
+        .filter(|r| r.is_read_only != Some(true))
+        .map(|rule| {
+            let mut rule = rule.desired();
+            map_rule_folders(&mut rule, |id| paths.get(id).cloned());
+            rule
+        })
+        .collect()
+}
+
+async fn apply(client: &TeamsClient, file: &str, dry_run: bool, prune: bool) -> Result<()> {
+    let content = if file == "-" {
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer)?;
+        buffer
+    } else {
+        std::fs::read_to_string(file).with_context(|| format!("Failed to read {}", file))?
+    };
+    let mut desired: Vec<MessageRule> =
+        serde_json::from_str(&content).context("Rules file must be a JSON array of rules")?;
+
+    // Folder paths in the file become IDs, resolving each path once
+    let mut resolved: HashMap<String, String> = HashMap::new();
+    for rule in &desired {
+        let actions = rule.actions.iter();
+        let folders = actions.flat_map(|a| [&a.move_to_folder, &a.copy_to_folder]);
+        for folder in folders.flatten() {
+            if !resolved.contains_key(folder) {
+                let id = resolve_folder_id(client, folder).await?;
+                resolved.insert(folder.clone(), id);
+            }
+        }
+    }
+    for rule in &mut desired {
+        map_rule_folders(rule, |folder| resolved.get(folder).cloned());
+    }
+
+    let current = client.get_message_rules().await?.value;
+    let changes = plan_changes(desired, &current, prune)?;
+    if changes.is_empty() {
+        print_info("Rules are up to date");
+        return Ok(());
+    }
+
+    for change in &changes {
+        match change {
+            RuleChange::Create(rule) => println!("{} {}", "+".green(), rule_name(rule)),
+            RuleChange::Update { rule, .. } => println!("{} {}", "~".yellow(), rule_name(rule)),
+            RuleChange::Delete { name, .. } => println!("{} {}", "-".red(), name),
+        }
+    }
+    if dry_run {
+        print_info(&format!(
+            "{} change(s) not applied (dry run)",
+            changes.len()
+        ));
+        return Ok(());
+    }
+
+    for change in &changes {
+        match change {
+            RuleChange::Create(rule) => {
+                client.create_message_rule(rule).await?;
+            }
+            RuleChange::Update { id, rule } => {
+                client.update_message_rule(id, rule).await?;
+            }
+            RuleChange::Delete { id, .. } => client.delete_message_rule(id).await?,
+        }
+    }
+    print_success(&format!("Applied {} change(s)", changes.len()));
+    Ok(())
+}
+
+/// Compare desired rules (folders already resolved to IDs) with the server's,
+/// matching them by name. Server rules missing from the file are only
+/// deleted when `prune` is set
+fn plan_changes(
+    desired: Vec<MessageRule>,
+    current: &[MessageRule],
+    prune: bool,
+) -> Result<Vec<RuleChange>> {
+    let mut changes = Vec::new();
+    let mut seen: Vec<String> = Vec::new();
+
+    for mut rule in desired {
+        let name = rule
+            .display_name
+            .clone()
+            .ok_or_else(|| anyhow!("Every rule in the file needs a displayName"))?;
+        if seen.iter().any(|n| n.eq_ignore_ascii_case(&name)) {
+            bail!("Rule '{}' appears twice in the file", name);
+        }
+        seen.push(name.clone());
+
+        let existing = current.iter().find(|r| {
+            r.display_name
+                .as_deref()
+                .is_some_and(|n| n.eq_ignore_ascii_case(&name))
+        });
+        match existing {
+            None => {
+                rule.is_enabled.get_or_insert(true);
+                changes.push(RuleChange::Create(rule));
+            }
+            Some(existing) => {
+                // Unset fields in the file keep the server's defaults
+                rule.is_enabled.get_or_insert(true);
+                if rule.sequence.is_none() {
+                    rule.sequence = existing.sequence;
+                }
+                let wanted = normalize(serde_json::to_value(rule.desired())?);
+                let actual = normalize(serde_json::to_value(existing.desired())?);
+                if wanted != actual {
+                    // PATCH keeps omitted fields, so cleared predicates are sent empty
+                    rule.conditions.get_or_insert_with(Default::default);
+                    rule.exceptions.get_or_insert_with(Default::default);
+                    changes.push(RuleChange::Update {
+                        id: existing.id.clone().unwrap_or_default(),
+                        rule,
+                    });
+                }
+            }
+        }
+    }
+
+    if prune {
+        for rule in current {
+            let name = rule_name(rule);
+            if rule.is_read_only == Some(true) || seen.iter().any(|n| n.eq_ignore_ascii_case(name))
+            {
+                continue;
+            }
+            changes.push(RuleChange::Delete {
+                id: rule.id.clone().unwrap_or_default(),
+                name: name.to_string(),
+            });
+        }
+    }
+    Ok(changes)
+}
+
+/// Drop nulls, `false`, empty arrays and empty objects, which Graph treats as unset
+fn normalize(value: Value) -> Value {
+    match value {
+        Value::Object(fields) => {
+            let fields: serde_json::Map<String, Value> = fields
+                .into_iter()
+                .map(|(k, v)| (k, normalize(v)))
+                .filter(|(_, v)| !is_unset(v))
+                .collect();
+            if fields.is_empty() {
+                Value::Null
+            } else {
+                Value::Object(fields)
+            }
+        }
+        Value::Array(items) => {
+            let items: Vec<Value> = items.into_iter().map(normalize).collect();
+            if items.is_empty() {
+                Value::Null
+            } else {
+                Value::Array(items)
+            }
+        }
+        other => other,
+    }
+}
+
+fn is_unset(value: &Value) -> bool {
+    matches!(value, Value::Null | Value::Bool(false))
+}
+
+/// One-line rendering of a condition or action value
+fn describe_value(value: &Value) -> String {
+    match value {
+        Value::Bool(true) => "yes".to_string(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items
+            .iter()
+            .map(|item| match item["emailAddress"]["address"].as_str() {
+                Some(address) => address.to_string(),
+                None => describe_value(item),
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        other => other.to_string(),
+    }
+}
+
+fn recipients(addresses: &[String]) -> Vec<Recipient> {
+    addresses
+        .iter()
+        .map(|address| Recipient {
+            email_address: EmailAddress {
+                address: address.clone(),
+                name: None,
+            },
+        })
+        .collect()
+}
+
+fn rule_name(rule: &MessageRule) -> &str {
+    rule.display_name.as_deref().unwrap_or("(unnamed)")
+}
+
+fn has_folder_actions(rule: &MessageRule) -> bool {
+    rule.actions
+        .as_ref()
+        .is_some_and(|a| a.move_to_folder.is_some() || a.copy_to_folder.is_some())
+}
+
+/// Rewrite the folder references of a rule's actions, leaving unmapped ones as they are
+fn map_rule_folders(rule: &mut MessageRule, map: impl Fn(&str) -> Option<String>) {
+    if let Some(actions) = rule.actions.as_mut() {
+        for folder in [&mut actions.move_to_folder, &mut actions.copy_to_folder] {
+            if let Some(mapped) = folder.as_deref().and_then(&map) {
+                *folder = Some(mapped);
+            }
+        }
+    }
+}
+
+async fn folder_paths(client: &TeamsClient) -> Result<HashMap<String, String>> {
+    Ok(walk_folders(client)
+        .await?
+        .into_iter()
+        .map(|(folder, _, path)| (folder.id, path))
+        .collect())
+}
+
+async fn sorted_rules(client: &TeamsClient) -> Result<Vec<MessageRule>> {
+    let mut rules = client.get_message_rules().await?.value;
+    rules.sort_by_key(|r| r.sequence.unwrap_or(i32::MAX));
+    Ok(rules)
+}
+
+/// Find a rule by ID, or by name (case-insensitive)
+async fn find_rule(client: &TeamsClient, key: &str) -> Result<MessageRule> {
+    let rules = client.get_message_rules().await?.value;
+    if let Some(rule) = rules.iter().find(|r| r.id.as_deref() == Some(key)) {
+        return Ok(rule.clone());
+    }
+    let mut matches = rules.into_iter().filter(|r| {
+        r.display_name
+            .as_deref()
+            .is_some_and(|n| n.eq_ignore_ascii_case(key))
+    });
+    match (matches.next(), matches.next()) {
+        (Some(rule), None) => Ok(rule),
+        (Some(_), Some(_)) => bail!("Several rules are named '{}'; use the rule ID", key),
+        (None, _) => bail!("No inbox rule with ID or name '{}'", key),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(name: &str, subject: &str) -> MessageRule {
+        MessageRule {
+            display_name: Some(name.to_string()),
+            conditions: Some(MessageRulePredicates {
+                subject_contains: Some(vec![subject.to_string()]),
+                ..Default::default()
+            }),
+            actions: Some(MessageRuleActions {
+                mark_as_read: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_export_then_apply_is_up_to_date() {
+        let mut archived = rule("Receipts", "receipt");
+        if let Some(actions) = archived.actions.as_mut() {
+            actions.move_to_folder = Some("AAMkArchiveId".to_string());
+        }
+        let current = vec![MessageRule {
+            id: Some("1".to_string()),
+            sequence: Some(1),
+            is_enabled: Some(true),
+            ..archived
+        }];
+
+        // Export writes the folder's path; apply resolves it back to the real ID
+        let paths = HashMap::from([("AAMkArchiveId".to_string(), "Archive".to_string())]);
+        let file = serde_json::to_string(&exported_rules(&current, &paths)).unwrap();
+        assert!(file.contains(r#""moveToFolder":"Archive""#));
+
+        let mut desired: Vec<MessageRule> = serde_json::from_str(&file).unwrap();
+        let resolved = HashMap::from([("Archive".to_string(), "AAMkArchiveId".to_string())]);
+        for rule in &mut desired {
+            map_rule_folders(rule, |folder| resolved.get(folder).cloned());
+        }
+        assert!(plan_changes(desired, &current, true).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_plan_changes() {
+        let server = |r: MessageRule, id: &str, seq: i32| MessageRule {
+            id: Some(id.to_string()),
+            sequence: Some(seq),
+            is_enabled: Some(true),
+            has_error: Some(false),
+            actions: r.actions.map(|a| MessageRuleActions {
+                stop_processing_rules: Some(false),
+                ..a
+            }),
+            ..r
+        };
+        let current = vec![
+            server(rule("Receipts", "receipt"), "1", 1),
+            server(rule("CI", "build failed"), "2", 2),
+            server(rule("Old", "legacy"), "3", 3),
+        ];
+        let desired = vec![
+            rule("Receipts", "receipt"),
+            rule("CI", "pipeline failed"),
+            rule("Newsletters", "unsubscribe"),
+        ];
+
+        let summary = |changes: Vec<RuleChange>| -> Vec<String> {
+            changes
+                .iter()
+                .map(|c| match c {
+                    RuleChange::Create(r) => format!("+{}", rule_name(r)),
+                    RuleChange::Update { id, .. } => format!("~{}", id),
+                    RuleChange::Delete { id, .. } => format!("-{}", id),
+                })
+                .collect()
+        };
+        let changes = plan_changes(desired.clone(), &current, false).unwrap();
+        assert_eq!(summary(changes), vec!["~2", "+Newsletters"]);
+        let changes = plan_changes(desired, &current, true).unwrap();
+        assert_eq!(summary(changes), vec!["~2", "+Newsletters", "-3"]);
+
+        let mut cleared = rule("Old", "legacy");
+        cleared.conditions = None;
+        let changes = plan_changes(vec![cleared], &current, false).unwrap();
+        let RuleChange::Update { rule: patch, .. } = &changes[0] else {
+            panic!("expected an update");
+        };
+        let body = serde_json::to_value(patch).unwrap();
+        assert_eq!(body["conditions"], serde_json::json!({}));
+        assert_eq!(body["exceptions"], serde_json::json!({}));
+
+        let duplicate = vec![rule("A", "x"), rule("a", "y")];
+        assert!(plan_changes(duplicate, &current, false).is_err());
+    }
+}
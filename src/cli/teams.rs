@@ -8,6 +8,9 @@ use tabled::Tabled;
 use crate::api::TeamsClient;
 use crate::config::Config;
 
+use super::cards::{
+    card_attachment, card_summary, load_card, message_cards, print_card_sections, render_cards,
+};
 use super::output::{print_error, print_output, print_single, print_success};
 use super::utils::{html_escape, markdown_to_html, strip_html, truncate};
 use super::OutputFormat;
@@ -70,6 +73,14 @@ pub enum TeamsSubcommand {
         /// Treat message as Markdown and convert to HTML
         #[arg(short, long)]
         markdown: bool,
+
+        /// Post an Adaptive Card from a JSON file (message text becomes optional)
+        #[arg(long)]
+        card: Option<String>,
+
+        /// JSON data file used to expand ${...} templates in the card
+        #[arg(long, requires = "card")]
+        data: Option<String>,
     },
 
     /// Reply to a message in a team channel
@@ -195,6 +206,9 @@ struct MessageRow {
     reactions: String,
     #[tabled(rename = "Content")]
     content: String,
+    #[tabled(skip)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    cards: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Tabled)]
@@ -236,6 +250,8 @@ pub async fn execute(cmd: TeamsCommand, config: &Config, format: OutputFormat) -
             subject,
             stdin,
             markdown,
+            card,
+            data,
         } => {
             post(
                 config,
@@ -245,6 +261,8 @@ pub async fn execute(cmd: TeamsCommand, config: &Config, format: OutputFormat) -
                 subject,
                 stdin,
                 markdown,
+                card,
+                data,
             )
             .await
         }
@@ -373,8 +391,15 @@ async fn messages(
         for msg in chain.messages {
             if msg.message_type.as_deref() == Some("RichText/Html")
                 || msg.message_type.as_deref() == Some("Text")
+                || !message_cards(&msg.properties).is_empty()
             {
-                let content = msg.content.map(|c| strip_html(&c)).unwrap_or_default();
+                let cards = render_cards(&msg.properties);
+                let mut content = msg.content.map(|c| strip_html(&c)).unwrap_or_default();
+                if content.is_empty() {
+                    if let Some(header) = cards.first() {
+                        content = header.clone();
+                    }
+                }
 
                 let subject = msg
                     .properties
@@ -412,6 +437,7 @@ async fn messages(
                         OutputFormat::Json => content.clone(),
                         _ => truncate(&content, 40),
                     },
+                    cards,
                 });
 
                 if rows.len() >= limit {
@@ -425,9 +451,18 @@ async fn messages(
     }
 
     print_output(&rows, format);
+    if !matches!(format, OutputFormat::Json) {
+        let card_sections: Vec<(String, Vec<String>)> = rows
+            .iter()
+            .filter(|r| !r.cards.is_empty())
+            .map(|r| (r.id.clone(), r.cards.clone()))
+            .collect();
+        print_card_sections(&card_sections);
+    }
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn post(
     config: &Config,
     team_id: &str,
@@ -436,13 +471,22 @@ async fn post(
     subject: Option<String>,
     stdin: bool,
     markdown: bool,
+    card: Option<String>,
+    data: Option<String>,
 ) -> Result<()> {
+    // Validate the card before doing any network work
+    let card = card
+        .map(|path| load_card(&path, data.as_deref()))
+        .transpose()?;
+
     let content = if let Some(msg) = message {
         msg
     } else if stdin {
         let mut buffer = String::new();
         io::stdin().read_to_string(&mut buffer)?;
         buffer.trim().to_string()
+    } else if let Some(card) = &card {
+        card_summary(card)
     } else {
         print_error("No message provided. Use --stdin or provide message as argument.");
         return Ok(());
@@ -461,6 +505,19 @@ async fn post(
         format!("<p>{}</p>", html_escape(&content))
     };
 
+    if let Some(card) = card {
+        client
+            .send_card_message(
+                channel_id,
+                &html_body,
+                &[card_attachment(&card)],
+                subject.as_deref(),
+            )
+            .await?;
+        print_success("Card posted to channel");
+        return Ok(());
+    }
+
     let result = client
         .send_channel_message(team_id, channel_id, &html_body, subject.as_deref())
         .await?;
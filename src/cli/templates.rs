@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+use std::fs;
+
+use anyhow::{bail, Context, Result};
+use clap::Args;
+
+use crate::config::Config;
+
+use super::utils::{html_escape, parse_key_value};
+
+/// Template selection, shared by `mail send`, `mail draft`, `mail reply` and `chats send`.
+/// Each command marks its own body options as conflicting with `--template`.
+#[derive(Args, Debug, Default)]
+pub struct TemplateArgs {
+    /// Use a message template from the templates directory instead of a body
+    #[arg(long, value_name = "NAME")]
+    pub template: Option<String>,
+
+    /// Template variable (repeatable)
+    #[arg(long = "var", value_name = "KEY=VALUE", requires = "template")]
+    pub vars: Vec<String>,
+}
+
+impl TemplateArgs {
+    /// The rendered template, when one was chosen
+    pub fn load(&self) -> Result<Option<Template>> {
+        self.template
+            .as_deref()
+            .map(|name| Template::load(name)?.render(&self.vars))
+            .transpose()
+    }
+}
+
+/// How a template body is written, from its file extension
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TemplateKind {
+    Text,
+    Markdown,
+    Html,
+}
+
+/// A canned message from the templates directory. A first line of
+/// `Subject: ...` sets the mail subject; `${key}` placeholders are filled by `--var`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    pub subject: Option<String>,
+    pub body: String,
+    pub kind: TemplateKind,
+}
+
+impl Template {
+    /// Load `<name>.md`, `<name>.html`, `<name>.txt` or `<name>` from the templates directory
+    pub fn load(name: &str) -> Result<Self> {
+        let dir = Config::templates_dir()?;
+        for (ext, kind) in [
+            ("md", TemplateKind::Markdown),
+            ("html", TemplateKind::Html),
+            ("txt", TemplateKind::Text),
+            ("", TemplateKind::Text),
+        ] {
+            let path = if ext.is_empty() {
+                dir.join(name)
+            } else {
+                dir.join(format!("{}.{}", name, ext))
+            };
+            if path.is_file() {
+                let content = fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read template {:?}", path))?;
+                return Ok(Self::parse(&content, kind));
+            }
+        }
+
+        let mut available: Vec<String> = fs::read_dir(&dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|e| {
+                        let path = e.path();
+                        Some(path.file_stem()?.to_string_lossy().into_owned())
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        available.sort();
+        if available.is_empty() {
+            bail!("Template '{}' not found; add it to {:?}", name, dir);
+        }
+        bail!(
+            "Template '{}' not found in {:?} (available: {})",
+            name,
+            dir,
+            available.join(", ")
+        )
+    }
+
+    fn parse(content: &str, kind: TemplateKind) -> Self {
+        let (subject, body) = match content.split_once('\n') {
+            Some((first, rest)) if first.starts_with("Subject:") => (
+                Some(first.trim_start_matches("Subject:").trim().to_string()),
+                rest.trim_start_matches(['\r', '\n']),
+            ),
+            _ => (None, content),
+        };
+        Self {
+            subject,
+            body: body.trim_end().to_string(),
+            kind,
+        }
+    }
+
+    /// The `(markdown, html)` body flags the template's file type stands for
+    pub fn format_flags(&self) -> (bool, bool) {
+        (
+            self.kind == TemplateKind::Markdown,
+            self.kind == TemplateKind::Html,
+        )
+    }
+
+    /// Fill `${key}` placeholders from `key=value` pairs; any placeholder left unset is an error.
+    /// Values are inserted as text: escaped in HTML and Markdown bodies, so they can't add markup.
+    pub fn render(self, vars: &[String]) -> Result<Self> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|v| parse_key_value(v))
+            .collect::<Result<_>>()?;
+        let escape: fn(&str) -> String = match self.kind {
+            TemplateKind::Html => html_escape,
+            TemplateKind::Markdown => markdown_escape,
+            TemplateKind::Text => |value| value.to_string(),
+        };
+        let mut missing = Vec::new();
+        let subject = self
+            .subject
+            .map(|s| substitute(&s, &vars, |value| value.to_string(), &mut missing));
+        let body = substitute(&self.body, &vars, escape, &mut missing);
+        if !missing.is_empty() {
+            missing.sort();
+            missing.dedup();
+            bail!(
+                "Missing template variable(s): {} (use --var key=value)",
+                missing.join(", ")
+            );
+        }
+        Ok(Self {
+            subject,
+            body,
+            kind: self.kind,
+        })
+    }
+}
+
+/// Backslash-escape Markdown punctuation (`:` and `@` too, against autolinks),
+/// and `&` so entities stay literal
+fn markdown_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == '&' {
+            out.push_str("&amp;");
+            continue;
+        }
+        if "\\`*_{}[]()#+-.!|<>~:@".contains(c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Replace `${key}` with its escaped value, recording unknown keys
+fn substitute(
+    text: &str,
+    vars: &HashMap<String, String>,
+    escape: fn(&str) -> String,
+    missing: &mut Vec<String>,
+) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let key = rest[start + 2..start + 2 + len].trim();
+        match vars.get(key) {
+            Some(value) => out.push_str(&escape(value)),
+            None => {
+                missing.push(key.to_string());
+                out.push_str(&rest[start..start + 3 + len]);
+            }
+        }
+        rest = &rest[start + 3 + len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = Template::parse(
+            "Subject: Ticket ${ticket}\n\nHi ${ name },\n\nTicket ${ticket} is closed.\n",
+            TemplateKind::Markdown,
+        );
+        let rendered = template
+            .clone()
+            .render(&["ticket=42".to_string(), "name=Alice".to_string()])
+            .unwrap();
+        assert_eq!(rendered.subject.as_deref(), Some("Ticket 42"));
+        assert_eq!(rendered.body, "Hi Alice,\n\nTicket 42 is closed.");
+
+        let err = template.render(&["ticket=42".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("name"));
+    }
+
+    #[test]
+    fn test_render_escapes_values() {
+        let vars = ["name=<b>Tom & Jerry</b>".to_string()];
+        let html = Template::parse(
+            "Subject: For ${name}\n<p>Hi ${name}</p>",
+            TemplateKind::Html,
+        )
+        .render(&vars)
+        .unwrap();
+        assert_eq!(html.body, "<p>Hi &lt;b&gt;Tom &amp; Jerry&lt;/b&gt;</p>");
+        assert_eq!(html.subject.as_deref(), Some("For <b>Tom & Jerry</b>"));
+
+        let markdown = Template::parse("Hi ${name}", TemplateKind::Markdown)
+            .render(&["name=*[x](https://evil.example)*".to_string()])
+            .unwrap();
+        assert_eq!(
+            markdown.body,
+            "Hi \\*\\[x\\]\\(https\\://evil\\.example\\)\\*"
+        );
+        assert!(!crate::cli::utils::markdown_to_html(&markdown.body).contains("<a"));
+
+        let text = Template::parse("Hi ${name}", TemplateKind::Text)
+            .render(&vars)
+            .unwrap();
+        assert_eq!(text.body, "Hi <b>Tom & Jerry</b>");
+    }
+}
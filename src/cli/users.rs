@@ -1,12 +1,16 @@
-use anyhow::Result;
-use clap::{Args, Subcommand};
+use anyhow::{bail, Result};
+use clap::{Args, Subcommand, ValueEnum};
 use serde::Serialize;
 use tabled::Tabled;
 
 use crate::api::TeamsClient;
 use crate::config::Config;
+use crate::types::{
+    DateTimeZone, PresenceMessageContent, PresenceStatusMessage, SetPresenceRequest,
+};
 
-use super::output::{print_error, print_output, print_single};
+use super::datetime::{parse_datetime, parse_duration, resolve_tz};
+use super::output::{print_error, print_output, print_single, print_success};
 use super::OutputFormat;
 
 #[derive(Args, Debug)]
@@ -57,6 +61,66 @@ pub enum UsersSubcommand {
         #[arg(long)]
         users: Option<String>,
     },
+
+    /// Set or clear your own availability and status message
+    Status {
+        #[command(subcommand)]
+        command: StatusSubcommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum StatusSubcommand {
+    /// Set your availability, overriding the automatic one
+    Set {
+        /// Availability to show
+        #[arg(short, long, value_enum)]
+        availability: Availability,
+
+        /// How long to keep it, 5m to 4h (30m, 2h); Teams' default when omitted
+        #[arg(long = "for", value_name = "DURATION")]
+        duration: Option<String>,
+    },
+
+    /// Set your status message
+    Message {
+        /// Message text
+        message: String,
+
+        /// When the message expires (5pm, tomorrow, +2h, ...)
+        #[arg(short, long)]
+        expires: Option<String>,
+    },
+
+    /// Reset availability to automatic and remove the status message
+    Clear,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Availability {
+    Available,
+    Busy,
+    /// Do not disturb
+    Dnd,
+    /// Be right back
+    Brb,
+    Away,
+    /// Appear offline
+    Offline,
+}
+
+impl Availability {
+    /// Graph availability and matching activity
+    fn graph(self) -> (&'static str, &'static str) {
+        match self {
+            Availability::Available => ("Available", "Available"),
+            Availability::Busy => ("Busy", "Busy"),
+            Availability::Dnd => ("DoNotDisturb", "DoNotDisturb"),
+            Availability::Brb => ("BeRightBack", "BeRightBack"),
+            Availability::Away => ("Away", "Away"),
+            Availability::Offline => ("Offline", "OffWork"),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Tabled)]
@@ -90,6 +154,16 @@ pub async fn execute(cmd: UsersCommand, config: &Config, format: OutputFormat) -
         UsersSubcommand::Me => me(config, format).await,
         UsersSubcommand::Search { query, limit } => search(config, &query, limit, format).await,
         UsersSubcommand::Presence { user, users } => presence(config, user, users, format).await,
+        UsersSubcommand::Status { command } => match command {
+            StatusSubcommand::Set {
+                availability,
+                duration,
+            } => status_set(config, availability, duration).await,
+            StatusSubcommand::Message { message, expires } => {
+                status_message(config, &message, expires).await
+            }
+            StatusSubcommand::Clear => status_clear(config).await,
+        },
     }
 }
 
@@ -308,3 +382,112 @@ fn format_availability(availability: Option<&str>) -> String {
         None => "-".to_string(),
     }
 }
+
+async fn status_set(
+    config: &Config,
+    availability: Availability,
+    duration: Option<String>,
+) -> Result<()> {
+    let client = TeamsClient::new(config)?;
+    let (availability, activity) = availability.graph();
+    let expiration = duration.as_deref().map(presence_expiration).transpose()?;
+
+    client
+        .set_preferred_presence(&SetPresenceRequest {
+            session_id: None,
+            availability: availability.to_string(),
+            activity: activity.to_string(),
+            expiration_duration: expiration,
+        })
+        .await?;
+
+    match duration {
+        Some(d) => print_success(&format!(
+            "Status set to {} for {}",
+            format_availability(Some(availability)),
+            d
+        )),
+        None => print_success(&format!(
+            "Status set to {}",
+            format_availability(Some(availability))
+        )),
+    }
+    Ok(())
+}
+
+/// ISO 8601 duration for a preferred presence, which Graph accepts from 5 to 240 minutes
+fn presence_expiration(duration: &str) -> Result<String> {
+    let minutes = parse_duration(duration)?.num_minutes();
+    if !(5..=240).contains(&minutes) {
+        bail!(
+            "--for must be between 5 minutes and 4 hours, got '{}'",
+            duration
+        );
+    }
+    Ok(format!("PT{}M", minutes))
+}
+
+async fn status_message(config: &Config, message: &str, expires: Option<String>) -> Result<()> {
+    let client = TeamsClient::new(config)?;
+    let tz = resolve_tz(None, config)?;
+    let expiry = expires
+        .as_deref()
+        .map(|e| parse_datetime(e, tz))
+        .transpose()?;
+
+    client
+        .set_status_message(&PresenceStatusMessage {
+            message: Some(PresenceMessageContent {
+                content: Some(message.to_string()),
+                content_type: Some("text".to_string()),
+            }),
+            expiry_date_time: expiry.map(|e| DateTimeZone {
+                date_time: e
+                    .with_timezone(&chrono::Utc)
+                    .format("%Y-%m-%dT%H:%M:%S")
+                    .to_string(),
+                time_zone: "UTC".to_string(),
+            }),
+        })
+        .await?;
+
+    match expiry {
+        Some(e) => print_success(&format!(
+            "Status message set until {}",
+            e.format("%Y-%m-%d %H:%M")
+        )),
+        None => print_success("Status message set"),
+    }
+    Ok(())
+}
+
+async fn status_clear(config: &Config) -> Result<()> {
+    let client = TeamsClient::new(config)?;
+    client.clear_preferred_presence().await?;
+    client
+        .set_status_message(&PresenceStatusMessage {
+            message: Some(PresenceMessageContent {
+                content: Some(String::new()),
+                content_type: Some("text".to_string()),
+            }),
+            expiry_date_time: None,
+        })
+        .await?;
+    print_success("Status reset to automatic and status message cleared");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presence_expiration() {
+        assert_eq!(presence_expiration("30m").unwrap(), "PT30M");
+        assert_eq!(presence_expiration("1h30m").unwrap(), "PT90M");
+        assert_eq!(presence_expiration("4h").unwrap(), "PT240M");
+        assert!(presence_expiration("30s").is_err());
+        assert!(presence_expiration("4m").is_err());
+        assert!(presence_expiration("1d").is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use anyhow::{anyhow, Result};
 use markdown;
 
 pub fn truncate(s: &str, max_len: usize) -> String {
@@ -18,11 +19,7 @@ pub fn strip_html(s: &str) -> String {
         match c {
             '<' => in_tag = true,
             '>' => in_tag = false,
-            '\n' | '\r' => {
-                if !in_tag {
-                    result.push(' ');
-                }
-            }
+            '\n' | '\r' if !in_tag => result.push(' '),
             _ if !in_tag => result.push(c),
             _ => {}
         }
@@ -43,6 +40,83 @@ pub fn strip_html(s: &str) -> String {
         .join(" ")
 }
 
+/// Convert an HTML body to readable text, keeping paragraphs, line breaks and list items
+pub fn html_to_text(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        push_text(&mut out, &rest[..start]);
+        let Some(len) = rest[start..].find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = &rest[start + 1..start + len];
+        rest = &rest[start + len + 1..];
+
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        match name.as_str() {
+            // Skip what is never displayed, up to the closing tag
+            "head" | "style" | "script" | "title" if !closing => {
+                let end = format!("</{}", name);
+                rest = rest
+                    .to_ascii_lowercase()
+                    .find(&end)
+                    .map_or("", |i| &rest[i..]);
+            }
+            "br" => out.push('\n'),
+            "li" if !closing => out.push_str("\n• "),
+            "p" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "blockquote" | "table" | "ul"
+            | "ol" => out.push_str("\n\n"),
+            "div" | "tr" | "hr" => out.push('\n'),
+            _ => {}
+        }
+    }
+    push_text(&mut out, rest);
+
+    // Trim every line and keep at most one blank line in a row
+    let mut text = String::new();
+    let mut blank = false;
+    for line in out.lines().map(str::trim) {
+        if line.is_empty() {
+            if blank || text.is_empty() {
+                continue;
+            }
+            blank = true;
+        } else {
+            blank = false;
+        }
+        text.push_str(line);
+        text.push('\n');
+    }
+    text.trim_end().to_string()
+}
+
+/// Append a run of HTML text, decoding entities and collapsing whitespace
+fn push_text(out: &mut String, text: &str) {
+    let decoded = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    for c in decoded.chars() {
+        if c.is_whitespace() {
+            if !out.is_empty() && !out.ends_with([' ', '\n']) {
+                out.push(' ');
+            }
+        } else {
+            out.push(c);
+        }
+    }
+}
+
 pub fn html_escape(s: &str) -> String {
     s.replace('&', "&amp;")
         .replace('<', "&lt;")
@@ -51,9 +125,16 @@ pub fn html_escape(s: &str) -> String {
         .replace('\'', "&#39;")
 }
 
+/// Stands in for `cid:` during conversion: the markdown crate blanks any image
+/// source that isn't http(s), which would drop inline (`--inline`) images
+const CID_PLACEHOLDER: &str = "https://cid.squads-cli.invalid/";
+
 pub fn markdown_to_html(content: &str) -> String {
+    let cid_destination =
+        regex::Regex::new(r"(?m)(\]\(\s*<?|^ {0,3}\[[^\]]+\]:[ \t]*<?)cid:").unwrap();
+    let content = cid_destination.replace_all(content, format!("${{1}}{}", CID_PLACEHOLDER));
     markdown::to_html_with_options(
-        content,
+        &content,
         &markdown::Options {
             parse: markdown::ParseOptions {
                 constructs: markdown::Constructs {
@@ -65,5 +146,40 @@ pub fn markdown_to_html(content: &str) -> String {
             ..markdown::Options::gfm()
         },
     )
+    .map(|html| html.replace(CID_PLACEHOLDER, "cid:"))
     .unwrap_or_else(|_| content.to_string())
 }
+
+/// Parse a `key=value` argument (used by `--input`, `--var`...)
+pub fn parse_key_value(s: &str) -> Result<(String, String)> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or_else(|| anyhow!("Invalid value '{}': expected key=value", s))?;
+    Ok((key.trim().to_string(), value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_to_text() {
+        let html = "<html><head><style>p { color: red; }</style></head><body>\
+            <p>Hi&nbsp;Alice,</p>\r\n<p>Two   items:</p><ul><li>one</li><li>two &amp; three</li></ul>\
+            <div>Thanks<br>Bob</div></body></html>";
+        assert_eq!(
+            html_to_text(html),
+            "Hi Alice,\n\nTwo items:\n\n• one\n• two & three\n\nThanks\nBob"
+        );
+    }
+
+    #[test]
+    fn test_markdown_keeps_cid_images() {
+        let html =
+            markdown_to_html("Logo: ![logo](cid:logo.png)\n\n![chart][c]\n\n[c]: cid:chart.png");
+        assert!(html.contains(r#"<img src="cid:logo.png" alt="logo" />"#));
+        assert!(html.contains(r#"<img src="cid:chart.png" alt="chart" />"#));
+        // Other non-http sources are still blanked
+        assert!(markdown_to_html("![x](javascript:alert(1))").contains(r#"src="""#));
+    }
+}
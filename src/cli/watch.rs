@@ -1,12 +1,24 @@
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use clap::{Args, ValueEnum};
 use colored::Colorize;
 use std::collections::HashSet;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crate::api::TeamsClient;
+use crate::cli::calendar::event_join_url;
+use crate::cli::datetime::{graph_time_utc, now_in, resolve_tz, to_graph_utc};
+use crate::cli::mailsync;
+use crate::cli::output::{print_error, print_warning};
 use crate::cli::utils::{strip_html, truncate};
 use crate::config::Config;
+use crate::types::CalendarEvent;
+
+/// How often upcoming meetings are re-fetched
+const MEETINGS_REFRESH: Duration = Duration::from_secs(60);
+/// How long after a meeting starts `--auto-open` may still open it
+const AUTO_OPEN_GRACE_MINUTES: i64 = 5;
 
 #[derive(Args, Debug)]
 pub struct WatchCommand {
@@ -29,6 +41,22 @@ pub struct WatchCommand {
     /// Specific chat ID to watch (can be repeated)
     #[arg(long)]
     pub chat: Vec<String>,
+
+    /// Minutes before a meeting to remind you (default: `[calendar] reminder_minutes`)
+    #[arg(long)]
+    pub remind: Option<u64>,
+
+    /// Open the Teams join link when a meeting starts
+    #[arg(long)]
+    pub auto_open: bool,
+
+    /// Show you as busy in Teams while a meeting is in progress
+    #[arg(long)]
+    pub follow_calendar: bool,
+
+    /// Shared or delegated mailbox to watch (repeatable; "me" for your own, the default)
+    #[arg(long)]
+    pub mailbox: Vec<String>,
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum, Default)]
@@ -40,6 +68,21 @@ pub enum WatchSource {
     Chats,
     /// Watch only emails
     Mail,
+    /// Watch only upcoming meetings
+    Meetings,
+}
+
+/// Upcoming meetings and the reminders already sent for them
+#[derive(Default)]
+struct MeetingWatch {
+    events: Vec<CalendarEvent>,
+    fetched: Option<Instant>,
+    reminded: HashSet<String>,
+    opened: HashSet<String>,
+    /// Meeting currently mirrored as this session's presence
+    presence_for: Option<String>,
+    /// A presence update failed and the user was told so
+    presence_warned: bool,
 }
 
 pub async fn execute(cmd: WatchCommand, config: &Config) -> Result<()> {
@@ -57,11 +100,24 @@ pub async fn execute(cmd: WatchCommand, config: &Config) -> Result<()> {
     if cmd.notify {
         println!("Desktop notifications: {}", "enabled".green());
     }
+    let watch_meetings =
+        matches!(cmd.source, WatchSource::All | WatchSource::Meetings) || cmd.follow_calendar;
+    let remind_minutes = cmd.remind.unwrap_or(config.calendar.reminder_minutes);
+    let tz = resolve_tz(None, config)?;
+    if watch_meetings {
+        println!(
+            "Meeting reminders: {} min before start{}",
+            remind_minutes,
+            if cmd.auto_open { ", auto-open" } else { "" }
+        );
+    }
+    if cmd.follow_calendar {
+        println!("Presence: {}", "busy during meetings".green());
+    }
     println!();
 
-    // Track seen message/email IDs to avoid duplicates
+    // Track seen message IDs to avoid duplicates
     let mut seen_messages: HashSet<String> = HashSet::new();
-    let mut seen_emails: HashSet<String> = HashSet::new();
 
     // Initial load to populate seen items
     if matches!(cmd.source, WatchSource::All | WatchSource::Chats) {
@@ -81,13 +137,19 @@ pub async fn execute(cmd: WatchCommand, config: &Config) -> Result<()> {
         }
     }
 
+    // One client per watched mailbox; mail changes since the previous watch run
+    // are reported right away
+    let mut mailboxes: Vec<TeamsClient> = Vec::new();
     if matches!(cmd.source, WatchSource::All | WatchSource::Mail) {
-        if let Ok(emails) = client.get_mail_messages(Some("inbox"), 50).await {
-            for email in emails.value {
-                if let Some(id) = &email.id {
-                    seen_emails.insert(id.clone());
-                }
-            }
+        if cmd.mailbox.is_empty() {
+            mailboxes.push(TeamsClient::new(config)?);
+        }
+        for mailbox in &cmd.mailbox {
+            let own = mailbox.eq_ignore_ascii_case("me");
+            mailboxes.push(TeamsClient::new(config)?.with_mailbox((!own).then_some(mailbox)));
+        }
+        for mail_client in &mailboxes {
+            check_new_emails(mail_client, &cmd).await;
         }
     }
 
@@ -99,8 +161,15 @@ pub async fn execute(cmd: WatchCommand, config: &Config) -> Result<()> {
         println!();
     }
 
+    let mut meetings = MeetingWatch::default();
+
     // Main watch loop
     loop {
+        // Check upcoming meetings first so that reminders due right away are not delayed
+        if watch_meetings {
+            check_meetings(&client, &mut meetings, &cmd, remind_minutes, tz).await;
+        }
+
         tokio::time::sleep(Duration::from_secs(cmd.interval)).await;
 
         // Check for new chat messages
@@ -109,8 +178,8 @@ pub async fn execute(cmd: WatchCommand, config: &Config) -> Result<()> {
         }
 
         // Check for new emails
-        if matches!(cmd.source, WatchSource::All | WatchSource::Mail) {
-            check_new_emails(&client, &mut seen_emails, &cmd).await;
+        for mail_client in &mailboxes {
+            check_new_emails(mail_client, &cmd).await;
         }
     }
 }
@@ -198,24 +267,37 @@ async fn check_new_messages(
     }
 }
 
-async fn check_new_emails(client: &TeamsClient, seen: &mut HashSet<String>, cmd: &WatchCommand) {
-    let emails = match client.get_mail_messages(Some("inbox"), 20).await {
-        Ok(e) => e,
-        Err(_) => return,
+/// Report inbox changes of the client's mailbox since the last sync, which is
+/// kept in the cache dir so nothing is missed across restarts
+async fn check_new_emails(client: &TeamsClient, cmd: &WatchCommand) {
+    let changes = match mailsync::sync_folder(client, "watch", "inbox").await {
+        Ok(c) => c,
+        Err(e) => {
+            print_error(&format!("Mail sync failed: {:#}", e));
+            return;
+        }
     };
-
-    for email in emails.value {
-        let email_id = match &email.id {
-            Some(id) => id.clone(),
-            None => continue,
-        };
-
-        if seen.contains(&email_id) {
-            continue;
+    let mailbox = client.mailbox();
+    if changes.initial {
+        if !cmd.quiet {
+            println!(
+                "{}",
+                format!(
+                    "Tracking inbox changes{}",
+                    mailbox.map(|m| format!(" in {}", m)).unwrap_or_default()
+                )
+                .dimmed()
+            );
         }
+        return;
+    }
 
-        seen.insert(email_id);
-
+    let stamp = format!(
+        "[{}]{}",
+        chrono::Local::now().format("%H:%M:%S"),
+        mailbox.map(|m| format!(" [{}]", m)).unwrap_or_default()
+    );
+    for email in &changes.new {
         // Only notify for unread emails
         if email.is_read == Some(true) {
             continue;
@@ -237,27 +319,190 @@ async fn check_new_emails(client: &TeamsClient, seen: &mut HashSet<String>, cmd:
             .clone()
             .unwrap_or_else(|| "(No subject)".to_string());
 
-        let time = chrono::Local::now().format("%H:%M:%S").to_string();
-
         if !cmd.quiet {
             println!(
                 "{} 📧 {} {}",
-                format!("[{}]", time).dimmed(),
+                stamp.dimmed(),
                 format!("{}:", sender).yellow().bold(),
                 truncate(&subject, 60)
             );
         }
 
         if cmd.notify {
-            send_notification(
-                &format!("Email: {}", sender),
-                &truncate(&subject, 100),
-                "mail",
-            );
+            let title = match mailbox {
+                Some(mailbox) => format!("Email ({}): {}", mailbox, sender),
+                None => format!("Email: {}", sender),
+            };
+            send_notification(&title, &truncate(&subject, 100), "mail");
+        }
+    }
+
+    if cmd.quiet {
+        return;
+    }
+    for email in &changes.read_changed {
+        let state = if email.is_read == Some(true) {
+            "read"
+        } else {
+            "unread"
+        };
+        println!(
+            "{} {}",
+            format!("{} ✉  marked {}:", stamp, state).dimmed(),
+            truncate(email.subject.as_deref().unwrap_or("(No subject)"), 60)
+        );
+    }
+    if !changes.removed.is_empty() {
+        println!(
+            "{}",
+            format!("{} 🗑  {} message(s) removed", stamp, changes.removed.len()).dimmed()
+        );
+    }
+}
+
+async fn check_meetings(
+    client: &TeamsClient,
+    watch: &mut MeetingWatch,
+    cmd: &WatchCommand,
+    remind_minutes: u64,
+    tz: Tz,
+) {
+    let remind = chrono::Duration::minutes(remind_minutes as i64);
+    let now = now_in(tz);
+
+    if watch
+        .fetched
+        .is_none_or(|t| t.elapsed() >= MEETINGS_REFRESH)
+    {
+        let start = now - chrono::Duration::minutes(AUTO_OPEN_GRACE_MINUTES);
+        let end = now + remind + chrono::Duration::hours(1);
+        if let Ok(events) = client
+            .get_calendar_events(&to_graph_utc(&start), &to_graph_utc(&end))
+            .await
+        {
+            watch.events = events.value;
+            watch.fetched = Some(Instant::now());
+        }
+    }
+
+    if cmd.follow_calendar {
+        follow_calendar(client, watch, now.with_timezone(&chrono::Utc)).await;
+    }
+    if !matches!(cmd.source, WatchSource::All | WatchSource::Meetings) {
+        return;
+    }
+
+    for event in watch.events.iter().filter(|e| attending(e)) {
+        let (Some(id), Some(start)) = (&event.id, event.start.as_ref().and_then(graph_time_utc))
+        else {
+            continue;
+        };
+        let start = start.with_timezone(&tz);
+        // Keyed by start too, so a rescheduled meeting is reminded again
+        let key = format!("{}@{}", id, start.timestamp());
+        let subject = event
+            .subject
+            .clone()
+            .unwrap_or_else(|| "(No subject)".to_string());
+        let join_url = event_join_url(event);
+
+        if now < start && now >= start - remind && watch.reminded.insert(key.clone()) {
+            let minutes = (start - now).num_minutes().max(1);
+            let when = format!("Starts at {} (in {} min)", start.format("%H:%M"), minutes);
+
+            if !cmd.quiet {
+                println!(
+                    "{} 📅 {} {}",
+                    format!("[{}]", chrono::Local::now().format("%H:%M:%S")).dimmed(),
+                    format!("{}:", truncate(&subject, 60)).magenta().bold(),
+                    when
+                );
+                if let Some(url) = &join_url {
+                    println!("   {}", format!("Join: {}", url).dimmed());
+                }
+            }
+
+            if cmd.notify {
+                let body = match &join_url {
+                    Some(url) => format!("{}\nJoin: {}", when, url),
+                    None => when,
+                };
+                send_notification(&format!("Meeting: {}", subject), &body, "meeting");
+            }
+        }
+
+        let grace = chrono::Duration::minutes(AUTO_OPEN_GRACE_MINUTES);
+        if cmd.auto_open && now >= start && now < start + grace && watch.opened.insert(key) {
+            if let Some(url) = &join_url {
+                if let Err(e) = open::that(url) {
+                    eprintln!("Failed to open meeting link: {}", e);
+                } else if !cmd.quiet {
+                    println!(
+                        "{} 📅 {} {}",
+                        format!("[{}]", chrono::Local::now().format("%H:%M:%S")).dimmed(),
+                        "Joining".magenta().bold(),
+                        truncate(&subject, 60)
+                    );
+                }
+            }
         }
     }
 }
 
+/// Whether the user is expected at a meeting: not cancelled, not declined, not all-day
+fn attending(event: &CalendarEvent) -> bool {
+    let declined = event
+        .response_status
+        .as_ref()
+        .and_then(|r| r.response.as_deref())
+        == Some("declined");
+    event.is_cancelled != Some(true) && !declined && event.is_all_day != Some(true)
+}
+
+/// Mirror an in-progress meeting as this app's presence session, and clear it afterwards
+async fn follow_calendar(client: &TeamsClient, watch: &mut MeetingWatch, now: DateTime<Utc>) {
+    let current = watch
+        .events
+        .iter()
+        .filter(|e| attending(e) && e.show_as.as_deref() != Some("free"))
+        .find_map(|e| {
+            let start = e.start.as_ref().and_then(graph_time_utc)?;
+            let end = e.end.as_ref().and_then(graph_time_utc)?;
+            let key = format!("{}@{}", e.id.as_ref()?, start.timestamp());
+            (start <= now && now < end).then_some((key, end))
+        });
+
+    if let Some((key, end)) = current {
+        if watch.presence_for.as_ref() == Some(&key) {
+            return;
+        }
+        // Graph accepts session expirations between 5 minutes and 4 hours
+        let minutes = (end - now).num_minutes().clamp(5, 240);
+        let expiration = Some(format!("PT{}M", minutes));
+        // Busy/InAConferenceCall is the closest pair setPresence accepts to "in a meeting"
+        match client
+            .set_session_presence("Busy", "InAConferenceCall", expiration)
+            .await
+        {
+            Ok(()) => watch.presence_for = Some(key),
+            Err(e) => warn_presence(watch, &e),
+        }
+    } else if watch.presence_for.is_some() {
+        match client.clear_session_presence().await {
+            Ok(()) => watch.presence_for = None,
+            Err(e) => warn_presence(watch, &e),
+        }
+    }
+}
+
+/// Report the first failed presence update; later ones would repeat it every poll
+fn warn_presence(watch: &mut MeetingWatch, error: &anyhow::Error) {
+    if !watch.presence_warned {
+        watch.presence_warned = true;
+        print_warning(&format!("Could not update presence: {}", error));
+    }
+}
+
 fn send_notification(title: &str, body: &str, _category: &str) {
     #[cfg(not(target_os = "windows"))]
     {
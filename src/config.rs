@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -11,6 +12,86 @@ pub struct Config {
     pub auth: AuthConfig,
     #[serde(default)]
     pub update: UpdateConfig,
+    #[serde(default)]
+    pub calendar: CalendarConfig,
+    #[serde(default)]
+    pub signatures: SignatureConfig,
+    #[serde(default)]
+    pub tui: TuiConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TuiConfig {
+    /// Directory mail attachments are saved to (default: your Downloads folder)
+    #[serde(default)]
+    pub download_dir: Option<PathBuf>,
+}
+
+/// Signatures appended to outgoing mail
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SignatureConfig {
+    /// Profile used for mail from your own mailbox
+    #[serde(default)]
+    pub default: Option<String>,
+    /// Profile used for each shared or delegated mailbox, by address
+    #[serde(default)]
+    pub mailboxes: HashMap<String, String>,
+    /// Named signatures
+    #[serde(default)]
+    pub profiles: HashMap<String, Signature>,
+}
+
+/// A signature, written in Markdown or HTML
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Signature {
+    #[serde(default)]
+    pub markdown: Option<String>,
+    #[serde(default)]
+    pub html: Option<String>,
+}
+
+impl SignatureConfig {
+    /// The named profile, or else the one set for `mailbox` (your own when `None`)
+    pub fn find(&self, mailbox: Option<&str>, profile: Option<&str>) -> Result<Option<&Signature>> {
+        let name = match (profile, mailbox) {
+            (Some(profile), _) => Some(profile),
+            (None, Some(mailbox)) => self
+                .mailboxes
+                .iter()
+                .find(|(address, _)| address.eq_ignore_ascii_case(mailbox))
+                .map(|(_, name)| name.as_str()),
+            (None, None) => self.default.as_deref(),
+        };
+        name.map(|name| {
+            self.profiles
+                .get(name)
+                .with_context(|| format!("No signature profile '{}' in the config", name))
+        })
+        .transpose()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarConfig {
+    /// IANA time zone used for dates and times, e.g. "Europe/Paris" (default: system zone)
+    #[serde(default)]
+    pub timezone: Option<String>,
+    /// Minutes before a meeting starts to send a reminder in watch mode (default: 5)
+    #[serde(default = "default_reminder_minutes")]
+    pub reminder_minutes: u64,
+}
+
+fn default_reminder_minutes() -> u64 {
+    5
+}
+
+impl Default for CalendarConfig {
+    fn default() -> Self {
+        Self {
+            timezone: None,
+            reminder_minutes: default_reminder_minutes(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,6 +152,12 @@ impl Config {
         Ok(dirs.config_dir().join("config.toml"))
     }
 
+    /// Get the directory holding message templates
+    pub fn templates_dir() -> Result<PathBuf> {
+        let dirs = Self::project_dirs().context("Could not determine config directory")?;
+        Ok(dirs.config_dir().join("templates"))
+    }
+
     /// Get the cache directory
     pub fn cache_dir() -> Result<PathBuf> {
         let dirs = Self::project_dirs().context("Could not determine cache directory")?;
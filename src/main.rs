@@ -42,6 +42,8 @@ async fn main() -> Result<()> {
         Commands::Chats(cmd) => cli::chats::execute(cmd, &config, cli.format).await,
         Commands::Teams(cmd) => cli::teams::execute(cmd, &config, cli.format).await,
         Commands::Users(cmd) => cli::users::execute(cmd, &config, cli.format).await,
+        Commands::Contacts(cmd) => cli::contacts::execute(cmd, &config, cli.format).await,
+        Commands::People(cmd) => cli::people::execute(cmd, &config, cli.format).await,
         Commands::Activity(cmd) => cli::activity::execute(cmd, &config, cli.format).await,
         Commands::Mail(cmd) => cli::mail::execute(cmd, &config, cli.format).await,
         Commands::Notes(cmd) => cli::notes::execute(cmd, &config, cli.format).await,
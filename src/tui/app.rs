@@ -1,5 +1,6 @@
 use std::collections::HashMap;
-use std::io;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -13,11 +14,16 @@ use ratatui::{backend::CrosstermBackend, Terminal};
 use tokio::sync::Mutex;
 
 use crate::api::TeamsClient;
+use crate::cli::mail::walk_folders;
+use crate::cli::mailsync;
 use crate::config::Config;
-use crate::types::{Chat, MailMessage, Message, Team};
+use crate::types::{Chat, MailAttachment, MailFilter, MailFolder, MailMessage, Message, Team};
 
 use super::ui;
 
+/// Messages loaded per mail folder
+const MAIL_LIMIT: usize = 50;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Panel {
     Chats,
@@ -29,6 +35,82 @@ pub enum Panel {
 pub enum LeftPanelView {
     Chats,
     Channels,
+    Mail,
+}
+
+/// What the input box writes in the Mail view
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MailCompose {
+    Reply,
+    ReplyAll,
+    Forward,
+}
+
+/// What a Normal-mode key does in the Mail view
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MailKey {
+    Help,
+    Compose(MailCompose),
+    Archive,
+    Delete,
+    ToggleRead,
+    SaveAttachments,
+    ScrollDown,
+    ScrollUp,
+    Reload,
+    /// `i` outside the message list: there is nothing to reply to, and the input
+    /// box must not fall back to the last open chat
+    NothingToCompose,
+}
+
+/// The Mail view meaning of a Normal-mode key, if it has one; other keys
+/// (navigation, view switching...) work as in the other views
+pub fn mail_key(key: KeyCode, in_mail_list: bool) -> Option<MailKey> {
+    let action = match key {
+        KeyCode::Char('?') => MailKey::Help,
+        KeyCode::Char('r') | KeyCode::Char('i') if in_mail_list => {
+            MailKey::Compose(MailCompose::Reply)
+        }
+        KeyCode::Char('R') if in_mail_list => MailKey::Compose(MailCompose::ReplyAll),
+        KeyCode::Char('f') if in_mail_list => MailKey::Compose(MailCompose::Forward),
+        KeyCode::Char('a') if in_mail_list => MailKey::Archive,
+        KeyCode::Char('d') if in_mail_list => MailKey::Delete,
+        KeyCode::Char('u') if in_mail_list => MailKey::ToggleRead,
+        KeyCode::Char('s') if in_mail_list => MailKey::SaveAttachments,
+        KeyCode::Char('r') => MailKey::Reload,
+        KeyCode::Char('i') => MailKey::NothingToCompose,
+        KeyCode::PageDown | KeyCode::Char(' ') => MailKey::ScrollDown,
+        KeyCode::PageUp => MailKey::ScrollUp,
+        _ => return None,
+    };
+    Some(action)
+}
+
+/// Where to save an attachment in `dir`: the bare file name, never a path chosen
+/// by the sender, numbered so that an existing file is never overwritten
+pub fn attachment_path(dir: &Path, name: &str, fallback: &str) -> PathBuf {
+    let base = name.rsplit(['/', '\\']).next().unwrap_or_default().trim();
+    let base = match base {
+        "" | "." | ".." => fallback,
+        base => base,
+    };
+
+    let path = dir.join(base);
+    if !path.exists() {
+        return path;
+    }
+    let stem = Path::new(base)
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = Path::new(base)
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    (1..)
+        .map(|n| dir.join(format!("{} ({}){}", stem, n, extension)))
+        .find(|candidate| !candidate.exists())
+        .expect("some numbered name is free")
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -66,6 +148,17 @@ pub struct App {
     // User name cache (user_id -> display_name)
     pub user_names: HashMap<String, String>,
     pub my_user_id: Option<String>,
+    // Mail view
+    pub mail_folders: Vec<(MailFolder, usize)>, // folder and its depth in the tree
+    pub selected_folder: usize,
+    pub current_folder: String,
+    pub current_folder_name: String,
+    pub selected_email: usize,
+    pub open_email: Option<MailMessage>,
+    pub open_attachments: Vec<MailAttachment>,
+    pub mail_scroll: u16,
+    pub mail_compose: Option<(MailCompose, String)>, // kind and message ID
+    pub download_dir: PathBuf,
 }
 
 impl App {
@@ -82,7 +175,9 @@ impl App {
             input: String::new(),
             input_cursor: 0,
             command_input: String::new(),
-            status_message: String::from("Press ? for help | 1: Chats | 2: Channels | q to quit"),
+            status_message: String::from(
+                "Press ? for help | 1: Chats | 2: Channels | 3: Mail | q to quit",
+            ),
             should_quit: false,
             unread_emails: 0,
             unread_messages: 0,
@@ -98,6 +193,17 @@ impl App {
             // User cache
             user_names: HashMap::new(),
             my_user_id: None,
+            // Mail view
+            mail_folders: Vec::new(),
+            selected_folder: 0,
+            current_folder: "inbox".to_string(),
+            current_folder_name: "Inbox".to_string(),
+            selected_email: 0,
+            open_email: None,
+            open_attachments: Vec::new(),
+            mail_scroll: 0,
+            mail_compose: None,
+            download_dir: PathBuf::from("."),
         }
     }
 
@@ -163,25 +269,34 @@ impl App {
             }
         }
 
-        // Load emails (just count unread)
-        match self.client.get_mail_messages(Some("inbox"), 50).await {
-            Ok(msgs) => {
-                self.unread_emails = msgs
-                    .value
-                    .iter()
-                    .filter(|m| m.is_read != Some(true))
-                    .count();
-                self.emails = msgs.value;
+        // Delta-sync the inbox so the list is refetched only when something changed
+        match mailsync::sync_folder(&self.client, "tui", "inbox").await {
+            Ok(changes) => {
+                if let Ok(inbox) = self.client.get_mail_folder("inbox").await {
+                    self.unread_emails = inbox.unread_item_count.unwrap_or(0).max(0) as usize;
+                }
+                let changed = changes.initial || !changes.is_empty();
+                if self.current_folder == "inbox" && (changed || self.emails.is_empty()) {
+                    if let Ok(msgs) = self
+                        .client
+                        .get_mail_messages(Some("inbox"), &MailFilter::default(), MAIL_LIMIT)
+                        .await
+                    {
+                        self.emails = msgs.value;
+                    }
+                }
             }
-            Err(_) => {
-                // Silently ignore mail errors
+            Err(e) => {
+                self.loading = false;
+                self.status_message = format!("Mail sync failed: {:#}", e);
+                return Ok(());
             }
         }
 
         self.loading = false;
         let channel_count: usize = self.teams.iter().map(|t| t.channels.len()).sum();
         self.status_message = format!(
-            "{} chats | {} channels | {} unread emails | 1/2: toggle view | ? for help",
+            "{} chats | {} channels | {} unread emails | 1/2/3: switch view | ? for help",
             self.chats.len(),
             channel_count,
             self.unread_emails
@@ -265,7 +380,8 @@ impl App {
                         .filter(|m| {
                             // Filter by message type
                             let is_content_msg = m.message_type.as_deref() == Some("RichText/Html")
-                                || m.message_type.as_deref() == Some("Text");
+                                || m.message_type.as_deref() == Some("Text")
+                                || m.properties.as_ref().is_some_and(|p| p.cards.is_some());
                             // Filter out deleted messages (deletetime > 0)
                             let is_deleted = m
                                 .properties
@@ -316,7 +432,8 @@ impl App {
                             .filter(|m| {
                                 let is_content_msg = m.message_type.as_deref()
                                     == Some("RichText/Html")
-                                    || m.message_type.as_deref() == Some("Text");
+                                    || m.message_type.as_deref() == Some("Text")
+                                    || m.properties.as_ref().is_some_and(|p| p.cards.is_some());
                                 let is_deleted = m
                                     .properties
                                     .as_ref()
@@ -346,11 +463,362 @@ impl App {
         Ok(())
     }
 
+    /// Load the mail folder tree for the Mail view
+    pub async fn load_mail_folders(&mut self) -> Result<()> {
+        self.loading = true;
+        self.status_message = "Loading mail folders...".to_string();
+
+        match walk_folders(&self.client).await {
+            Ok(folders) => {
+                self.mail_folders = folders
+                    .into_iter()
+                    .map(|(folder, depth, _)| (folder, depth))
+                    .collect();
+                self.selected_folder = self
+                    .selected_folder
+                    .min(self.mail_folders.len().saturating_sub(1));
+                self.status_message = format!(
+                    "{} folders | Enter: open folder | ? for help",
+                    self.mail_folders.len()
+                );
+            }
+            Err(e) => {
+                self.status_message = format!("Error loading folders: {}", e);
+            }
+        }
+
+        self.loading = false;
+        Ok(())
+    }
+
+    /// Open the folder selected in the Mail view
+    pub async fn load_mail_messages(&mut self) -> Result<()> {
+        if let Some((folder, _)) = self.mail_folders.get(self.selected_folder) {
+            self.current_folder = folder.id.clone();
+            self.current_folder_name = folder.display_name.clone();
+            self.selected_email = 0;
+            self.open_email = None;
+            self.open_attachments.clear();
+        }
+        self.reload_mail().await
+    }
+
+    /// Reload the messages of the current mail folder
+    pub async fn reload_mail(&mut self) -> Result<()> {
+        self.loading = true;
+        self.status_message = format!("Loading {}...", self.current_folder_name);
+
+        match self
+            .client
+            .get_mail_messages(
+                Some(&self.current_folder),
+                &MailFilter::default(),
+                MAIL_LIMIT,
+            )
+            .await
+        {
+            Ok(msgs) => {
+                self.emails = msgs.value;
+                self.selected_email = self.selected_email.min(self.emails.len().saturating_sub(1));
+                self.status_message = format!(
+                    "{} | {} messages | Enter: read | ? for help",
+                    self.current_folder_name,
+                    self.emails.len()
+                );
+            }
+            Err(e) => {
+                self.status_message = format!("Error: {}", e);
+            }
+        }
+
+        self.loading = false;
+        Ok(())
+    }
+
+    /// Whether keys go to the mail message list
+    pub fn in_mail_list(&self) -> bool {
+        self.left_panel_view == LeftPanelView::Mail && self.active_panel == Panel::Messages
+    }
+
+    fn selected_email_id(&self) -> Option<String> {
+        self.emails.get(self.selected_email)?.id.clone()
+    }
+
+    /// Show the selected message in the reading pane, marking it read
+    pub async fn read_email(&mut self) -> Result<()> {
+        let Some(id) = self.selected_email_id() else {
+            return Ok(());
+        };
+        self.loading = true;
+        self.status_message = "Loading message...".to_string();
+
+        match self.client.get_mail_message(&id).await {
+            Ok(message) => {
+                self.open_attachments = if message.has_attachments == Some(true) {
+                    self.client
+                        .get_mail_attachments(&id)
+                        .await
+                        .map(|a| a.value)
+                        .unwrap_or_default()
+                } else {
+                    Vec::new()
+                };
+                let unread = message.is_read != Some(true);
+                self.open_email = Some(message);
+                self.mail_scroll = 0;
+                self.status_message =
+                    "r/R: reply/all | f: forward | a: archive | d: delete | u: unread | s: save attachments"
+                        .to_string();
+                if unread {
+                    self.mark_email(true).await?;
+                }
+            }
+            Err(e) => {
+                self.status_message = format!("Error: {}", e);
+            }
+        }
+
+        self.loading = false;
+        Ok(())
+    }
+
+    /// Mark the selected message read or unread
+    pub async fn mark_email(&mut self, is_read: bool) -> Result<()> {
+        let Some(id) = self.selected_email_id() else {
+            return Ok(());
+        };
+
+        match self.client.mark_mail(&id, is_read).await {
+            Ok(()) => {
+                if let Some(message) = self.emails.get_mut(self.selected_email) {
+                    // The unread count covers the inbox only
+                    if self.current_folder == "inbox" && message.is_read != Some(is_read) {
+                        if is_read {
+                            self.unread_emails = self.unread_emails.saturating_sub(1);
+                        } else {
+                            self.unread_emails += 1;
+                        }
+                    }
+                    message.is_read = Some(is_read);
+                }
+                if let Some(open) = self
+                    .open_email
+                    .as_mut()
+                    .filter(|m| m.id.as_deref() == Some(id.as_str()))
+                {
+                    open.is_read = Some(is_read);
+                }
+            }
+            Err(e) => {
+                self.status_message = format!("Mark failed: {}", e);
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn toggle_email_read(&mut self) -> Result<()> {
+        let is_read = self
+            .emails
+            .get(self.selected_email)
+            .is_some_and(|m| m.is_read == Some(true));
+        self.mark_email(!is_read).await?;
+        self.status_message = if is_read {
+            "Marked as unread".to_string()
+        } else {
+            "Marked as read".to_string()
+        };
+        Ok(())
+    }
+
+    /// Move the selected message to another folder (well-known name or ID)
+    pub async fn move_email(&mut self, folder: &str, done: &str) -> Result<()> {
+        let Some(id) = self.selected_email_id() else {
+            return Ok(());
+        };
+
+        match self.client.move_mail(&id, folder).await {
+            Ok(_) => {
+                self.emails.remove(self.selected_email);
+                self.selected_email = self.selected_email.min(self.emails.len().saturating_sub(1));
+                if self.open_email.as_ref().and_then(|m| m.id.as_deref()) == Some(id.as_str()) {
+                    self.open_email = None;
+                    self.open_attachments.clear();
+                }
+                self.status_message = done.to_string();
+            }
+            Err(e) => {
+                self.status_message = format!("Move failed: {}", e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Save the open message's attachments to the download directory
+    pub async fn save_attachments(&mut self) -> Result<()> {
+        let Some(message_id) = self.open_email.as_ref().and_then(|m| m.id.clone()) else {
+            self.status_message = "Open a message first (Enter)".to_string();
+            return Ok(());
+        };
+        let attachment_ids: Vec<String> = self
+            .open_attachments
+            .iter()
+            .filter(|a| a.is_inline != Some(true))
+            .filter_map(|a| a.id.clone())
+            .collect();
+        if attachment_ids.is_empty() {
+            self.status_message = "No attachments".to_string();
+            return Ok(());
+        }
+
+        self.loading = true;
+        if let Err(e) = std::fs::create_dir_all(&self.download_dir) {
+            self.status_message = format!("Download failed: {}", e);
+            self.loading = false;
+            return Ok(());
+        }
+        let mut saved = Vec::new();
+        for attachment_id in attachment_ids {
+            let result = self
+                .client
+                .download_attachment(&message_id, &attachment_id)
+                .await
+                .and_then(|(name, content)| {
+                    let path = attachment_path(&self.download_dir, &name, &attachment_id);
+                    std::fs::OpenOptions::new()
+                        .write(true)
+                        .create_new(true)
+                        .open(&path)?
+                        .write_all(&content)?;
+                    Ok(path.display().to_string())
+                });
+            match result {
+                Ok(name) => saved.push(name),
+                Err(e) => {
+                    self.status_message = format!("Download failed: {}", e);
+                    self.loading = false;
+                    return Ok(());
+                }
+            }
+        }
+        self.loading = false;
+        self.status_message = format!("Saved {}", saved.join(", "));
+        Ok(())
+    }
+
+    /// Run a Mail view key
+    pub async fn handle_mail_key(&mut self, key: MailKey) -> Result<()> {
+        match key {
+            MailKey::Help => {
+                self.status_message = "j/k: navigate | Enter: open | r/i: reply | R: reply all | f: forward | a: archive | d: delete | u: read/unread | s: save attachments | PgUp/PgDn: scroll".to_string();
+            }
+            MailKey::Compose(compose) => self.start_mail_compose(compose),
+            MailKey::Archive => self.move_email("archive", "Archived").await?,
+            MailKey::Delete => {
+                self.move_email("deleteditems", "Moved to Deleted Items")
+                    .await?
+            }
+            MailKey::ToggleRead => self.toggle_email_read().await?,
+            MailKey::SaveAttachments => self.save_attachments().await?,
+            MailKey::ScrollDown => self.mail_scroll = self.mail_scroll.saturating_add(10),
+            MailKey::ScrollUp => self.mail_scroll = self.mail_scroll.saturating_sub(10),
+            MailKey::Reload => self.reload_mail().await?,
+            MailKey::NothingToCompose => {
+                self.status_message =
+                    "Select a message, then r to reply or f to forward".to_string();
+            }
+        }
+        Ok(())
+    }
+
+    /// Write a reply or forward of the selected message in the input box
+    pub fn start_mail_compose(&mut self, compose: MailCompose) {
+        let Some(id) = self.selected_email_id() else {
+            return;
+        };
+        self.mail_compose = Some((compose, id));
+        self.mode = Mode::Insert;
+        self.active_panel = Panel::Input;
+        self.status_message = match compose {
+            MailCompose::Forward => {
+                "-- FORWARD -- first line: recipients, then a comment (Esc: cancel, Enter: send, F2: newline)"
+            }
+            _ => "-- REPLY -- (Esc: cancel, Enter: send, F2: newline)",
+        }
+        .to_string();
+    }
+
+    async fn send_mail_compose(&mut self, compose: MailCompose, message_id: &str) -> Result<()> {
+        let text = self.input.trim().to_string();
+        let result = match compose {
+            MailCompose::Reply | MailCompose::ReplyAll => {
+                self.client
+                    .reply_mail(
+                        message_id,
+                        &text,
+                        "Text",
+                        compose == MailCompose::ReplyAll,
+                        None,
+                        None,
+                        None,
+                    )
+                    .await
+            }
+            MailCompose::Forward => {
+                let (to, comment) = text.split_once('\n').unwrap_or((&text, ""));
+                let to: Vec<&str> = to
+                    .split([',', ';'])
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .collect();
+                if to.is_empty() || to.iter().any(|address| !address.contains('@')) {
+                    self.status_message =
+                        "Put the recipients' email addresses on the first line".to_string();
+                    return Ok(());
+                }
+                let comment = comment.trim();
+                self.client
+                    .forward_mail(
+                        message_id,
+                        to,
+                        (!comment.is_empty()).then_some(comment),
+                        None,
+                    )
+                    .await
+            }
+        };
+
+        match result {
+            Ok(()) => {
+                self.mail_compose = None;
+                self.clear_input();
+                self.active_panel = Panel::Messages;
+                self.status_message = match compose {
+                    MailCompose::Forward => "Forwarded",
+                    _ => "Reply sent",
+                }
+                .to_string();
+            }
+            Err(e) => {
+                self.status_message = format!("Send failed: {}", e);
+            }
+        }
+        Ok(())
+    }
+
     pub async fn send_message(&mut self) -> Result<()> {
         if self.input.is_empty() {
             return Ok(());
         }
 
+        if let Some((compose, message_id)) = self.mail_compose.clone() {
+            return self.send_mail_compose(compose, &message_id).await;
+        }
+        // Never post to a chat that isn't on screen
+        if self.left_panel_view == LeftPanelView::Mail {
+            self.status_message = "Select a message, then r to reply or f to forward".to_string();
+            return Ok(());
+        }
+
         // Convert newlines to <br> for multi-line messages
         let escaped = html_escape(&self.input);
         let with_breaks = escaped.replace('\n', "<br>");
@@ -410,6 +878,26 @@ impl App {
         }
     }
 
+    pub fn next_folder(&mut self) {
+        if !self.mail_folders.is_empty() {
+            self.selected_folder = (self.selected_folder + 1).min(self.mail_folders.len() - 1);
+        }
+    }
+
+    pub fn previous_folder(&mut self) {
+        self.selected_folder = self.selected_folder.saturating_sub(1);
+    }
+
+    pub fn next_email(&mut self) {
+        if !self.emails.is_empty() {
+            self.selected_email = (self.selected_email + 1).min(self.emails.len() - 1);
+        }
+    }
+
+    pub fn previous_email(&mut self) {
+        self.selected_email = self.selected_email.saturating_sub(1);
+    }
+
     pub fn next_message(&mut self) {
         if !self.messages.is_empty() {
             self.selected_message = (self.selected_message + 1).min(self.messages.len() - 1);
@@ -595,7 +1083,17 @@ pub async fn run(config: &Config) -> Result<()> {
 
     // Create app
     let client = TeamsClient::new(config)?;
-    let app = Arc::new(Mutex::new(App::new(client)));
+    let mut app = App::new(client);
+    // Attachments go to the configured directory, else Downloads, else the current directory
+    app.download_dir = config
+        .tui
+        .download_dir
+        .clone()
+        .or_else(|| {
+            directories::UserDirs::new().and_then(|dirs| dirs.download_dir().map(Path::to_path_buf))
+        })
+        .unwrap_or_else(|| PathBuf::from("."));
+    let app = Arc::new(Mutex::new(app));
 
     // Initial data load
     {
@@ -639,14 +1137,22 @@ async fn run_app(
                     app.should_quit = true;
                 }
 
-                match app.mode {
-                    Mode::Normal => {
+                // Mail view keys come first, so `i` never opens the chat input there
+                let mail_action = if app.left_panel_view == LeftPanelView::Mail {
+                    mail_key(key.code, app.in_mail_list())
+                } else {
+                    None
+                };
+
+                match (app.mode, mail_action) {
+                    (Mode::Normal, Some(action)) => app.handle_mail_key(action).await?,
+                    (Mode::Normal, None) => {
                         match key.code {
                             KeyCode::Char('q') => app.should_quit = true,
                             KeyCode::Char('?') => {
-                                app.status_message = "j/k: navigate | Enter: select | 1: chats | 2: channels | i: compose | r: refresh | q: quit".to_string();
+                                app.status_message = "j/k: navigate | Enter: select | 1: chats | 2: channels | 3: mail | i: compose | r: refresh | q: quit".to_string();
                             }
-                            // View switching with 1 and 2
+                            // View switching with 1, 2 and 3
                             KeyCode::Char('1') => {
                                 app.left_panel_view = LeftPanelView::Chats;
                                 app.active_panel = Panel::Chats;
@@ -655,39 +1161,45 @@ async fn run_app(
                                 app.left_panel_view = LeftPanelView::Channels;
                                 app.active_panel = Panel::Chats;
                             }
-                            KeyCode::Char('j') | KeyCode::Down => match app.active_panel {
-                                Panel::Chats => {
-                                    if app.left_panel_view == LeftPanelView::Chats {
-                                        app.next_chat();
-                                    } else {
-                                        app.next_channel();
-                                    }
+                            KeyCode::Char('3') => {
+                                app.left_panel_view = LeftPanelView::Mail;
+                                app.active_panel = Panel::Chats;
+                                if app.mail_folders.is_empty() {
+                                    app.load_mail_folders().await?;
                                 }
+                            }
+                            KeyCode::Char('j') | KeyCode::Down => match app.active_panel {
+                                Panel::Chats => match app.left_panel_view {
+                                    LeftPanelView::Chats => app.next_chat(),
+                                    LeftPanelView::Channels => app.next_channel(),
+                                    LeftPanelView::Mail => app.next_folder(),
+                                },
+                                Panel::Messages if app.in_mail_list() => app.next_email(),
                                 Panel::Messages => app.next_message(),
                                 _ => {}
                             },
                             KeyCode::Char('k') | KeyCode::Up => match app.active_panel {
-                                Panel::Chats => {
-                                    if app.left_panel_view == LeftPanelView::Chats {
-                                        app.previous_chat();
-                                    } else {
-                                        app.previous_channel();
-                                    }
-                                }
+                                Panel::Chats => match app.left_panel_view {
+                                    LeftPanelView::Chats => app.previous_chat(),
+                                    LeftPanelView::Channels => app.previous_channel(),
+                                    LeftPanelView::Mail => app.previous_folder(),
+                                },
+                                Panel::Messages if app.in_mail_list() => app.previous_email(),
                                 Panel::Messages => app.previous_message(),
                                 _ => {}
                             },
                             KeyCode::Char('g') => {
                                 // Go to top
                                 match app.active_panel {
-                                    Panel::Chats => {
-                                        if app.left_panel_view == LeftPanelView::Chats {
-                                            app.selected_chat = 0;
-                                        } else {
+                                    Panel::Chats => match app.left_panel_view {
+                                        LeftPanelView::Chats => app.selected_chat = 0,
+                                        LeftPanelView::Channels => {
                                             app.selected_team = 0;
                                             app.selected_channel = 0;
                                         }
-                                    }
+                                        LeftPanelView::Mail => app.selected_folder = 0,
+                                    },
+                                    Panel::Messages if app.in_mail_list() => app.selected_email = 0,
                                     Panel::Messages => app.selected_message = 0,
                                     _ => {}
                                 }
@@ -695,10 +1207,11 @@ async fn run_app(
                             KeyCode::Char('G') => {
                                 // Go to bottom
                                 match app.active_panel {
-                                    Panel::Chats => {
-                                        if app.left_panel_view == LeftPanelView::Chats {
+                                    Panel::Chats => match app.left_panel_view {
+                                        LeftPanelView::Chats => {
                                             app.selected_chat = app.chats.len().saturating_sub(1);
-                                        } else if !app.teams.is_empty() {
+                                        }
+                                        LeftPanelView::Channels if !app.teams.is_empty() => {
                                             let last_team_idx = app.teams.len() - 1;
                                             let last_channel_idx = app.teams[last_team_idx]
                                                 .channels
@@ -707,6 +1220,14 @@ async fn run_app(
                                             app.selected_team = last_team_idx;
                                             app.selected_channel = last_channel_idx;
                                         }
+                                        LeftPanelView::Channels => {}
+                                        LeftPanelView::Mail => {
+                                            app.selected_folder =
+                                                app.mail_folders.len().saturating_sub(1);
+                                        }
+                                    },
+                                    Panel::Messages if app.in_mail_list() => {
+                                        app.selected_email = app.emails.len().saturating_sub(1)
                                     }
                                     Panel::Messages => {
                                         app.selected_message = app.messages.len().saturating_sub(1)
@@ -730,11 +1251,15 @@ async fn run_app(
                             KeyCode::Enter => {
                                 if app.active_panel == Panel::Chats {
                                     app.active_panel = Panel::Messages;
-                                    if app.left_panel_view == LeftPanelView::Chats {
-                                        app.load_messages().await?;
-                                    } else {
-                                        app.load_channel_messages().await?;
+                                    match app.left_panel_view {
+                                        LeftPanelView::Chats => app.load_messages().await?,
+                                        LeftPanelView::Channels => {
+                                            app.load_channel_messages().await?
+                                        }
+                                        LeftPanelView::Mail => app.load_mail_messages().await?,
                                     }
+                                } else if app.in_mail_list() {
+                                    app.read_email().await?;
                                 }
                             }
                             KeyCode::Char('i') => {
@@ -760,10 +1285,14 @@ async fn run_app(
                             _ => {}
                         }
                     }
-                    Mode::Insert => {
+                    (Mode::Insert, _) => {
                         match key.code {
                             KeyCode::Esc => {
                                 app.mode = Mode::Normal;
+                                if app.mail_compose.take().is_some() {
+                                    app.clear_input();
+                                    app.active_panel = Panel::Messages;
+                                }
                                 app.status_message = "Press ? for help".to_string();
                             }
                             // Multiple ways to insert newline:
@@ -858,7 +1387,7 @@ async fn run_app(
                             _ => {}
                         }
                     }
-                    Mode::Command => match key.code {
+                    (Mode::Command, _) => match key.code {
                         KeyCode::Esc => {
                             app.mode = Mode::Normal;
                             app.command_input.clear();
@@ -875,6 +1404,11 @@ async fn run_app(
                                     app.load_data().await?;
                                 }
                                 "mail" | "m" => {
+                                    app.left_panel_view = LeftPanelView::Mail;
+                                    app.active_panel = Panel::Chats;
+                                    if app.mail_folders.is_empty() {
+                                        app.load_mail_folders().await?;
+                                    }
                                     app.status_message =
                                         format!("{} unread emails", app.unread_emails);
                                 }
@@ -904,3 +1438,60 @@ async fn run_app(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mail_key() {
+        // `i` replies on the message list and never opens the chat input elsewhere
+        assert_eq!(
+            mail_key(KeyCode::Char('i'), true),
+            Some(MailKey::Compose(MailCompose::Reply))
+        );
+        assert_eq!(
+            mail_key(KeyCode::Char('i'), false),
+            Some(MailKey::NothingToCompose)
+        );
+        assert_eq!(mail_key(KeyCode::Char('r'), false), Some(MailKey::Reload));
+        assert_eq!(
+            mail_key(KeyCode::Char('R'), true),
+            Some(MailKey::Compose(MailCompose::ReplyAll))
+        );
+        assert_eq!(mail_key(KeyCode::Char('a'), true), Some(MailKey::Archive));
+        assert_eq!(mail_key(KeyCode::Char('a'), false), None);
+        assert_eq!(
+            mail_key(KeyCode::Char(' '), false),
+            Some(MailKey::ScrollDown)
+        );
+        assert_eq!(mail_key(KeyCode::Char('j'), true), None);
+        assert_eq!(mail_key(KeyCode::Char('2'), true), None);
+    }
+
+    #[test]
+    fn test_attachment_path() {
+        let dir = std::env::temp_dir().join(format!("squads-tui-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            attachment_path(&dir, "../../.bashrc", "x"),
+            dir.join(".bashrc")
+        );
+        assert_eq!(
+            attachment_path(&dir, "C:\\Users\\evil\\run.bat", "x"),
+            dir.join("run.bat")
+        );
+        assert_eq!(attachment_path(&dir, "..", "AAMk"), dir.join("AAMk"));
+        assert_eq!(attachment_path(&dir, "dir/", "AAMk"), dir.join("AAMk"));
+
+        std::fs::write(dir.join("report.pdf"), b"first").unwrap();
+        std::fs::write(dir.join("report (1).pdf"), b"second").unwrap();
+        assert_eq!(
+            attachment_path(&dir, "report.pdf", "x"),
+            dir.join("report (2).pdf")
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -6,7 +6,10 @@ use ratatui::{
     Frame,
 };
 
-use super::app::{App, LeftPanelView, Mode, Panel};
+use super::app::{App, LeftPanelView, MailCompose, Mode, Panel};
+use crate::cli::cards::render_cards;
+use crate::cli::utils::html_to_text;
+use crate::types::{MailMessage, Recipient};
 
 pub fn draw(f: &mut Frame, app: &App) {
     // Calculate input height based on content (min 3, max 10)
@@ -134,10 +137,254 @@ fn draw_chats(f: &mut Frame, app: &App, area: Rect) {
 
             f.render_widget(channels, area);
         }
+        LeftPanelView::Mail => {
+            let items: Vec<ListItem> = app
+                .mail_folders
+                .iter()
+                .enumerate()
+                .map(|(i, (folder, depth))| {
+                    let unread = folder.unread_item_count.unwrap_or(0);
+                    let label = if unread > 0 {
+                        format!("{} ({})", folder.display_name, unread)
+                    } else {
+                        folder.display_name.clone()
+                    };
+                    let display = format!(
+                        "{}{}",
+                        "  ".repeat(*depth),
+                        truncate(&label, max_name_width.saturating_sub(depth * 2))
+                    );
+
+                    let style = if i == app.selected_folder && is_active {
+                        Style::default()
+                            .bg(Color::Cyan)
+                            .fg(Color::Black)
+                            .add_modifier(Modifier::BOLD)
+                    } else if i == app.selected_folder {
+                        Style::default().bg(Color::DarkGray).fg(Color::White)
+                    } else if unread > 0 {
+                        Style::default().fg(Color::Yellow)
+                    } else {
+                        Style::default()
+                    };
+
+                    ListItem::new(display).style(style)
+                })
+                .collect();
+
+            let mut list_state = ListState::default();
+            list_state.select(Some(app.selected_folder));
+
+            let title = format!(" [3] Mail ({}) ", app.mail_folders.len());
+            let folders = List::new(items).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(border_style)
+                    .title(title),
+            );
+
+            f.render_stateful_widget(folders, area, &mut list_state);
+        }
     }
 }
 
+/// Message list above, reading pane below
+fn draw_mail(f: &mut Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(area);
+
+    draw_mail_list(f, app, chunks[0]);
+    draw_mail_reader(f, app, chunks[1]);
+}
+
+fn draw_mail_list(f: &mut Frame, app: &App, area: Rect) {
+    let is_active = app.active_panel == Panel::Messages;
+    let border_style = if is_active {
+        Style::default().fg(Color::Cyan)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(border_style)
+        .title(format!(
+            " {} ({}) ",
+            truncate(&app.current_folder_name, 30),
+            app.emails.len()
+        ));
+
+    if app.emails.is_empty() {
+        let empty = Paragraph::new("No messages. Press Enter on a folder to load it.")
+            .style(Style::default().fg(Color::DarkGray))
+            .block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    // Date (11) + sender (20) + spacing
+    let subject_width = area.width.saturating_sub(4 + 11 + 21 + 2) as usize;
+
+    let items: Vec<ListItem> = app
+        .emails
+        .iter()
+        .enumerate()
+        .map(|(i, msg)| {
+            let unread = msg.is_read != Some(true);
+            let marker = if unread { "● " } else { "  " };
+            let from = msg
+                .from
+                .as_ref()
+                .map(|r| {
+                    r.email_address
+                        .name
+                        .clone()
+                        .unwrap_or_else(|| r.email_address.address.clone())
+                })
+                .unwrap_or_default();
+            let subject = msg.subject.clone().unwrap_or_default();
+
+            let style = if i == app.selected_email && is_active {
+                Style::default().bg(Color::DarkGray)
+            } else {
+                Style::default()
+            };
+            let text_style = if unread {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+
+            ListItem::new(Line::from(vec![
+                Span::styled(marker, Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    format!("{:<11} ", short_date(msg.received_date_time.as_deref())),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(
+                    format!("{:<20} ", truncate(&from, 20)),
+                    Style::default().fg(Color::Blue),
+                ),
+                Span::styled(truncate(&subject, subject_width.max(10)), text_style),
+            ]))
+            .style(style)
+        })
+        .collect();
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(app.selected_email));
+
+    let list = List::new(items).block(block);
+    f.render_stateful_widget(list, area, &mut list_state);
+}
+
+fn draw_mail_reader(f: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+
+    let Some(message) = &app.open_email else {
+        let empty = Paragraph::new("Press Enter on a message to read it.")
+            .style(Style::default().fg(Color::DarkGray))
+            .block(block.title(" Reading pane "));
+        f.render_widget(empty, area);
+        return;
+    };
+
+    let label = Style::default().fg(Color::DarkGray);
+    let mut lines: Vec<Line> = vec![
+        Line::from(vec![
+            Span::styled("From: ", label),
+            Span::raw(message.from.as_ref().map(recipient).unwrap_or_default()),
+        ]),
+        Line::from(vec![
+            Span::styled("To: ", label),
+            Span::raw(recipients(&message.to_recipients)),
+        ]),
+    ];
+    if message
+        .cc_recipients
+        .as_ref()
+        .is_some_and(|cc| !cc.is_empty())
+    {
+        lines.push(Line::from(vec![
+            Span::styled("Cc: ", label),
+            Span::raw(recipients(&message.cc_recipients)),
+        ]));
+    }
+    lines.push(Line::from(vec![
+        Span::styled("Date: ", label),
+        Span::raw(message.received_date_time.clone().unwrap_or_default()),
+    ]));
+
+    let attachments: Vec<String> = app
+        .open_attachments
+        .iter()
+        .filter(|a| a.is_inline != Some(true))
+        .map(|a| format!("{} ({} KB)", a.name, (a.size.unwrap_or(0) + 1023) / 1024))
+        .collect();
+    if !attachments.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled("Attachments: ", label),
+            Span::styled(attachments.join(", "), Style::default().fg(Color::Magenta)),
+            Span::styled("  (s: save)", label),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.extend(
+        body_text(message)
+            .lines()
+            .map(|l| Line::from(l.to_string())),
+    );
+
+    let title = message.subject.clone().unwrap_or_default();
+    let reader = Paragraph::new(lines)
+        .block(block.title(format!(" {} ", truncate(&title, 60))))
+        .wrap(Wrap { trim: false })
+        .scroll((app.mail_scroll, 0));
+
+    f.render_widget(reader, area);
+}
+
+fn body_text(message: &MailMessage) -> String {
+    match &message.body {
+        Some(body) if body.content_type.eq_ignore_ascii_case("text") => body.content.clone(),
+        Some(body) => html_to_text(&body.content),
+        None => message.body_preview.clone().unwrap_or_default(),
+    }
+}
+
+fn recipient(r: &Recipient) -> String {
+    match &r.email_address.name {
+        Some(name) if !name.is_empty() => format!("{} <{}>", name, r.email_address.address),
+        _ => r.email_address.address.clone(),
+    }
+}
+
+fn recipients(list: &Option<Vec<Recipient>>) -> String {
+    list.iter()
+        .flatten()
+        .map(recipient)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// "2026-10-18T09:30:00Z" as "10-18 09:30"
+fn short_date(date: Option<&str>) -> String {
+    date.and_then(|d| d.get(5..16))
+        .map(|d| d.replace('T', " "))
+        .unwrap_or_default()
+}
+
 fn draw_messages(f: &mut Frame, app: &App, area: Rect) {
+    if app.left_panel_view == LeftPanelView::Mail {
+        draw_mail(f, app, area);
+        return;
+    }
+
     let is_active = app.active_panel == Panel::Messages;
     let border_style = if is_active {
         Style::default().fg(Color::Cyan)
@@ -242,6 +489,17 @@ fn draw_messages(f: &mut Frame, app: &App, area: Rect) {
                 }
             }
 
+            // Bot/workflow cards rendered as structured text below the message
+            for card_line in render_cards(&msg.properties) {
+                lines.push(Line::from(vec![
+                    Span::raw("                         "),
+                    Span::styled(
+                        truncate(&card_line, msg_width.max(20)),
+                        Style::default().fg(Color::Magenta),
+                    ),
+                ]));
+            }
+
             ListItem::new(lines).style(style)
         })
         .collect();
@@ -306,7 +564,18 @@ fn draw_input(f: &mut Frame, app: &App, area: Rect) {
     };
 
     let input_title = match app.mode {
-        Mode::Insert => " Compose (Enter: send, Shift+Enter: newline, Esc: cancel) ",
+        Mode::Insert => match app.mail_compose {
+            Some((MailCompose::Reply, _)) => {
+                " Reply (Enter: send, Shift+Enter: newline, Esc: cancel) "
+            }
+            Some((MailCompose::ReplyAll, _)) => {
+                " Reply all (Enter: send, Shift+Enter: newline, Esc: cancel) "
+            }
+            Some((MailCompose::Forward, _)) => {
+                " Forward: recipients on the first line, then a comment (Enter: send, Esc: cancel) "
+            }
+            None => " Compose (Enter: send, Shift+Enter: newline, Esc: cancel) ",
+        },
         Mode::Command => " Command ",
         Mode::Normal => " Press 'i' to compose ",
     };
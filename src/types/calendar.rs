@@ -19,6 +19,59 @@ pub struct CalendarEvent {
     pub response_status: Option<ResponseStatus>,
     pub is_cancelled: Option<bool>,
     pub is_all_day: Option<bool>,
+    /// free, tentative, busy, oof, workingElsewhere or unknown
+    pub show_as: Option<String>,
+    /// singleInstance, occurrence, exception or seriesMaster
+    #[serde(rename = "type")]
+    pub event_type: Option<String>,
+    pub series_master_id: Option<String>,
+    pub recurrence: Option<PatternedRecurrence>,
+    #[serde(rename = "iCalUId")]
+    pub i_cal_u_id: Option<String>,
+}
+
+/// Recurrence of an event series
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PatternedRecurrence {
+    pub pattern: RecurrencePattern,
+    pub range: RecurrenceRange,
+}
+
+/// How often a series repeats
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurrencePattern {
+    /// daily, weekly, absoluteMonthly, relativeMonthly, absoluteYearly or relativeYearly
+    #[serde(rename = "type")]
+    pub pattern_type: String,
+    pub interval: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days_of_week: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub day_of_month: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub month: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_day_of_week: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<String>,
+}
+
+/// When a series stops
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurrenceRange {
+    /// endDate, noEnd or numbered
+    #[serde(rename = "type")]
+    pub range_type: String,
+    pub start_date: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number_of_occurrences: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recurrence_time_zone: Option<String>,
 }
 
 /// Date time with timezone
@@ -77,6 +130,66 @@ pub struct OnlineMeeting {
     pub join_url: Option<String>,
 }
 
+/// Teams online meeting resource (Graph `onlineMeetings`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnlineMeetingInfo {
+    pub id: String,
+    pub subject: Option<String>,
+    pub join_web_url: Option<String>,
+    pub start_date_time: Option<String>,
+    pub end_date_time: Option<String>,
+    pub chat_info: Option<MeetingChatInfo>,
+}
+
+/// Chat thread attached to an online meeting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MeetingChatInfo {
+    pub thread_id: Option<String>,
+    pub message_id: Option<String>,
+}
+
+/// Online meetings list response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnlineMeetings {
+    pub value: Vec<OnlineMeetingInfo>,
+}
+
+/// Transcript of an online meeting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MeetingTranscript {
+    pub id: String,
+    pub meeting_id: Option<String>,
+    pub created_date_time: Option<String>,
+    pub end_date_time: Option<String>,
+    pub transcript_content_url: Option<String>,
+}
+
+/// Meeting transcripts list response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingTranscripts {
+    pub value: Vec<MeetingTranscript>,
+}
+
+/// Recording of an online meeting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MeetingRecording {
+    pub id: String,
+    pub meeting_id: Option<String>,
+    pub created_date_time: Option<String>,
+    pub end_date_time: Option<String>,
+    pub recording_content_url: Option<String>,
+}
+
+/// Meeting recordings list response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingRecordings {
+    pub value: Vec<MeetingRecording>,
+}
+
 /// Calendar events list response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CalendarEvents {
@@ -124,6 +237,44 @@ pub struct CreateEventRequest {
     pub is_online_meeting: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub online_meeting_provider: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<PatternedRecurrence>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_all_day: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub single_value_extended_properties: Option<Vec<SingleValueExtendedProperty>>,
+}
+
+/// Extended property storing the UID of an event imported from an .ics file
+pub const ICS_UID_PROPERTY: &str = "String {3d4a7c52-9b1e-4f0a-8c6d-5e2f1b7a9c30} Name IcsUid";
+
+/// Custom single-value property attached to an event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SingleValueExtendedProperty {
+    pub id: String,
+    pub value: String,
+}
+
+/// Partial event update (PATCH); only set fields are sent
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateEventRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<DateTimeZone>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<DateTimeZone>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<EventBody>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<Location>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attendees: Option<Vec<AttendeeRequest>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_online_meeting: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub online_meeting_provider: Option<String>,
 }
 
 /// Event body for creation
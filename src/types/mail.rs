@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
 
+use super::DateTimeZone;
+
 /// Email address with optional name
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EmailAddress {
     pub address: String,
@@ -9,7 +11,7 @@ pub struct EmailAddress {
 }
 
 /// Recipient wrapper
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Recipient {
     pub email_address: EmailAddress,
@@ -34,6 +36,7 @@ pub struct MailMessage {
     pub from: Option<Recipient>,
     pub to_recipients: Option<Vec<Recipient>>,
     pub cc_recipients: Option<Vec<Recipient>>,
+    pub bcc_recipients: Option<Vec<Recipient>>,
     pub received_date_time: Option<String>,
     pub sent_date_time: Option<String>,
     pub is_read: Option<bool>,
@@ -41,6 +44,50 @@ pub struct MailMessage {
     pub has_attachments: Option<bool>,
     pub importance: Option<String>,
     pub web_link: Option<String>,
+    pub flag: Option<FollowupFlag>,
+    pub categories: Option<Vec<String>>,
+    pub conversation_id: Option<String>,
+    pub internet_message_id: Option<String>,
+    /// Message-ID this message replies to; Graph has no such property, so it
+    /// is filled from the `In-Reply-To` header when headers are requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_reply_to: Option<String>,
+    /// Body without the quoted history of earlier messages
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unique_body: Option<ItemBody>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub internet_message_headers: Option<Vec<InternetMessageHeader>>,
+    /// Set on delta query entries for messages deleted or moved out of the folder
+    #[serde(rename = "@removed", default, skip_serializing_if = "Option::is_none")]
+    pub removed: Option<RemovedMarker>,
+}
+
+/// Why a delta query entry was removed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemovedMarker {
+    pub reason: Option<String>,
+}
+
+/// Follow-up flag on a message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FollowupFlag {
+    /// notFlagged, flagged or complete
+    pub flag_status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date_time: Option<DateTimeZone>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_date_time: Option<DateTimeZone>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_date_time: Option<DateTimeZone>,
+}
+
+/// Raw internet message header
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InternetMessageHeader {
+    pub name: String,
+    pub value: String,
 }
 
 /// Mail messages list response
@@ -50,9 +97,210 @@ pub struct MailMessages {
     pub context: Option<String>,
     #[serde(rename = "@odata.nextLink")]
     pub next_link: Option<String>,
+    #[serde(rename = "@odata.deltaLink")]
+    pub delta_link: Option<String>,
     pub value: Vec<MailMessage>,
 }
 
+/// Inbox rule (Graph `messageRule`); only set fields are sent
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageRule {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// Order in which rules run, lowest first
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_error: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_read_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conditions: Option<MessageRulePredicates>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exceptions: Option<MessageRulePredicates>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actions: Option<MessageRuleActions>,
+}
+
+impl MessageRule {
+    /// The rule without server-managed fields, as sent on create/update and exported
+    pub fn desired(&self) -> MessageRule {
+        MessageRule {
+            id: None,
+            has_error: None,
+            is_read_only: None,
+            ..self.clone()
+        }
+    }
+}
+
+/// Rules list response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageRules {
+    #[serde(rename = "@odata.context")]
+    pub context: Option<String>,
+    pub value: Vec<MessageRule>,
+}
+
+/// Conditions (or exceptions) of an inbox rule
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageRulePredicates {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_contains: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_or_subject_contains: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub categories: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_addresses: Option<Vec<Recipient>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_attachments: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header_contains: Option<Vec<String>>,
+    /// low, normal or high
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub importance: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_automatic_reply: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_meeting_request: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not_sent_to_me: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipient_contains: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender_contains: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sent_cc_me: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sent_only_to_me: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sent_to_addresses: Option<Vec<Recipient>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sent_to_me: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sent_to_or_cc_me: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject_contains: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub within_size_range: Option<SizeRange>,
+}
+
+/// Message size bounds in kilobytes
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeRange {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum_size: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum_size: Option<i32>,
+}
+
+/// What an inbox rule does with matching messages
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageRuleActions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assign_categories: Option<Vec<String>>,
+    /// Folder ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub copy_to_folder: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forward_as_attachment_to: Option<Vec<Recipient>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forward_to: Option<Vec<Recipient>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mark_as_read: Option<bool>,
+    /// low, normal or high
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mark_importance: Option<String>,
+    /// Folder ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub move_to_folder: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permanent_delete: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redirect_to: Option<Vec<Recipient>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_processing_rules: Option<bool>,
+}
+
+/// Structured mail query, rendered as a Graph `$filter`
+#[derive(Debug, Clone, Default)]
+pub struct MailFilter {
+    pub unread: bool,
+    /// Sender address
+    pub from: Option<String>,
+    /// Received at or after (Graph UTC date-time)
+    pub since: Option<String>,
+    /// Received before (Graph UTC date-time)
+    pub until: Option<String>,
+    pub flagged: bool,
+    pub has_attachments: bool,
+    /// low, normal or high
+    pub importance: Option<String>,
+    pub category: Option<String>,
+}
+
+impl MailFilter {
+    /// Render as an OData `$filter` expression, or `None` when nothing is set.
+    ///
+    /// Graph rejects filters that do not start with the `$orderby` property, so a
+    /// `receivedDateTime` bound always comes first.
+    pub fn to_odata(&self) -> Option<String> {
+        let mut terms = Vec::new();
+        if self.unread {
+            terms.push("isRead eq false".to_string());
+        }
+        if let Some(from) = &self.from {
+            terms.push(format!(
+                "from/emailAddress/address eq {}",
+                odata_string(from)
+            ));
+        }
+        if let Some(until) = &self.until {
+            terms.push(format!("receivedDateTime lt {}", until));
+        }
+        if self.flagged {
+            terms.push("flag/flagStatus eq 'flagged'".to_string());
+        }
+        if self.has_attachments {
+            terms.push("hasAttachments eq true".to_string());
+        }
+        if let Some(importance) = &self.importance {
+            terms.push(format!("importance eq {}", odata_string(importance)));
+        }
+        if let Some(category) = &self.category {
+            terms.push(format!("categories/any(c:c eq {})", odata_string(category)));
+        }
+
+        if terms.is_empty() && self.since.is_none() {
+            return None;
+        }
+        let since = self.since.as_deref().unwrap_or("1900-01-01T00:00:00Z");
+        terms.insert(0, format!("receivedDateTime ge {}", since));
+        Some(terms.join(" and "))
+    }
+}
+
+/// Quote a string literal for an OData expression
+pub fn odata_string(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Quote a free-text query for `$search`
+pub fn search_phrase(query: &str) -> String {
+    format!("\"{}\"", query.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
 /// Mail folder
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -70,6 +318,8 @@ pub struct MailFolder {
 pub struct MailFolders {
     #[serde(rename = "@odata.context")]
     pub context: Option<String>,
+    #[serde(rename = "@odata.nextLink")]
+    pub next_link: Option<String>,
     pub value: Vec<MailFolder>,
 }
 
@@ -90,6 +340,10 @@ pub struct SendMailMessage {
     pub to_recipients: Vec<Recipient>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cc_recipients: Option<Vec<Recipient>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<Recipient>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender: Option<Recipient>,
 }
 
 /// Request to create a draft message
@@ -101,6 +355,10 @@ pub struct CreateDraftRequest {
     pub to_recipients: Vec<Recipient>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cc_recipients: Option<Vec<Recipient>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<Recipient>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender: Option<Recipient>,
 }
 
 /// Email attachment
@@ -122,3 +380,91 @@ pub struct MailAttachments {
     pub context: Option<String>,
     pub value: Vec<MailAttachment>,
 }
+
+/// Mailbox settings (Graph `mailboxSettings`); only set fields are sent on update
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MailboxSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub automatic_replies_setting: Option<AutomaticRepliesSetting>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_zone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<LocaleInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_hours: Option<MailboxWorkingHours>,
+}
+
+/// Automatic replies (out of office)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomaticRepliesSetting {
+    /// disabled, alwaysEnabled or scheduled
+    pub status: String,
+    /// none, contactsOnly or all
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_audience: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheduled_start_date_time: Option<DateTimeZone>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheduled_end_date_time: Option<DateTimeZone>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub internal_reply_message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_reply_message: Option<String>,
+}
+
+/// Mailbox language
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocaleInfo {
+    pub locale: Option<String>,
+    pub display_name: Option<String>,
+}
+
+/// Working days and hours of the mailbox owner
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MailboxWorkingHours {
+    #[serde(default)]
+    pub days_of_week: Vec<String>,
+    pub start_time: Option<String>,
+    pub end_time: Option<String>,
+    pub time_zone: Option<TimeZoneBase>,
+}
+
+/// Time zone name as used by mailbox settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeZoneBase {
+    pub name: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mail_filter_odata() {
+        assert_eq!(MailFilter::default().to_odata(), None);
+
+        let filter = MailFilter {
+            unread: true,
+            from: Some("o'brien@example.com".into()),
+            until: Some("2026-10-08T00:00:00Z".into()),
+            category: Some("Project X".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            filter.to_odata().unwrap(),
+            "receivedDateTime ge 1900-01-01T00:00:00Z and isRead eq false and \
+             from/emailAddress/address eq 'o''brien@example.com' and \
+             receivedDateTime lt 2026-10-08T00:00:00Z and categories/any(c:c eq 'Project X')"
+        );
+        assert_eq!(search_phrase(r#"say "hi""#), r#""say \"hi\"""#);
+    }
+}
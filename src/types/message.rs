@@ -50,17 +50,94 @@ pub struct Emotion {
     pub users: Vec<EmotionUser>,
 }
 
-/// Card button
+/// Card button (hero/thumbnail cards)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CardContentButton {
     #[serde(rename = "type")]
     pub button_type: String,
     pub title: String,
+    #[serde(default)]
+    pub value: Option<Value>,
+}
+
+/// Fact in an Adaptive Card FactSet
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardFact {
+    #[serde(default)]
+    pub title: String,
+    /// Cards sometimes carry numbers or booleans here
+    #[serde(default, deserialize_with = "value_to_string")]
+    pub value: String,
+}
+
+/// Choice in an Adaptive Card Input.ChoiceSet
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardChoice {
+    #[serde(default)]
+    pub title: String,
+    /// Cards sometimes carry numbers or booleans here
+    #[serde(default, deserialize_with = "value_to_string")]
     pub value: String,
 }
 
-/// Card content
+/// Adaptive Card action (Action.Submit, Action.OpenUrl, Action.Execute, Action.ShowCard)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardAction {
+    #[serde(rename = "type")]
+    pub action_type: String,
+    pub id: Option<String>,
+    pub title: Option<String>,
+    pub url: Option<String>,
+    pub verb: Option<String>,
+    pub data: Option<Value>,
+    pub card: Option<Box<CardContent>>,
+}
+
+/// Adaptive Card element (TextBlock, FactSet, Container, ColumnSet, Input.*, ActionSet...)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardElement {
+    #[serde(rename = "type", default)]
+    pub element_type: String,
+    pub id: Option<String>,
+    pub text: Option<String>,
+    pub title: Option<String>,
+    pub label: Option<String>,
+    pub placeholder: Option<String>,
+    pub value: Option<Value>,
+    pub is_required: Option<bool>,
+    pub url: Option<String>,
+    pub alt_text: Option<String>,
+    pub facts: Option<Vec<CardFact>>,
+    pub choices: Option<Vec<CardChoice>>,
+    pub items: Option<Vec<CardItem>>,
+    #[serde(default)]
+    pub columns: Option<Vec<CardItem>>,
+    pub actions: Option<Vec<CardAction>>,
+}
+
+/// Entry in a card body: a modelled element, or the raw JSON of a shape we don't know
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CardItem {
+    Element(Box<CardElement>),
+    Unknown(Value),
+}
+
+impl CardItem {
+    pub fn as_element(&self) -> Option<&CardElement> {
+        match self {
+            CardItem::Element(element) => Some(element),
+            CardItem::Unknown(_) => None,
+        }
+    }
+}
+
+/// Card content (hero card fields plus the Adaptive Card body/actions)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CardContent {
@@ -68,6 +145,11 @@ pub struct CardContent {
     pub component_url: Option<String>,
     pub source_type: Option<String>,
     pub buttons: Option<Vec<CardContentButton>>,
+    pub title: Option<String>,
+    pub subtitle: Option<String>,
+    pub version: Option<String>,
+    pub body: Option<Vec<CardItem>>,
+    pub actions: Option<Vec<CardAction>>,
 }
 
 /// Card attachment
@@ -77,6 +159,7 @@ pub struct Card {
     pub app_id: Option<String>,
     pub app_name: Option<String>,
     pub app_icon: Option<String>,
+    #[serde(default)]
     pub card_client_id: String,
     pub content: CardContent,
     pub content_type: String,
@@ -154,6 +237,18 @@ where
     }
 }
 
+fn value_to_string<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<Value>::deserialize(deserializer)?;
+    match value {
+        Some(Value::String(s)) => Ok(s),
+        None | Some(Value::Null) => Ok(String::new()),
+        Some(other) => Ok(other.to_string()),
+    }
+}
+
 fn string_to_bool_opt<'de, D>(deserializer: D) -> Result<bool, D::Error>
 where
     D: Deserializer<'de>,
@@ -191,17 +286,22 @@ where
     }
 }
 
+/// Cards come from bots and apps, so a shape the model can't read drops that card
+/// instead of failing the whole message
 fn deserialize_cards<'de, D>(deserializer: D) -> Result<Option<Vec<Card>>, D::Error>
 where
     D: Deserializer<'de>,
 {
     let s: Option<String> = Option::deserialize(deserializer)?;
-    match s {
-        Some(json_str) if json_str != "[]" => serde_json::from_str(&json_str)
-            .map(Some)
-            .map_err(serde::de::Error::custom),
-        _ => Ok(None),
-    }
+    let Some(json_str) = s else {
+        return Ok(None);
+    };
+    let cards: Vec<Card> = serde_json::from_str::<Vec<Value>>(&json_str)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|card| serde_json::from_value(card).ok())
+        .collect();
+    Ok((!cards.is_empty()).then_some(cards))
 }
 
 /// Chat/Team message
@@ -245,6 +345,16 @@ where
 #[serde(rename_all = "camelCase")]
 pub struct Conversations {
     pub messages: Vec<Message>,
+    #[serde(rename = "_metadata", default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ConversationsMetadata>,
+}
+
+/// Paging links of a conversation page
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationsMetadata {
+    /// Link to the page of older messages
+    pub backward_link: Option<String>,
 }
 
 /// Message to send
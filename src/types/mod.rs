@@ -65,6 +65,71 @@ impl TokenStore {
     }
 }
 
+/// One request inside a Graph JSON `$batch`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchRequest {
+    pub id: String,
+    pub method: String,
+    /// Path relative to the API version, e.g. `/me/messages/{id}`
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<std::collections::HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+}
+
+impl BatchRequest {
+    pub fn new(id: impl Into<String>, method: &str, url: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            method: method.to_string(),
+            url: url.into(),
+            headers: None,
+            body: None,
+        }
+    }
+
+    /// Attach a JSON body (and the content type Graph requires with it)
+    pub fn json(mut self, body: Value) -> Self {
+        self.headers = Some(std::collections::HashMap::from([(
+            "Content-Type".to_string(),
+            "application/json".to_string(),
+        )]));
+        self.body = Some(body);
+        self
+    }
+}
+
+/// One response from a Graph JSON `$batch`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchResponse {
+    pub id: String,
+    pub status: u16,
+    #[serde(default)]
+    pub headers: std::collections::HashMap<String, String>,
+    pub body: Option<Value>,
+}
+
+impl BatchResponse {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Graph error message carried in the body, if any
+    pub fn error_message(&self) -> Option<String> {
+        self.body
+            .as_ref()
+            .and_then(|b| b["error"]["message"].as_str())
+            .map(String::from)
+    }
+}
+
+/// Graph JSON `$batch` response envelope
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchResponses {
+    pub responses: Vec<BatchResponse>,
+}
+
 // Helper deserializers (ported from Squads)
 
 /// Strip URL prefix from contact IDs
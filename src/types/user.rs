@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use super::{DateTimeZone, EmailAddress};
+
 /// User profile from Microsoft Graph
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -118,7 +120,8 @@ pub struct GraphPresence {
 #[serde(rename_all = "camelCase")]
 pub struct PresenceStatusMessage {
     pub message: Option<PresenceMessageContent>,
-    pub expiry_date_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiry_date_time: Option<DateTimeZone>,
 }
 
 /// Presence message content
@@ -134,3 +137,100 @@ pub struct PresenceMessageContent {
 pub struct GraphPresences {
     pub value: Vec<GraphPresence>,
 }
+
+/// Request body for `setUserPreferredPresence` and `setPresence`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetPresenceRequest {
+    /// Application session; only used by `setPresence`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    pub availability: String,
+    pub activity: String,
+    /// ISO 8601 duration, e.g. PT1H
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration_duration: Option<String>,
+}
+
+/// Outlook contact from Microsoft Graph
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Contact {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub given_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub surname: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub email_addresses: Vec<EmailAddress>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub business_phones: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mobile_phone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub company_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub personal_notes: Option<String>,
+}
+
+/// Contacts list response from Graph API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Contacts {
+    #[serde(rename = "@odata.nextLink")]
+    pub next_link: Option<String>,
+    pub value: Vec<Contact>,
+}
+
+/// Person from the relevance-ranked `/me/people` API
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Person {
+    pub id: Option<String>,
+    pub display_name: Option<String>,
+    pub job_title: Option<String>,
+    pub department: Option<String>,
+    pub company_name: Option<String>,
+    pub user_principal_name: Option<String>,
+    #[serde(default)]
+    pub scored_email_addresses: Vec<ScoredEmailAddress>,
+    pub person_type: Option<PersonType>,
+}
+
+impl Person {
+    /// Best-ranked email address
+    pub fn email(&self) -> Option<&str> {
+        self.scored_email_addresses
+            .first()
+            .and_then(|e| e.address.as_deref())
+            .or(self.user_principal_name.as_deref())
+    }
+}
+
+/// Email address of a person, with its relevance score
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoredEmailAddress {
+    pub address: Option<String>,
+    pub relevance_score: Option<f64>,
+}
+
+/// Kind of person, e.g. Person/OrganizationUser or Group/UnifiedGroup
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersonType {
+    pub class: Option<String>,
+    pub subclass: Option<String>,
+}
+
+/// People list response from Graph API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct People {
+    #[serde(rename = "@odata.nextLink")]
+    pub next_link: Option<String>,
+    pub value: Vec<Person>,
+}